@@ -1,16 +1,88 @@
-use std::time::Instant;
+use std::collections::HashSet;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
 
 use minifb::{Key, Window, WindowOptions};
 
+use crate::assets::AssetManager;
 use crate::camera::Camera;
 use crate::collision;
+use crate::flight::FlightModel;
+use crate::hotreload::FileWatcher;
 use crate::input::InputState;
-use crate::math::Vec3;
-use crate::renderer::Renderer;
-use crate::skybox;
+use crate::math::rng::seed_from_str;
+use crate::math::{Mat4, Vec3};
+use crate::mesh::{procgen, Mesh};
+use crate::renderer::color;
+use crate::renderer::shader::{self, SurfaceShader};
+use crate::renderer::{BodyTextures, Light, Renderer};
+use crate::ship::{Ship, CHASE_OFFSET, SHIP_RADIUS};
+use crate::skybox::{self, Cubemap, Sky, SkyFormat, SkyRays, StarCatalog};
+use crate::skybox::cubemap::FACE_NAMES;
+use crate::texture::procgen as texture_procgen;
 use crate::texture::Texture;
 use crate::warp::WarpState;
-use crate::world::SolarSystem;
+use crate::world::comet::{self, CometState};
+use crate::world::solar::{self, Scale};
+use crate::world::{Body, BodyKind, CloudLayer, MeshInstance, Ring, SolarSystem};
+
+/// Separación mínima entre la cámara libre y la superficie de un cuerpo.
+const CAMERA_MARGIN: f32 = 2.0;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum CameraMode {
+    /// Cámara en tercera persona detrás de la nave; el teclado pilotea la nave.
+    Chase,
+    /// Cámara libre; la nave queda sola siguiendo su inercia.
+    Free,
+}
+
+/// Luminancia a partir de la cual un píxel se derrama en el bloom.
+const BLOOM_THRESHOLD: f32 = 0.72;
+/// Radio del desenfoque del bloom (en píxeles a media resolución).
+const BLOOM_RADIUS: usize = 6;
+
+const DEFAULT_SCENE: &str = "scenes/demo.scene";
+const SKY_TEXTURE: &str = "textures/stars.jpg";
+/// Carpeta del cielo en cubemap: seis caras `px.png` … `nz.png` o una `cross.png`.
+const SKYBOX_DIR: &str = "textures/skybox";
+const SKYBOX_EXTENSIONS: [&str; 2] = ["png", "jpg"];
+const STAR_CATALOG: &str = "catalog/stars.csv";
+/// Cono (en radianes) en el que se busca una estrella con nombre al identificar.
+const IDENTIFY_ANGLE: f32 = 0.1;
+const CONSTELLATIONS: &str = "catalog/constellations.txt";
+
+/// De dónde sale el sistema que se muestra.
+pub enum SystemSource {
+    /// La escena de demo (con recarga en caliente).
+    Scene,
+    /// Sistema generado con una semilla.
+    Generated(u64),
+    /// El Sistema Solar real en un día juliano.
+    Real { date: f64, scale: Scale },
+}
+
+/// Malla de una instancia de la escena con las texturas de sus materiales.
+struct LoadedMesh {
+    mesh: Rc<Mesh>,
+    textures: Vec<Option<Rc<Texture>>>,
+}
+
+/// Mapas de relieve y brillo de un cuerpo (los que falten quedan en `None`).
+struct SurfaceTextures {
+    normal: Option<Rc<Texture>>,
+    height: Option<Rc<Texture>>,
+    specular: Option<Rc<Texture>>,
+    emissive: Option<Rc<Texture>>,
+}
+
+/// Lo que se dibuja en orden del pintor (de lejos a cerca).
+#[derive(Clone, Copy)]
+enum Drawable {
+    Body(usize),
+    Mesh(usize),
+    Ship,
+}
 
 pub struct App {
     window: Window,
@@ -21,19 +93,43 @@ pub struct App {
     last_frame: Instant,
     running: bool,
     warp: WarpState,
+    flight: FlightModel,
+    ship: Ship,
+    camera_mode: CameraMode,
+
+    // Texturas (una por cuerpo, compartidas a través del AssetManager)
+    assets: AssetManager,
+    body_textures: Vec<Rc<Texture>>,
+    sky: Sky,
+    /// Formato pedido con `--sky`; `None` usa el del archivo que se encuentre.
+    sky_format: Option<SkyFormat>,
+    /// Rayos del cielo por píxel, cacheados por resolución y FOV.
+    sky_rays: SkyRays,
+    /// Catálogo de estrellas; con `--stars` (o la tecla K) el cielo se dibuja con
+    /// él en vez de con la imagen.
+    star_catalog: Option<StarCatalog>,
+    use_catalog: bool,
+    show_constellations: bool,
+    /// Una por `system.meshes`; `None` si el modelo no se pudo cargar.
+    meshes: Vec<Option<LoadedMesh>>,
+    /// Una por `system.bodies`; solo los cuerpos irregulares tienen malla.
+    body_meshes: Vec<Option<LoadedMesh>>,
+    /// Franja radial del anillo de cada cuerpo (`None` si no tiene anillo).
+    ring_strips: Vec<Option<Rc<Texture>>>,
+    /// Mapas de superficie de cada cuerpo (`None` si no tiene).
+    surface_maps: Vec<Option<SurfaceTextures>>,
+    /// Densidad de la capa de nubes de cada cuerpo (`None` si no tiene).
+    cloud_textures: Vec<Option<Rc<Texture>>>,
+    /// Shader procedural de cada cuerpo (`None` = se usa su textura).
+    shaders: Vec<Option<Box<dyn SurfaceShader>>>,
 
-    // Texturas
-    textura_sol: Texture,
-    textura_planeta1: Texture,
-    textura_planeta2: Texture,
-    textura_planeta3: Texture,
-    textura_planeta4: Texture,
-    textura_luna: Texture,
-    textura_cielo: Texture,
+    // Recarga en caliente de la escena y las texturas (None si el sistema es generado)
+    scene_key: Option<String>,
+    watcher: FileWatcher,
 }
 
 impl App {
-    pub fn new(width: usize, height: usize) -> Self {
+    pub fn new(width: usize, height: usize, source: SystemSource, sky_format: Option<SkyFormat>, stars: bool) -> Self {
         let window = Window::new(
             "Sistema Solar - Rust Software Renderer",
             width,
@@ -43,18 +139,41 @@ impl App {
         .expect("No se pudo crear la ventana");
 
         let renderer = Renderer::new(width, height);
-        let system = SolarSystem::new_demo();
         let camera = Camera::new();
+        let ship = Ship::in_front_of(&camera);
 
-        let textura_sol = Texture::from_file("assets/textures/sun.jpg");
-        let textura_planeta1 = Texture::from_file("assets/textures/mercury.jpg");
-        let textura_planeta2 = Texture::from_file("assets/textures/venus.jpg");
-        let textura_planeta3 = Texture::from_file("assets/textures/earth.jpg");
-        let textura_planeta4 = Texture::from_file("assets/textures/moon.jpg");
-        let textura_luna = Texture::from_file("assets/textures/mars.jpg");
-        let textura_cielo = Texture::from_file("assets/textures/stars.jpg");
+        let mut assets = AssetManager::new();
+        let (system, scene_key) = match source {
+            SystemSource::Generated(seed) => {
+                let system = SolarSystem::generate(seed);
+                println!("Sistema generado con semilla {}: {}", seed, system.bodies[0].name);
+                (system, None)
+            }
+            SystemSource::Real { date, scale } => {
+                let (year, month, day) = solar::calendar_date(date);
+                println!("Sistema Solar al {:04}-{:02}-{:02} ({:?})", year, month, day, scale);
+                (SolarSystem::real(date, scale), None)
+            }
+            SystemSource::Scene => {
+                let scene_key = DEFAULT_SCENE.to_string();
+                let system = load_scene(&assets, &scene_key).unwrap_or_else(|e| {
+                    eprintln!("No se pudo cargar la escena {}: {} (usando el sistema de demo)", scene_key, e);
+                    SolarSystem::new_demo()
+                });
+                (system, Some(scene_key))
+            }
+        };
+        let body_textures = load_body_textures(&mut assets, &system);
+        let sky = load_sky(&mut assets, sky_format);
+        let star_catalog = load_star_catalog(&assets);
+        let meshes = load_meshes(&mut assets, &system);
+        let body_meshes = build_body_meshes(&system, &body_textures);
+        let ring_strips = load_ring_strips(&mut assets, &system);
+        let surface_maps = load_surface_maps(&mut assets, &system);
+        let cloud_textures = load_cloud_textures(&mut assets, &system);
+        let shaders = build_shaders(&system);
 
-        Self {
+        let mut app = Self {
             window,
             renderer,
             system,
@@ -63,14 +182,28 @@ impl App {
             last_frame: Instant::now(),
             running: true,
             warp: WarpState::new(),
-            textura_sol,
-            textura_planeta1,
-            textura_planeta2,
-            textura_planeta3,
-            textura_planeta4,
-            textura_luna,
-            textura_cielo,
-        }
+            flight: FlightModel::new(),
+            ship,
+            camera_mode: CameraMode::Chase,
+            assets,
+            body_textures,
+            sky,
+            sky_format,
+            sky_rays: SkyRays::new(),
+            use_catalog: stars && star_catalog.is_some(),
+            star_catalog,
+            show_constellations: true,
+            meshes,
+            body_meshes,
+            ring_strips,
+            surface_maps,
+            cloud_textures,
+            shaders,
+            scene_key,
+            watcher: FileWatcher::new(Duration::from_millis(500)),
+        };
+        app.watch_assets();
+        app
     }
 
     pub fn run(&mut self) {
@@ -88,17 +221,256 @@ impl App {
     }
 
     fn update(&mut self, dt: f32) {
+        self.check_hot_reload();
         self.handle_warps();
 
+        self.handle_flight_toggles();
+        self.handle_sky_toggles();
+        self.handle_identify();
+
+        let chase = self.camera_mode == CameraMode::Chase;
+
         if self.warp.active {
-            self.warp.update(dt, &mut self.camera);
+            let position = match self.camera_mode {
+                CameraMode::Chase => &mut self.ship.position,
+                CameraMode::Free => &mut self.camera.position,
+            };
+            self.warp.update(dt, position);
+        } else if chase {
+            self.ship.update(dt, &self.input, true);
         } else {
-            self.camera.update(dt, &self.input);
+            self.flight.update(
+                dt,
+                &self.input,
+                &mut self.camera.position,
+                &mut self.camera.orientation,
+            );
+            self.ship.update(dt, &self.input, false);
         }
 
         self.system.update(dt);
 
-        collision::resolve_collisions(&self.system, &mut self.camera);
+        if let Some(normal) = collision::resolve_collisions(&self.system, &mut self.ship.position, SHIP_RADIUS) {
+            self.ship.flight.on_collision(normal);
+        }
+
+        if chase {
+            self.camera
+                .follow(self.ship.position, self.ship.orientation, CHASE_OFFSET, dt);
+        }
+
+        if let Some(normal) = collision::resolve_collisions(&self.system, &mut self.camera.position, CAMERA_MARGIN) {
+            self.flight.on_collision(normal);
+        }
+    }
+
+    /// Modelo de vuelo de lo que se está controlando (la nave o la cámara libre).
+    fn active_flight(&mut self) -> &mut FlightModel {
+        match self.camera_mode {
+            CameraMode::Chase => &mut self.ship.flight,
+            CameraMode::Free => &mut self.flight,
+        }
+    }
+
+    fn handle_sky_toggles(&mut self) {
+        if self.input.toggle_starfield && self.star_catalog.is_some() {
+            self.use_catalog = !self.use_catalog;
+            println!("Cielo: {}", if self.use_catalog { "catálogo de estrellas" } else { "imagen" });
+        }
+        if self.input.toggle_constellations {
+            self.show_constellations = !self.show_constellations;
+        }
+    }
+
+    /// Informa la estrella con nombre hacia la que se mira y el cinturón en el que se está.
+    fn handle_identify(&mut self) {
+        if !self.input.identify {
+            return;
+        }
+        if let Some(catalog) = &self.star_catalog {
+            match catalog.star_toward(self.camera.forward(), IDENTIFY_ANGLE) {
+                Some(star) => println!(
+                    "Estrella al frente: {} (magnitud {:.2})",
+                    star.name.as_deref().unwrap_or_default(),
+                    star.magnitude
+                ),
+                None => println!("No hay estrellas con nombre al frente"),
+            }
+        }
+        let position = match self.camera_mode {
+            CameraMode::Chase => self.ship.position,
+            CameraMode::Free => self.camera.position,
+        };
+        if let Some(belt) = self.system.belt_at(position) {
+            println!("Cinturón: {}", belt.name);
+        }
+    }
+
+    fn handle_flight_toggles(&mut self) {
+        if self.input.toggle_camera {
+            self.camera_mode = match self.camera_mode {
+                CameraMode::Chase => CameraMode::Free,
+                CameraMode::Free => CameraMode::Chase,
+            };
+            // Ninguno de los dos sigue de largo con la velocidad que traía
+            self.flight.stop();
+            self.ship.flight.stop();
+            println!("Cámara: {:?}", self.camera_mode);
+        }
+
+        let toggle_flight = self.input.toggle_flight;
+        let toggle_assist = self.input.toggle_assist;
+        let cycle_speed = self.input.cycle_speed;
+
+        let flight = self.active_flight();
+        if toggle_flight {
+            flight.toggle_mode();
+            println!("Modelo de vuelo: {:?}", flight.mode);
+        }
+        if toggle_assist {
+            flight.assist = !flight.assist;
+            println!("Asistencia de vuelo: {}", if flight.assist { "activada" } else { "desactivada" });
+        }
+        if cycle_speed {
+            flight.cycle_speed();
+            println!("Multiplicador de velocidad: x{}", flight.speed_multiplier());
+        }
+    }
+
+    /// Registra en el watcher la escena y todas las texturas y mallas que vienen de disco.
+    fn watch_assets(&mut self) {
+        self.watcher.clear();
+        if let Some(path) = self.scene_key.as_ref().and_then(|key| self.assets.resolve(key)) {
+            self.watcher.watch(path);
+        }
+        for path in self.assets.texture_paths() {
+            self.watcher.watch(path);
+        }
+        for path in self.assets.mesh_paths() {
+            self.watcher.watch(path);
+        }
+        for key in [STAR_CATALOG, CONSTELLATIONS] {
+            if let Some(path) = self.assets.resolve(key) {
+                self.watcher.watch(path);
+            }
+        }
+    }
+
+    /// Aplica los cambios en disco sin reiniciar: las texturas y mallas se
+    /// reemplazan en la caché y solo se rehace lo que depende de ellas. Si cambió
+    /// la escena se reconstruye en el mismo instante de simulación.
+    /// La cámara y el warp en curso no se tocan.
+    fn check_hot_reload(&mut self) {
+        let changed = self.watcher.poll();
+        if changed.is_empty() {
+            return;
+        }
+
+        let scene_path = self.scene_key.as_ref().and_then(|key| self.assets.resolve(key));
+        let catalog_paths: Vec<_> = [STAR_CATALOG, CONSTELLATIONS]
+            .iter()
+            .filter_map(|key| self.assets.resolve(key))
+            .collect();
+        let mut scene_changed = false;
+        let mut catalog_changed = false;
+        let mut textures = HashSet::new();
+        let mut meshes = HashSet::new();
+
+        for path in &changed {
+            if Some(path) == scene_path.as_ref() {
+                scene_changed = true;
+                continue;
+            }
+            if catalog_paths.contains(path) {
+                catalog_changed = true;
+            }
+            for key in self.assets.texture_keys_at(path) {
+                if self.assets.reload_texture(&key) {
+                    println!("Textura recargada: {}", key);
+                    textures.insert(key);
+                }
+            }
+            for key in self.assets.mesh_keys_at(path) {
+                if self.assets.reload_mesh(&key) {
+                    println!("Malla recargada: {}", key);
+                    meshes.insert(key);
+                }
+            }
+        }
+
+        let mut scene_reloaded = false;
+        if let Some(scene_key) = self.scene_key.as_ref().filter(|_| scene_changed) {
+            match load_scene(&self.assets, scene_key) {
+                Ok(mut system) => {
+                    system.advance_to(self.system.time);
+                    self.system = system;
+                    scene_reloaded = true;
+                    println!("Escena recargada: {}", scene_key);
+                }
+                Err(e) => {
+                    eprintln!("No se pudo recargar la escena {}: {} (se mantiene la anterior)", scene_key, e);
+                }
+            }
+        }
+
+        if scene_reloaded {
+            // Otros cuerpos, otras instancias: se rehace todo lo que sale del sistema
+            self.body_textures = load_body_textures(&mut self.assets, &self.system);
+            self.meshes = load_meshes(&mut self.assets, &self.system);
+            self.body_meshes = build_body_meshes(&self.system, &self.body_textures);
+            self.ring_strips = load_ring_strips(&mut self.assets, &self.system);
+            self.surface_maps = load_surface_maps(&mut self.assets, &self.system);
+            self.cloud_textures = load_cloud_textures(&mut self.assets, &self.system);
+            self.shaders = build_shaders(&self.system);
+        } else {
+            self.refresh_assets(&textures, &meshes);
+        }
+
+        if textures.iter().any(|key| is_sky_texture(key)) {
+            self.sky = load_sky(&mut self.assets, self.sky_format);
+        }
+        if catalog_changed {
+            self.star_catalog = load_star_catalog(&self.assets);
+        }
+        self.watch_assets();
+    }
+
+    /// Rehace solo los cuerpos e instancias que usan alguna de las texturas o
+    /// mallas recargadas. Los terrenos y shaders procedurales no dependen de
+    /// ningún archivo y no se tocan.
+    fn refresh_assets(&mut self, textures: &HashSet<String>, meshes: &HashSet<String>) {
+        let uses = |key: &Option<String>| key.as_ref().is_some_and(|k| textures.contains(k));
+
+        for (i, body) in self.system.bodies.iter().enumerate() {
+            if body.terrain.is_none() && uses(&body.texture) {
+                self.body_textures[i] = load_body_texture(&mut self.assets, body);
+                self.body_meshes[i] = build_body_mesh(body, &self.body_textures[i]);
+            }
+            if body.ring.as_ref().is_some_and(|ring| uses(&ring.texture)) {
+                self.ring_strips[i] = load_ring_strip(&mut self.assets, body);
+            }
+            if body.surface.as_ref().is_some_and(|surface| {
+                [&surface.normal_map, &surface.height_map, &surface.specular_map, &surface.emissive_map]
+                    .into_iter()
+                    .any(uses)
+            }) {
+                self.surface_maps[i] = load_surface(&mut self.assets, body);
+            }
+            if body.clouds.as_ref().is_some_and(|clouds| uses(&clouds.texture)) {
+                self.cloud_textures[i] = load_cloud_texture(&mut self.assets, body);
+            }
+        }
+
+        let mut reported = HashSet::new();
+        for (i, instance) in self.system.meshes.iter().enumerate() {
+            let stale = meshes.contains(&instance.model)
+                || self.meshes[i]
+                    .as_ref()
+                    .is_some_and(|loaded| loaded.mesh.materials.iter().any(|m| uses(&m.texture)));
+            if stale {
+                self.meshes[i] = load_mesh(&mut self.assets, instance, &mut reported);
+            }
+        }
     }
 
     fn handle_warps(&mut self) {
@@ -122,7 +494,16 @@ impl App {
             return;
         }
         let center = self.system.body_position(index);
-        self.camera.position = center + Vec3::new(0.0, 20.0, 40.0);
+        *self.controlled_position() = center + Vec3::new(0.0, 20.0, 40.0);
+        self.active_flight().stop();
+    }
+
+    /// Posición de lo que se está controlando (la nave o la cámara libre).
+    fn controlled_position(&mut self) -> &mut Vec3 {
+        match self.camera_mode {
+            CameraMode::Chase => &mut self.ship.position,
+            CameraMode::Free => &mut self.camera.position,
+        }
     }
 
     fn start_animated_warp(&mut self, index: usize) {
@@ -131,51 +512,357 @@ impl App {
         }
         let center = self.system.body_position(index);
         let target = center + Vec3::new(0.0, 20.0, 40.0);
-        let start = self.camera.position;
+        let start = *self.controlled_position();
 
         self.warp.start_animated(start, target, 1.8);
+        self.active_flight().stop();
     }
 
     fn render(&mut self) {
         self.renderer.clear(0x000000);
 
-        skybox::draw_skybox(&mut self.renderer, &self.camera, &self.textura_cielo);  
+        match self.star_catalog.as_ref().filter(|_| self.use_catalog) {
+            Some(catalog) => skybox::draw_starfield(&mut self.renderer, &self.camera, catalog, self.show_constellations),
+            None => skybox::draw_sky(&mut self.renderer, &self.camera, &self.sky, &mut self.sky_rays),
+        }
 
         self.system.render(&mut self.renderer, &self.camera);
 
-        let mut body_indices: Vec<(usize, f32)> = Vec::new();
-        
+        // Cuerpos, mallas y nave ordenados de lejos a cerca. Las mallas y la nave
+        // además usan el z-buffer, así que entre ellas se tapan bien aunque se crucen.
+        let eye = self.camera.position;
+        let mut draw_list: Vec<(Drawable, f32)> = Vec::new();
         for i in 0..self.system.bodies.len() {
-            let body_pos = self.system.body_position(i);
-            let distance = (body_pos - self.camera.position).length();
-            body_indices.push((i, distance));
-        }
-        
-        body_indices.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
-        
-        for (i, _) in body_indices {
-            if let Some(((sx, sy), radius_px)) =
-                self.system.project_body(i, &self.renderer, &self.camera)
-            {
-                let tex = match i {
-                    0 => &self.textura_sol,
-                    1 => &self.textura_planeta1,
-                    2 => &self.textura_planeta2,
-                    3 => &self.textura_planeta3,
-                    4 => &self.textura_planeta4,
-                    5 => &self.textura_luna,
-                    _ => continue,
-                };
-                
-                let rotation = self.system.bodies[i].angle;
-
-                self.renderer
-                    .draw_textured_sphere(tex, (sx, sy), radius_px, rotation);
+            let distance = (self.system.body_position(i) - eye).length();
+            draw_list.push((Drawable::Body(i), distance));
+        }
+        for i in 0..self.system.meshes.len() {
+            let distance = (self.system.mesh_position(i) - eye).length();
+            draw_list.push((Drawable::Mesh(i), distance));
+        }
+        draw_list.push((Drawable::Ship, (self.ship.position - eye).length()));
+        draw_list.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+
+        let light_pos = self.system.light_position();
+        let occluders = self.system.occluders();
+        let light = Light {
+            position: light_pos,
+            radius: self.system.light_radius(),
+            occluders: &occluders,
+        };
+
+        for (item, _) in draw_list {
+            match item {
+                Drawable::Body(i) => self.render_body(i, &light),
+                Drawable::Mesh(i) => self.render_mesh(i, light_pos),
+                Drawable::Ship => self.ship.render(&mut self.renderer, &self.camera, light_pos),
             }
         }
 
+        // Los cinturones van al final: con el z-buffer quedan delante o detrás de cada cuerpo
+        self.system.render_belts(&mut self.renderer, &self.camera);
+
+        self.renderer
+            .bloom(BLOOM_THRESHOLD, self.bloom_strength(), BLOOM_RADIUS);
+
         self.window
             .update_with_buffer(self.renderer.buffer(), self.renderer.width, self.renderer.height)
             .expect("Error al actualizar la ventana");
     }
+
+    fn render_body(&mut self, i: usize, light: &Light) {
+        let b = &self.system.bodies[i];
+        let center = self.system.body_position(i);
+
+        // Los cuerpos irregulares son mallas 3D; el resto, esferas trazadas por píxel
+        if let Some(Some(loaded)) = self.body_meshes.get(i) {
+            let model = Mat4::translation(center)
+                * b.orientation().to_mat4()
+                * Mat4::scale(Vec3::new(b.radius, b.radius, b.radius));
+            self.renderer
+                .draw_mesh(&loaded.mesh, &loaded.textures, &model, &self.camera, light.position);
+            self.render_comet_tail(i);
+            return;
+        }
+
+        let Some(tex) = self.body_textures.get(i) else {
+            return;
+        };
+        let maps = self.surface_maps.get(i).and_then(Option::as_ref);
+        let textures = BodyTextures {
+            surface: tex,
+            ring: self.ring_strips.get(i).and_then(|s| s.as_deref()),
+            normal: maps.and_then(|m| m.normal.as_deref()),
+            height: maps.and_then(|m| m.height.as_deref()),
+            specular: maps.and_then(|m| m.specular.as_deref()),
+            emissive: maps.and_then(|m| m.emissive.as_deref()),
+            clouds: self.cloud_textures.get(i).and_then(|c| c.as_deref()),
+            shader: self.shaders.get(i).and_then(|s| s.as_deref()),
+        };
+        self.renderer
+            .draw_body(&self.camera, b, center, &textures, light, self.system.time);
+
+        if matches!(b.kind, BodyKind::Star) {
+            self.renderer
+                .draw_star_glow(&self.camera, center, b.radius, color::to_rgb(b.color), self.system.time);
+        }
+    }
+
+    /// Fuerza del bloom según la estrella más brillante (su `Body::color`).
+    fn bloom_strength(&self) -> f32 {
+        self.system
+            .bodies
+            .iter()
+            .filter(|b| matches!(b.kind, BodyKind::Star))
+            .map(|b| color::luminance(color::to_rgb(b.color)))
+            .fold(0.0, f32::max)
+    }
+
+    /// Coma y colas del cuerpo i si es un cometa (se iluminan con la estrella más cercana).
+    fn render_comet_tail(&mut self, i: usize) {
+        let b = &self.system.bodies[i];
+        let (Some(tail), Some(orbit)) = (&b.tail, &b.orbit) else {
+            return;
+        };
+        let nucleus = self.system.body_position(i);
+        let Some(star) = self.system.nearest_star(nucleus) else {
+            return;
+        };
+
+        let state = CometState {
+            nucleus,
+            star,
+            velocity: self.system.body_velocity(i),
+            activity: comet::activity(orbit, (nucleus - star).length()),
+        };
+        tail.render(&mut self.renderer, &self.camera, &state, i as u64);
+    }
+
+    fn render_mesh(&mut self, i: usize, light_pos: Vec3) {
+        let Some(Some(loaded)) = self.meshes.get(i) else {
+            return;
+        };
+
+        let model = self.system.meshes[i].model_matrix(self.system.mesh_position(i));
+        self.renderer
+            .draw_mesh(&loaded.mesh, &loaded.textures, &model, &self.camera, light_pos);
+    }
+}
+
+fn load_scene(assets: &AssetManager, key: &str) -> Result<SolarSystem, Box<dyn std::error::Error>> {
+    let src = assets.text(key)?;
+    Ok(SolarSystem::from_scene(&src)?)
+}
+
+/// Cielo de fondo: primero las seis caras sueltas del cubemap, después una cruz
+/// y por último la imagen equirectangular. Si se pidió un formato, se convierte.
+fn load_sky(assets: &mut AssetManager, format: Option<SkyFormat>) -> Sky {
+    let sky = load_cubemap(assets)
+        .map(|cubemap| Sky::Cubemap(Rc::new(cubemap)))
+        .unwrap_or_else(|| Sky::Equirect(assets.texture_or_else(SKY_TEXTURE, || Texture::solid(0xFF000008))));
+    match format {
+        Some(format) => sky.convert(format),
+        None => sky,
+    }
+}
+
+/// `true` si la textura es parte del cielo (la imagen o alguna cara del cubemap).
+fn is_sky_texture(key: &str) -> bool {
+    key == SKY_TEXTURE || key.starts_with(SKYBOX_DIR)
+}
+
+fn load_cubemap(assets: &mut AssetManager) -> Option<Cubemap> {
+    let find = |assets: &AssetManager, name: &str| {
+        SKYBOX_EXTENSIONS
+            .iter()
+            .map(|ext| format!("{}/{}.{}", SKYBOX_DIR, name, ext))
+            .find(|key| assets.resolve(key).is_some())
+    };
+
+    let face_keys: Vec<String> = FACE_NAMES.iter().filter_map(|name| find(assets, name)).collect();
+    if face_keys.len() == FACE_NAMES.len() {
+        let faces = face_keys.iter().map(|key| assets.texture(key)).collect::<Result<Vec<_>, _>>();
+        match faces {
+            Ok(faces) => match Cubemap::from_faces(std::array::from_fn(|i| &*faces[i])) {
+                Ok(cubemap) => return Some(cubemap),
+                Err(e) => eprintln!("Cubemap inválido en {}: {}", SKYBOX_DIR, e),
+            },
+            Err(e) => eprintln!("No se pudieron cargar las caras del cubemap: {}", e),
+        }
+    } else if !face_keys.is_empty() {
+        eprintln!("Cubemap incompleto en {}: hay {} de las 6 caras", SKYBOX_DIR, face_keys.len());
+    }
+
+    let key = find(assets, "cross")?;
+    let cross = assets
+        .texture(&key)
+        .map_err(|e| eprintln!("No se pudo cargar {}: {}", key, e))
+        .ok()?;
+    Cubemap::from_cross(&cross)
+        .map_err(|e| eprintln!("Cruz inválida en {}: {}", key, e))
+        .ok()
+}
+
+/// Catálogo de estrellas con sus constelaciones; sin catálogo (o si no se puede
+/// leer) el cielo es la imagen equirectangular. Las constelaciones son opcionales.
+fn load_star_catalog(assets: &AssetManager) -> Option<StarCatalog> {
+    let text = assets.text(STAR_CATALOG).ok()?;
+    let mut catalog = match StarCatalog::parse(&text) {
+        Ok(catalog) => catalog,
+        Err(e) => {
+            eprintln!("No se pudo leer el catálogo {}: {} (usando la imagen del cielo)", STAR_CATALOG, e);
+            return None;
+        }
+    };
+    if let Ok(text) = assets.text(CONSTELLATIONS) {
+        if let Err(e) = catalog.add_constellations(&text) {
+            eprintln!("No se pudieron leer las constelaciones {}: {}", CONSTELLATIONS, e);
+        }
+    }
+    Some(catalog)
+}
+
+/// Textura de cada cuerpo del sistema, en el mismo orden que `system.bodies`.
+fn load_body_textures(assets: &mut AssetManager, system: &SolarSystem) -> Vec<Rc<Texture>> {
+    system.bodies.iter().map(|b| load_body_texture(assets, b)).collect()
+}
+
+/// Textura de un cuerpo: la de su terreno procedural o la de su archivo.
+/// Si el archivo falta o está corrupto se usa un tablero con el color del cuerpo.
+fn load_body_texture(assets: &mut AssetManager, body: &Body) -> Rc<Texture> {
+    match (&body.terrain, &body.texture) {
+        (Some(terrain), _) => Rc::new(texture_procgen::planet(terrain)),
+        (None, Some(key)) => assets.texture_or_else(key, || Texture::fallback_for(body.color)),
+        (None, None) => Rc::new(Texture::fallback_for(body.color)),
+    }
+}
+
+/// Malla y texturas de cada instancia de `system.meshes`, en el mismo orden.
+fn load_meshes(assets: &mut AssetManager, system: &SolarSystem) -> Vec<Option<LoadedMesh>> {
+    let mut reported = HashSet::new();
+    system
+        .meshes
+        .iter()
+        .map(|instance| load_mesh(assets, instance, &mut reported))
+        .collect()
+}
+
+/// Malla y texturas de una instancia. Si el modelo falta o tiene errores la
+/// instancia no se dibuja. Varias instancias comparten la malla: los avisos de
+/// cada modelo se muestran una sola vez por `reported`.
+fn load_mesh(assets: &mut AssetManager, instance: &MeshInstance, reported: &mut HashSet<String>) -> Option<LoadedMesh> {
+    let mesh = match assets.mesh(&instance.model) {
+        Ok(mesh) => mesh,
+        Err(e) => {
+            eprintln!("No se pudo cargar el modelo {} de {}: {}", instance.model, instance.name, e);
+            return None;
+        }
+    };
+    if reported.insert(instance.model.clone()) {
+        for warning in &mesh.warnings {
+            eprintln!("Modelo {}, {}", instance.model, warning);
+        }
+    }
+    let textures = mesh
+        .materials
+        .iter()
+        .map(|m| {
+            let key = m.texture.as_ref()?;
+            assets
+                .texture(key)
+                .map_err(|e| eprintln!("No se pudo cargar la textura {}: {}", key, e))
+                .ok()
+        })
+        .collect();
+    Some(LoadedMesh { mesh, textures })
+}
+
+/// Malla procedural de cada cuerpo con `shape`, en el mismo orden que `system.bodies`.
+fn build_body_meshes(system: &SolarSystem, textures: &[Rc<Texture>]) -> Vec<Option<LoadedMesh>> {
+    system
+        .bodies
+        .iter()
+        .zip(textures)
+        .map(|(b, texture)| build_body_mesh(b, texture))
+        .collect()
+}
+
+/// Con textura propia se usa la textura; si no, el color del cuerpo.
+fn build_body_mesh(body: &Body, texture: &Rc<Texture>) -> Option<LoadedMesh> {
+    let params = body.shape.as_ref()?;
+    let mut mesh = procgen::asteroid(params);
+    let texture = body.texture.as_ref().map(|_| texture.clone());
+    mesh.materials[0].diffuse = match texture {
+        Some(_) => Vec3::new(1.0, 1.0, 1.0),
+        None => color::to_rgb(body.color),
+    };
+    Some(LoadedMesh {
+        mesh: Rc::new(mesh),
+        textures: vec![texture],
+    })
+}
+
+/// Franja del anillo de cada cuerpo, en el mismo orden que `system.bodies`.
+fn load_ring_strips(assets: &mut AssetManager, system: &SolarSystem) -> Vec<Option<Rc<Texture>>> {
+    system.bodies.iter().map(|b| load_ring_strip(assets, b)).collect()
+}
+
+/// Sin textura (o si no se puede cargar) se genera una con el color del anillo.
+fn load_ring_strip(assets: &mut AssetManager, body: &Body) -> Option<Rc<Texture>> {
+    let ring = body.ring.as_ref()?;
+    Some(match &ring.texture {
+        Some(key) => assets.texture_or_else(key, || Ring::default_strip(ring.color)),
+        None => Rc::new(Ring::default_strip(ring.color)),
+    })
+}
+
+/// Mapas de normales, alturas y brillo de cada cuerpo.
+fn load_surface_maps(assets: &mut AssetManager, system: &SolarSystem) -> Vec<Option<SurfaceTextures>> {
+    system.bodies.iter().map(|b| load_surface(assets, b)).collect()
+}
+
+/// Un mapa que no se puede cargar se avisa y se ignora: el cuerpo se dibuja liso.
+fn load_surface(assets: &mut AssetManager, body: &Body) -> Option<SurfaceTextures> {
+    let surface = body.surface.as_ref()?;
+    let mut load = |key: &Option<String>| {
+        let key = key.as_ref()?;
+        match assets.texture(key) {
+            Ok(texture) => Some(texture),
+            Err(e) => {
+                eprintln!("No se pudo cargar el mapa {}: {}", key, e);
+                None
+            }
+        }
+    };
+    Some(SurfaceTextures {
+        normal: load(&surface.normal_map),
+        height: load(&surface.height_map),
+        specular: load(&surface.specular_map),
+        emissive: load(&surface.emissive_map),
+    })
+}
+
+/// Textura de nubes de cada cuerpo, en el mismo orden que `system.bodies`.
+fn load_cloud_textures(assets: &mut AssetManager, system: &SolarSystem) -> Vec<Option<Rc<Texture>>> {
+    system.bodies.iter().map(|b| load_cloud_texture(assets, b)).collect()
+}
+
+/// Sin `cloud_texture` las nubes se generan con una semilla sacada del nombre.
+fn load_cloud_texture(assets: &mut AssetManager, body: &Body) -> Option<Rc<Texture>> {
+    let clouds = body.clouds.as_ref()?;
+    let seed = seed_from_str(&body.name);
+    Some(match &clouds.texture {
+        Some(key) => assets.texture_or_else(key, || CloudLayer::default_texture(seed)),
+        None => Rc::new(CloudLayer::default_texture(seed)),
+    })
+}
+
+/// Shaders procedurales de los cuerpos que eligieron uno. La semilla sale del
+/// nombre, así cada cuerpo tiene su propio dibujo y no cambia entre recargas.
+fn build_shaders(system: &SolarSystem) -> Vec<Option<Box<dyn SurfaceShader>>> {
+    system
+        .bodies
+        .iter()
+        .map(|b| b.shader.map(|kind| shader::build(kind, seed_from_str(&b.name), b.color)))
+        .collect()
 }
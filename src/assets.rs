@@ -0,0 +1,319 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use crate::mesh::{obj, Mesh, MeshError};
+use crate::texture::{Texture, TextureError};
+
+/// Variable de entorno con una raíz extra de assets (tiene prioridad sobre las demás).
+pub const ASSETS_ENV: &str = "SOLAR_ASSETS";
+
+/// Texturas de la demo compiladas dentro del binario, por si no hay carpeta `assets/` a mano.
+const EMBEDDED_TEXTURES: &[(&str, &[u8])] = &[
+    ("textures/sun.jpg", include_bytes!("../assets/textures/sun.jpg")),
+    ("textures/mercury.jpg", include_bytes!("../assets/textures/mercury.jpg")),
+    ("textures/venus.jpg", include_bytes!("../assets/textures/venus.jpg")),
+    ("textures/earth.jpg", include_bytes!("../assets/textures/earth.jpg")),
+    ("textures/earth_height.png", include_bytes!("../assets/textures/earth_height.png")),
+    ("textures/earth_specular.png", include_bytes!("../assets/textures/earth_specular.png")),
+    ("textures/earth_lights.png", include_bytes!("../assets/textures/earth_lights.png")),
+    ("textures/moon.jpg", include_bytes!("../assets/textures/moon.jpg")),
+    ("textures/mars.jpg", include_bytes!("../assets/textures/mars.jpg")),
+    ("textures/mars_height.png", include_bytes!("../assets/textures/mars_height.png")),
+    ("textures/stars.jpg", include_bytes!("../assets/textures/stars.jpg")),
+];
+
+/// Escenas, mallas y catálogos embebidos, con el mismo criterio que las texturas.
+const EMBEDDED_TEXT: &[(&str, &str)] = &[
+    ("scenes/demo.scene", include_str!("../assets/scenes/demo.scene")),
+    ("models/station.obj", include_str!("../assets/models/station.obj")),
+    ("models/station.mtl", include_str!("../assets/models/station.mtl")),
+    ("catalog/stars.csv", include_str!("../assets/catalog/stars.csv")),
+    ("catalog/constellations.txt", include_str!("../assets/catalog/constellations.txt")),
+];
+
+/// Registro de assets: resuelve claves relativas (`textures/sun.jpg`) contra
+/// varias raíces de búsqueda y guarda cada textura cargada una sola vez.
+pub struct AssetManager {
+    roots: Vec<PathBuf>,
+    textures: HashMap<String, Rc<Texture>>,
+    meshes: HashMap<String, Rc<Mesh>>,
+    /// Claves que no cargaron: se siguen vigilando para reintentar cuando se arregle el archivo.
+    failed_textures: HashSet<String>,
+    failed_meshes: HashSet<String>,
+    /// Claves de los `mtllib` que pidió cada malla la última vez que se leyó.
+    mesh_libraries: HashMap<String, Vec<String>>,
+}
+
+impl AssetManager {
+    /// Raíces por defecto, en orden: `$SOLAR_ASSETS`, `./assets`, `assets/` junto al
+    /// ejecutable y `assets/` en la raíz del proyecto cuando se corre desde `target/<perfil>/`.
+    pub fn new() -> Self {
+        let mut assets = Self::empty();
+
+        if let Some(dir) = std::env::var_os(ASSETS_ENV) {
+            assets.add_root(PathBuf::from(dir));
+        }
+
+        assets.add_root(PathBuf::from("assets"));
+
+        if let Some(exe_dir) = std::env::current_exe().ok().and_then(|p| p.parent().map(Path::to_path_buf)) {
+            assets.add_root(exe_dir.join("assets"));
+            if let Some(project) = exe_dir.parent().and_then(Path::parent) {
+                assets.add_root(project.join("assets"));
+            }
+        }
+
+        assets
+    }
+
+    /// Registro sin raíces: solo sirve lo embebido hasta que se agreguen.
+    pub fn empty() -> Self {
+        Self {
+            roots: Vec::new(),
+            textures: HashMap::new(),
+            meshes: HashMap::new(),
+            failed_textures: HashSet::new(),
+            failed_meshes: HashSet::new(),
+            mesh_libraries: HashMap::new(),
+        }
+    }
+
+    pub fn add_root(&mut self, root: PathBuf) {
+        if !self.roots.contains(&root) {
+            self.roots.push(root);
+        }
+    }
+
+    /// Primera ruta existente para `key` entre las raíces de búsqueda.
+    pub fn resolve(&self, key: &str) -> Option<PathBuf> {
+        self.roots
+            .iter()
+            .map(|root| root.join(key))
+            .find(|path| path.is_file())
+    }
+
+    /// Textura para `key`, compartida entre todos los que la pidan.
+    /// Busca primero en disco y después en los assets embebidos.
+    pub fn texture(&mut self, key: &str) -> Result<Rc<Texture>, TextureError> {
+        if let Some(tex) = self.textures.get(key) {
+            return Ok(Rc::clone(tex));
+        }
+
+        let tex = match self.load_texture(key) {
+            Ok(tex) => Rc::new(tex),
+            Err(e) => {
+                self.failed_textures.insert(key.to_string());
+                return Err(e);
+            }
+        };
+        self.failed_textures.remove(key);
+        self.textures.insert(key.to_string(), Rc::clone(&tex));
+        Ok(tex)
+    }
+
+    /// Igual que `texture`, pero si falla registra el error y devuelve `fallback`
+    /// (que no se guarda en caché, así un reintento puede encontrar el archivo).
+    pub fn texture_or_else(&mut self, key: &str, fallback: impl FnOnce() -> Texture) -> Rc<Texture> {
+        match self.texture(key) {
+            Ok(tex) => tex,
+            Err(e) => {
+                eprintln!("No se pudo cargar la textura {}: {} (usando textura de reemplazo)", key, e);
+                Rc::new(fallback())
+            }
+        }
+    }
+
+    /// Vuelve a leer `key` desde disco y reemplaza la copia en caché.
+    /// Si la nueva versión no carga se conserva la anterior y se devuelve `false`.
+    pub fn reload_texture(&mut self, key: &str) -> bool {
+        let loaded = match self.resolve(key) {
+            Some(path) => Texture::from_file(&path.to_string_lossy()),
+            None => Err(TextureError::NotFound(key.to_string())),
+        };
+
+        match loaded {
+            Ok(tex) => {
+                self.failed_textures.remove(key);
+                self.textures.insert(key.to_string(), Rc::new(tex));
+                true
+            }
+            Err(e) => {
+                eprintln!("No se pudo recargar la textura {}: {} (se mantiene la anterior)", key, e);
+                false
+            }
+        }
+    }
+
+    /// Claves de las texturas (en caché o que fallaron) que se leen de `path`.
+    pub fn texture_keys_at(&self, path: &Path) -> Vec<String> {
+        self.textures
+            .keys()
+            .chain(&self.failed_textures)
+            .filter(|key| self.resolve(key).as_deref() == Some(path))
+            .cloned()
+            .collect()
+    }
+
+    /// Rutas en disco de todas las texturas en caché (las embebidas no tienen)
+    /// y de las que están en disco pero no cargaron.
+    pub fn texture_paths(&self) -> Vec<PathBuf> {
+        self.textures
+            .keys()
+            .chain(&self.failed_textures)
+            .filter_map(|key| self.resolve(key))
+            .collect()
+    }
+
+    /// Malla OBJ para `key`, compartida entre todas las instancias que la usen.
+    /// Los `mtllib` y `map_Kd` se resuelven relativos a la carpeta del OBJ.
+    pub fn mesh(&mut self, key: &str) -> Result<Rc<Mesh>, MeshError> {
+        if let Some(mesh) = self.meshes.get(key) {
+            return Ok(Rc::clone(mesh));
+        }
+
+        let mesh = match self.load_mesh(key) {
+            Ok(mesh) => Rc::new(mesh),
+            Err(e) => {
+                self.failed_meshes.insert(key.to_string());
+                return Err(e);
+            }
+        };
+        self.failed_meshes.remove(key);
+        self.meshes.insert(key.to_string(), Rc::clone(&mesh));
+        Ok(mesh)
+    }
+
+    /// Vuelve a leer la malla `key`; si falla se conserva la anterior.
+    pub fn reload_mesh(&mut self, key: &str) -> bool {
+        match self.load_mesh(key) {
+            Ok(mesh) => {
+                self.failed_meshes.remove(key);
+                self.meshes.insert(key.to_string(), Rc::new(mesh));
+                true
+            }
+            Err(e) => {
+                eprintln!("No se pudo recargar la malla {}: {} (se mantiene la anterior)", key, e);
+                false
+            }
+        }
+    }
+
+    /// Claves de las mallas (en caché o que fallaron) que dependen de `path`
+    /// (el OBJ o alguno de sus `mtllib`).
+    pub fn mesh_keys_at(&self, path: &Path) -> Vec<String> {
+        self.meshes
+            .keys()
+            .chain(&self.failed_meshes)
+            .filter(|key| self.mesh_files(key).any(|p| p == path))
+            .cloned()
+            .collect()
+    }
+
+    /// Rutas en disco de las mallas (en caché o que fallaron) y de sus MTL.
+    pub fn mesh_paths(&self) -> Vec<PathBuf> {
+        self.meshes
+            .keys()
+            .chain(&self.failed_meshes)
+            .flat_map(|key| self.mesh_files(key))
+            .collect()
+    }
+
+    /// El OBJ de `key` y los MTL que pidió, los que estén en disco.
+    fn mesh_files<'a>(&'a self, key: &'a str) -> impl Iterator<Item = PathBuf> + 'a {
+        let libraries = self.mesh_libraries.get(key).into_iter().flatten();
+        std::iter::once(key)
+            .chain(libraries.map(String::as_str))
+            .filter_map(|k| self.resolve(k))
+    }
+
+    fn load_mesh(&mut self, key: &str) -> Result<Mesh, MeshError> {
+        let src = self.text(key).map_err(|_| MeshError::NotFound(key.to_string()))?;
+        let dir = match key.rfind('/') {
+            Some(i) => &key[..=i],
+            None => "",
+        };
+
+        // Se recuerdan los MTL pedidos (aunque falten) para vigilarlos
+        let mut libraries = Vec::new();
+        let parsed = obj::parse_obj(&src, |mtl| {
+            let lib = format!("{}{}", dir, mtl);
+            let text = self.text(&lib).ok();
+            libraries.push(lib);
+            text
+        });
+        self.mesh_libraries.insert(key.to_string(), libraries);
+        let mut mesh = parsed?;
+        for material in &mut mesh.materials {
+            if let Some(tex) = &material.texture {
+                material.texture = Some(format!("{}{}", dir, tex));
+            }
+        }
+        Ok(mesh)
+    }
+
+    /// Contenido de un asset de texto (escenas), de disco o embebido.
+    pub fn text(&self, key: &str) -> std::io::Result<String> {
+        if let Some(path) = self.resolve(key) {
+            match std::fs::read_to_string(&path) {
+                Ok(text) => return Ok(text),
+                Err(e) => eprintln!("No se pudo leer {}: {}", path.display(), e),
+            }
+        }
+
+        EMBEDDED_TEXT
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, text)| text.to_string())
+            .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, format!("no se encontró el asset {}", key)))
+    }
+
+    fn load_texture(&self, key: &str) -> Result<Texture, TextureError> {
+        let disk_error = match self.resolve(key) {
+            Some(path) => match Texture::from_file(&path.to_string_lossy()) {
+                Ok(tex) => return Ok(tex),
+                Err(e) => {
+                    eprintln!("Textura {} inválida en disco: {}", path.display(), e);
+                    Some(e)
+                }
+            },
+            None => None,
+        };
+
+        match embedded(key) {
+            Some(bytes) => Texture::from_bytes(bytes),
+            None => Err(disk_error.unwrap_or_else(|| TextureError::NotFound(key.to_string()))),
+        }
+    }
+}
+
+fn embedded(key: &str) -> Option<&'static [u8]> {
+    EMBEDDED_TEXTURES
+        .iter()
+        .find(|(k, _)| *k == key)
+        .map(|(_, bytes)| *bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn watches_the_mtllib_the_obj_names() {
+        let root = std::env::temp_dir().join(format!("solar_assets_{}", std::process::id()));
+        std::fs::create_dir_all(root.join("models")).unwrap();
+        std::fs::write(root.join("models/ship.obj"), "mtllib paint.mtl\nv 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 3\n").unwrap();
+        std::fs::write(root.join("models/paint.mtl"), "newmtl hull\nKd 1 1 1\n").unwrap();
+
+        let mut assets = AssetManager::empty();
+        assets.add_root(root.clone());
+        let mesh = assets.mesh("models/ship.obj").unwrap();
+        assert!(mesh.warnings.is_empty());
+
+        let mtl = root.join("models/paint.mtl");
+        assert!(assets.mesh_paths().contains(&mtl));
+        assert_eq!(assets.mesh_keys_at(&mtl), ["models/ship.obj"]);
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
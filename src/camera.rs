@@ -1,12 +1,14 @@
-use std::f32::consts::PI;
-
 use crate::input::InputState;
-use crate::math::Vec3;
+use crate::math::{Mat4, Quat, Vec3};
+
+/// Planos de recorte de la proyección.
+pub const NEAR: f32 = 0.1;
+pub const FAR: f32 = 5000.0;
 
 pub struct Camera {
     pub position: Vec3,
-    pub yaw: f32,
-    pub pitch: f32,
+    /// Orientación libre (6DOF). Con la identidad la cámara mira hacia -Z con +Y arriba.
+    pub orientation: Quat,
     pub fov_y: f32,
 }
 
@@ -14,75 +16,119 @@ impl Camera {
     pub fn new() -> Self {
         Camera {
             position: Vec3::new(0.0, 30.0, 80.0),
-            yaw: 0.0,
-            pitch: -0.3,
+            orientation: Quat::from_axis_angle(Vec3::new(1.0, 0.0, 0.0), -0.3),
             fov_y: 60.0_f32.to_radians(),
         }
     }
 
     pub fn forward(&self) -> Vec3 {
-        let cp = self.pitch.cos();
-        let sp = self.pitch.sin();
-        let cy = self.yaw.cos();
-        let sy = self.yaw.sin();
+        self.orientation.rotate(Vec3::new(0.0, 0.0, -1.0))
+    }
 
-        Vec3::new(sy * cp, sp, -cy * cp).normalized()
+    pub fn right(&self) -> Vec3 {
+        self.orientation.rotate(Vec3::new(1.0, 0.0, 0.0))
     }
 
-    pub fn update(&mut self, dt: f32, input: &InputState) {
-        let move_speed = 50.0;
-        let rot_speed = 1.5;
+    pub fn up(&self) -> Vec3 {
+        self.orientation.rotate(Vec3::up())
+    }
 
-        // Rotación
-        if input.look_left {
-            self.yaw += rot_speed * dt;
-        }
-        if input.look_right {
-            self.yaw -= rot_speed * dt;
-        }
-        if input.look_up {
-            self.pitch += rot_speed * dt;
-        }
-        if input.look_down {
-            self.pitch -= rot_speed * dt;
-        }
+    /// Base de la cámara en el mundo: (right, up, forward). Sale directo del
+    /// cuaternión, así que no se degenera al mirar recto hacia arriba o abajo.
+    pub fn basis(&self) -> (Vec3, Vec3, Vec3) {
+        (self.right(), self.up(), self.forward())
+    }
 
-        // Clamp del pitch
-        let max_pitch = 1.3;
-        if self.pitch > max_pitch {
-            self.pitch = max_pitch;
-        }
-        if self.pitch < -max_pitch {
-            self.pitch = -max_pitch;
-        }
+    /// Matriz mundo -> cámara.
+    pub fn view_matrix(&self) -> Mat4 {
+        let (right, up, forward) = self.basis();
+        Mat4::view_from_basis(self.position, right, up, -forward)
+    }
 
-        let forward = self.forward();
-        let right = Vec3::cross(forward, Vec3::up()).normalized();
-        let mut velocity = Vec3::zero();
+    /// Matriz cámara -> clip para un viewport con esta relación de aspecto.
+    pub fn projection_matrix(&self, aspect: f32) -> Mat4 {
+        Mat4::perspective(self.fov_y, aspect, NEAR, FAR)
+    }
 
-        // Movimiento
-        if input.move_forward {
-            velocity = velocity + forward;
-        }
-        if input.move_back {
-            velocity = velocity - forward;
-        }
-        if input.move_right {
-            velocity = velocity + right;
-        }
-        if input.move_left {
-            velocity = velocity - right;
-        }
-        if input.move_up {
-            velocity.y += 1.0;
-        }
-        if input.move_down {
-            velocity.y -= 1.0;
-        }
+    pub fn view_projection(&self, aspect: f32) -> Mat4 {
+        self.projection_matrix(aspect) * self.view_matrix()
+    }
 
-        if velocity.length() > 0.0 {
-            velocity = velocity.normalized() * move_speed * dt;
-            self.position = self.position + velocity;
-        }
+    /// Cámara en tercera persona: se acerca suavemente a `offset` (en ejes del
+    /// objetivo) y gira para mirar un poco por delante de él, con su mismo "arriba".
+    pub fn follow(&mut self, target: Vec3, target_orientation: Quat, offset: Vec3, dt: f32) {
+        let stiffness = 6.0;
+        let t = 1.0 - (-stiffness * dt).exp();
+
+        let desired = target + target_orientation.rotate(offset);
+        self.position = self.position.lerp(desired, t);
+
+        let look_at = target + target_orientation.rotate(Vec3::new(0.0, 0.0, -10.0));
+        let up = target_orientation.rotate(Vec3::up());
+        let desired_q = Quat::look_rotation(look_at - self.position, up);
+        self.orientation = self.orientation.slerp(desired_q, t).normalized();
+    }
+}
+
+/// Dirección de empuje pedida por el teclado, en coordenadas de mundo (sin normalizar).
+pub fn thrust_input(input: &InputState, orientation: Quat) -> Vec3 {
+    let right = orientation.rotate(Vec3::new(1.0, 0.0, 0.0));
+    let up = orientation.rotate(Vec3::up());
+    let forward = orientation.rotate(Vec3::new(0.0, 0.0, -1.0));
+
+    let mut dir = Vec3::zero();
+    if input.move_forward {
+        dir += forward;
+    }
+    if input.move_back {
+        dir -= forward;
+    }
+    if input.move_right {
+        dir += right;
+    }
+    if input.move_left {
+        dir -= right;
+    }
+    if input.move_up {
+        dir += up;
+    }
+    if input.move_down {
+        dir -= up;
     }
+    dir
+}
+
+/// Giro pedido por el teclado en ejes locales (yaw, pitch, roll), cada uno en [-1, 1].
+/// Positivo = izquierda, arriba y alabeo a la izquierda.
+pub fn rotation_input(input: &InputState) -> Vec3 {
+    let mut wanted = Vec3::zero();
+    if input.look_left {
+        wanted.x += 1.0;
+    }
+    if input.look_right {
+        wanted.x -= 1.0;
+    }
+    if input.look_up {
+        wanted.y += 1.0;
+    }
+    if input.look_down {
+        wanted.y -= 1.0;
+    }
+    if input.roll_left {
+        wanted.z += 1.0;
+    }
+    if input.roll_right {
+        wanted.z -= 1.0;
+    }
+    wanted
+}
+
+/// Gira `orientation` alrededor de sus ejes locales (radianes): `yaw` sobre su up,
+/// `pitch` sobre su right y `roll` sobre su eje de vista.
+/// Positivo = izquierda, arriba y alabeo a la izquierda.
+pub fn rotate_local(orientation: Quat, angles: Vec3) -> Quat {
+    let q = Quat::from_axis_angle(Vec3::up(), angles.x)
+        * Quat::from_axis_angle(Vec3::new(1.0, 0.0, 0.0), angles.y)
+        * Quat::from_axis_angle(Vec3::new(0.0, 0.0, 1.0), angles.z);
+    (orientation * q).normalized()
 }
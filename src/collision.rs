@@ -1,17 +1,19 @@
-use crate::camera::Camera;
 use crate::math::Vec3;
 use crate::world::{BodyKind, SolarSystem};
 
-pub fn resolve_collisions(system: &SolarSystem, camera: &mut Camera) {
-    let margin = 2.0;
+/// Saca `position` (cámara o nave) de los cuerpos y de las partículas de los
+/// cinturones que atraviesa, dejando `margin` de separación. Devuelve la normal
+/// del último contacto, para que el modelo de vuelo pueda anular la velocidad.
+pub fn resolve_collisions(system: &SolarSystem, position: &mut Vec3, margin: f32) -> Option<Vec3> {
+    let mut contact = None;
 
     for i in 0..system.bodies.len() {
         let b = &system.bodies[i];
 
         match b.kind {
-            BodyKind::Star | BodyKind::Planet | BodyKind::Moon => {
+            BodyKind::Star | BodyKind::Planet | BodyKind::Moon | BodyKind::Asteroid | BodyKind::Comet => {
                 let center = system.body_position(i);
-                let to_cam = camera.position - center;
+                let to_cam = *position - center;
                 let dist = to_cam.length();
                 let min_dist = b.radius + margin;
 
@@ -21,9 +23,32 @@ pub fn resolve_collisions(system: &SolarSystem, camera: &mut Camera) {
                     } else {
                         to_cam / dist
                     };
-                    camera.position = center + dir * min_dist;
+                    *position = center + dir * min_dist;
+                    contact = Some(dir);
                 }
             }
         }
     }
+
+    for (i, belt) in system.belts.iter().enumerate() {
+        let center = system.belt_center(i);
+        if !belt.may_contain(*position - center, margin) {
+            continue;
+        }
+
+        for (particle, local) in belt.particles.iter().zip(&belt.positions) {
+            let rock = center + *local;
+            let to_cam = *position - rock;
+            let dist = to_cam.length();
+            let min_dist = particle.size + margin;
+
+            if dist < min_dist {
+                let dir = if dist == 0.0 { Vec3::up() } else { to_cam / dist };
+                *position = rock + dir * min_dist;
+                contact = Some(dir);
+            }
+        }
+    }
+
+    contact
 }
@@ -0,0 +1,261 @@
+use crate::camera::{rotate_local, rotation_input, thrust_input};
+use crate::input::InputState;
+use crate::math::{Quat, Vec3};
+
+/// Multiplicadores de velocidad para cruzar distancias grandes (se rotan con Tab).
+pub const SPEED_MULTIPLIERS: [f32; 4] = [1.0, 5.0, 25.0, 125.0];
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FlightMode {
+    /// Velocidad constante mientras haya teclas apretadas; se detiene al soltarlas.
+    Arcade,
+    /// Modelo newtoniano: el empuje acelera y sin empuje se sigue en línea recta.
+    Inertial,
+}
+
+/// Modelo de vuelo (arcade o inercial). No es dueño de la posición ni de la
+/// orientación, así que sirve tanto para la cámara libre como para la nave.
+pub struct FlightModel {
+    pub mode: FlightMode,
+    pub velocity: Vec3,
+    /// Velocidad angular en ejes locales (yaw, pitch, roll) en rad/s.
+    pub angular_velocity: Vec3,
+
+    /// Aceleración del empuje principal (unidades/s²).
+    pub thrust: f32,
+    /// Aceleración angular de los propulsores de orientación (rad/s²).
+    pub torque: f32,
+    /// Limitador de velocidad (antes del multiplicador).
+    pub max_speed: f32,
+    pub max_angular_speed: f32,
+    /// Desaceleración del freno (unidades/s²).
+    pub brake: f32,
+
+    /// Asistencia: frena la rotación al soltar las teclas y corrige la deriva lateral.
+    pub assist: bool,
+    pub speed_index: usize,
+}
+
+impl FlightModel {
+    pub fn new() -> Self {
+        Self {
+            mode: FlightMode::Arcade,
+            velocity: Vec3::zero(),
+            angular_velocity: Vec3::zero(),
+            thrust: 40.0,
+            torque: 4.0,
+            max_speed: 60.0,
+            max_angular_speed: 1.5,
+            brake: 60.0,
+            assist: true,
+            speed_index: 0,
+        }
+    }
+
+    pub fn speed_multiplier(&self) -> f32 {
+        SPEED_MULTIPLIERS[self.speed_index]
+    }
+
+    pub fn toggle_mode(&mut self) {
+        self.mode = match self.mode {
+            FlightMode::Arcade => FlightMode::Inertial,
+            FlightMode::Inertial => FlightMode::Arcade,
+        };
+        self.stop();
+    }
+
+    pub fn cycle_speed(&mut self) {
+        self.speed_index = (self.speed_index + 1) % SPEED_MULTIPLIERS.len();
+    }
+
+    /// Detiene toda la inercia (p. ej. al terminar un warp).
+    pub fn stop(&mut self) {
+        self.velocity = Vec3::zero();
+        self.angular_velocity = Vec3::zero();
+    }
+
+    /// Quita la componente de la velocidad que va contra `normal` (choque con un cuerpo).
+    pub fn on_collision(&mut self, normal: Vec3) {
+        let into = self.velocity.dot(normal);
+        if into < 0.0 {
+            self.velocity -= normal * into;
+        }
+    }
+
+    /// Mueve la posición y orientación dadas según el modo de vuelo.
+    pub fn update(&mut self, dt: f32, input: &InputState, position: &mut Vec3, orientation: &mut Quat) {
+        match self.mode {
+            FlightMode::Arcade => self.update_arcade(dt, input, position, orientation),
+            FlightMode::Inertial => self.update_inertial(dt, input, position, orientation),
+        }
+    }
+
+    fn update_arcade(&mut self, dt: f32, input: &InputState, position: &mut Vec3, orientation: &mut Quat) {
+        let move_speed = 50.0 * self.speed_multiplier();
+        let rot_speed = 1.5;
+
+        // Rotación (sin límite de pitch: la orientación es un cuaternión)
+        let turn = rotation_input(input) * rot_speed * dt;
+        *orientation = rotate_local(*orientation, turn);
+
+        let direction = thrust_input(input, *orientation);
+        self.velocity = if direction.length() > 0.0 {
+            direction.normalized() * move_speed
+        } else {
+            Vec3::zero()
+        };
+        *position += self.velocity * dt;
+    }
+
+    /// Integra un paso del modelo inercial.
+    fn update_inertial(&mut self, dt: f32, input: &InputState, position: &mut Vec3, orientation: &mut Quat) {
+        self.update_rotation(dt, input, orientation);
+
+        let forward = orientation.rotate(Vec3::new(0.0, 0.0, -1.0));
+        let multiplier = self.speed_multiplier();
+
+        // Empuje en ejes locales
+        let dir = thrust_input(input, *orientation);
+        let thrusting = dir.length() > 0.0;
+        if thrusting {
+            self.velocity += dir.normalized() * self.thrust * multiplier * dt;
+        }
+
+        if input.brake {
+            self.velocity = approach_zero(self.velocity, self.brake * multiplier * dt);
+        } else if self.assist && !thrusting {
+            // Corrige la deriva: la componente lateral se frena, la de avance se conserva
+            let along = forward * self.velocity.dot(forward);
+            let lateral = self.velocity - along;
+            self.velocity = along + approach_zero(lateral, self.brake * 0.5 * multiplier * dt);
+        }
+
+        // Limitador de velocidad
+        let max_speed = self.max_speed * multiplier;
+        let speed = self.velocity.length();
+        if speed > max_speed {
+            self.velocity = self.velocity * (max_speed / speed);
+        }
+
+        *position += self.velocity * dt;
+    }
+
+    fn update_rotation(&mut self, dt: f32, input: &InputState, orientation: &mut Quat) {
+        let wanted = rotation_input(input);
+        let step = self.torque * dt;
+        if wanted.length() > 0.0 {
+            self.angular_velocity += wanted * step;
+        } else if self.assist || input.brake {
+            self.angular_velocity = approach_zero(self.angular_velocity, step);
+        }
+
+        let spin = self.angular_velocity.length();
+        if spin > self.max_angular_speed {
+            self.angular_velocity = self.angular_velocity * (self.max_angular_speed / spin);
+        }
+
+        *orientation = rotate_local(*orientation, self.angular_velocity * dt);
+    }
+
+    /// `true` si en este frame se está acelerando (para la llama del motor).
+    pub fn is_thrusting(input: &InputState) -> bool {
+        input.move_forward || input.move_back || input.move_left || input.move_right || input.move_up || input.move_down
+    }
+}
+
+/// Reduce la longitud de `v` en `amount` sin pasarse de cero.
+fn approach_zero(v: Vec3, amount: f32) -> Vec3 {
+    let len = v.length();
+    if len <= amount {
+        Vec3::zero()
+    } else {
+        v * ((len - amount) / len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DT: f32 = 1.0 / 60.0;
+
+    fn inertial() -> FlightModel {
+        let mut flight = FlightModel::new();
+        flight.mode = FlightMode::Inertial;
+        flight
+    }
+
+    /// Corre `steps` pasos con la misma entrada; devuelve la posición final.
+    fn fly(flight: &mut FlightModel, input: &InputState, steps: usize) -> Vec3 {
+        let mut position = Vec3::zero();
+        let mut orientation = Quat::identity();
+        for _ in 0..steps {
+            flight.update(DT, input, &mut position, &mut orientation);
+        }
+        position
+    }
+
+    #[test]
+    fn coasting_keeps_the_velocity() {
+        let idle = InputState::new();
+        // Sin asistencia se conserva todo; con asistencia, la componente de avance
+        for (assist, velocity) in [(false, Vec3::new(3.0, -2.0, 7.0)), (true, Vec3::new(0.0, 0.0, -9.0))] {
+            let mut flight = inertial();
+            flight.assist = assist;
+            flight.velocity = velocity;
+            let position = fly(&mut flight, &idle, 120);
+
+            assert!((flight.velocity - velocity).length() < 1e-5, "{:?}", flight.velocity);
+            assert!((position - velocity * (120.0 * DT)).length() < 1e-3, "{:?}", position);
+        }
+    }
+
+    #[test]
+    fn speed_is_clamped_to_the_multiplied_limit() {
+        let mut input = InputState::new();
+        input.move_forward = true;
+        for (i, multiplier) in SPEED_MULTIPLIERS.iter().enumerate() {
+            let mut flight = inertial();
+            flight.speed_index = i;
+            fly(&mut flight, &input, 600);
+
+            let limit = flight.max_speed * multiplier;
+            let speed = flight.velocity.length();
+            assert!((speed - limit).abs() < limit * 1e-4, "x{}: {} vs {}", multiplier, speed, limit);
+        }
+    }
+
+    #[test]
+    fn brake_and_assist_reach_zero_in_finite_time() {
+        let mut flight = inertial();
+        flight.velocity = Vec3::new(0.0, 0.0, -flight.max_speed);
+        let mut input = InputState::new();
+        input.brake = true;
+        // v / a segundos, más un paso de redondeo
+        let steps = (flight.max_speed / (flight.brake * DT)).ceil() as usize + 1;
+        fly(&mut flight, &input, steps);
+        assert_eq!(flight.velocity, Vec3::zero());
+
+        // La asistencia anula la deriva lateral (a media desaceleración)
+        let mut flight = inertial();
+        flight.velocity = Vec3::new(flight.max_speed, 0.0, 0.0);
+        let steps = (flight.max_speed / (flight.brake * 0.5 * DT)).ceil() as usize + 1;
+        fly(&mut flight, &InputState::new(), steps);
+        assert_eq!(flight.velocity, Vec3::zero());
+    }
+
+    #[test]
+    fn collision_removes_only_the_normal_component() {
+        let mut flight = inertial();
+        let normal = Vec3::new(0.0, 1.0, 0.0);
+
+        flight.velocity = Vec3::new(4.0, -5.0, 2.0);
+        flight.on_collision(normal);
+        assert_eq!(flight.velocity, Vec3::new(4.0, 0.0, 2.0));
+
+        // Si ya se aleja de la superficie no se toca
+        flight.velocity = Vec3::new(4.0, 5.0, 2.0);
+        flight.on_collision(normal);
+        assert_eq!(flight.velocity, Vec3::new(4.0, 5.0, 2.0));
+    }
+}
@@ -0,0 +1,55 @@
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+/// Vigila archivos por polling de la fecha de modificación.
+/// No depende de inotify, así que funciona igual en todas las plataformas.
+pub struct FileWatcher {
+    files: Vec<(PathBuf, Option<SystemTime>)>,
+    interval: Duration,
+    last_poll: Instant,
+}
+
+impl FileWatcher {
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            files: Vec::new(),
+            interval,
+            last_poll: Instant::now(),
+        }
+    }
+
+    pub fn watch(&mut self, path: PathBuf) {
+        if self.files.iter().any(|(p, _)| *p == path) {
+            return;
+        }
+        let mtime = modified(&path);
+        self.files.push((path, mtime));
+    }
+
+    pub fn clear(&mut self) {
+        self.files.clear();
+    }
+
+    /// Archivos que cambiaron desde la última revisión. Como mucho revisa
+    /// una vez por `interval`, así que se puede llamar en cada frame.
+    pub fn poll(&mut self) -> Vec<PathBuf> {
+        if self.last_poll.elapsed() < self.interval {
+            return Vec::new();
+        }
+        self.last_poll = Instant::now();
+
+        let mut changed = Vec::new();
+        for (path, last) in &mut self.files {
+            let now = modified(path);
+            if now.is_some() && now != *last {
+                *last = now;
+                changed.push(path.clone());
+            }
+        }
+        changed
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
@@ -1,4 +1,4 @@
-use minifb::{Key, Window};
+use minifb::{Key, KeyRepeat, Window};
 
 pub struct InputState {
     pub move_forward: bool,
@@ -12,11 +12,27 @@ pub struct InputState {
     pub look_right: bool,
     pub look_up: bool,
     pub look_down: bool,
+    pub roll_left: bool,
+    pub roll_right: bool,
 
     pub warp_1: bool,
     pub warp_2: bool,
     pub warp_3: bool,
     pub warp_animated: bool,
+
+    // Modelo de vuelo
+    pub brake: bool,
+    /// Estas tres solo valen `true` en el frame en que se aprieta la tecla.
+    pub toggle_flight: bool,
+    pub toggle_assist: bool,
+    pub cycle_speed: bool,
+    /// Alterna entre cámara de persecución y cámara libre (solo en el frame de la tecla).
+    pub toggle_camera: bool,
+    /// Cielo con el catálogo de estrellas o con la imagen, y líneas de constelaciones.
+    pub toggle_starfield: bool,
+    pub toggle_constellations: bool,
+    /// Informa qué estrella hay adelante y en qué cinturón se está (solo en el frame de la tecla).
+    pub identify: bool,
 }
 
 impl InputState {
@@ -32,10 +48,20 @@ impl InputState {
             look_right: false,
             look_up: false,
             look_down: false,
+            roll_left: false,
+            roll_right: false,
             warp_1: false,
             warp_2: false,
             warp_3: false,
             warp_animated: false,
+            brake: false,
+            toggle_flight: false,
+            toggle_assist: false,
+            cycle_speed: false,
+            toggle_camera: false,
+            toggle_starfield: false,
+            toggle_constellations: false,
+            identify: false,
         }
     }
 
@@ -51,11 +77,22 @@ impl InputState {
         self.look_right = window.is_key_down(Key::Right);
         self.look_up = window.is_key_down(Key::Up);
         self.look_down = window.is_key_down(Key::Down);
+        self.roll_left = window.is_key_down(Key::Z);
+        self.roll_right = window.is_key_down(Key::C);
 
         self.warp_1 = window.is_key_down(Key::Key1);
         self.warp_2 = window.is_key_down(Key::Key2);
         self.warp_3 = window.is_key_down(Key::Key3);
 
         self.warp_animated = window.is_key_down(Key::Space);
+
+        self.brake = window.is_key_down(Key::X);
+        self.toggle_flight = window.is_key_pressed(Key::F, KeyRepeat::No);
+        self.toggle_assist = window.is_key_pressed(Key::G, KeyRepeat::No);
+        self.cycle_speed = window.is_key_pressed(Key::Tab, KeyRepeat::No);
+        self.toggle_camera = window.is_key_pressed(Key::V, KeyRepeat::No);
+        self.toggle_starfield = window.is_key_pressed(Key::K, KeyRepeat::No);
+        self.toggle_constellations = window.is_key_pressed(Key::L, KeyRepeat::No);
+        self.identify = window.is_key_pressed(Key::I, KeyRepeat::No);
     }
 }
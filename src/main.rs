@@ -1,17 +1,69 @@
+
 mod app;
+mod assets;
 mod renderer;
 mod math;
+mod mesh;
 mod world;
 mod camera;
 mod warp;
 mod skybox;
 mod collision;
+mod flight;
+mod hotreload;
 mod input;
+mod ship;
 mod texture;   
 
-use app::App;
+use app::{App, SystemSource};
+use skybox::SkyFormat;
+use math::rng::seed_from_str;
+use world::solar::{self, Scale};
 
 fn main() {
-    let mut app = App::new(800, 600);
+    // `--stars` arranca con el cielo del catálogo en vez de la imagen
+    let stars = std::env::args().any(|a| a == "--stars");
+    let mut app = App::new(800, 600, source_from_args(), sky_format_from_args(), stars);
     app.run();
 }
+
+/// `--seed <n>` genera un sistema nuevo; la semilla puede ser un número o
+/// cualquier texto ("--seed andromeda").
+/// `--real [AAAA-MM-DD]` muestra el Sistema Solar en esa fecha (por defecto hoy),
+/// con los radios exagerados salvo que se agregue `--true-scale`.
+fn source_from_args() -> SystemSource {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let value_after = |flag: &str| {
+        let at = args.iter().position(|a| a == flag)?;
+        Some(args.get(at + 1).filter(|v| !v.starts_with("--")).cloned())
+    };
+
+    if let Some(Some(value)) = value_after("--seed") {
+        return SystemSource::Generated(value.parse().unwrap_or_else(|_| seed_from_str(&value)));
+    }
+    if let Some(date) = value_after("--real") {
+        let date = match date {
+            Some(text) => solar::parse_date(&text).unwrap_or_else(|| {
+                eprintln!("Fecha inválida: {} (se espera AAAA-MM-DD; se usa la de hoy)", text);
+                solar::today()
+            }),
+            None => solar::today(),
+        };
+        let scale = if args.iter().any(|a| a == "--true-scale") { Scale::True } else { Scale::Exaggerated };
+        return SystemSource::Real { date, scale };
+    }
+    SystemSource::Scene
+}
+
+/// `--sky cubemap|equirect` convierte el cielo al cargarlo; sin la opción se
+/// dibuja en el formato del archivo encontrado.
+fn sky_format_from_args() -> Option<SkyFormat> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let at = args.iter().position(|a| a == "--sky")?;
+    let name = args.get(at + 1)?;
+    let format = SkyFormat::from_name(name);
+    if format.is_none() {
+        eprintln!("Formato de cielo desconocido: {} (se espera cubemap o equirect)", name);
+    }
+    format
+}
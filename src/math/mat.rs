@@ -0,0 +1,312 @@
+use std::ops::Mul;
+
+use super::{Vec3, Vec4};
+
+/// Matriz 3x3 por filas (`m[fila][columna]`); multiplica vectores columna.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Mat3 {
+    pub m: [[f32; 3]; 3],
+}
+
+impl Mat3 {
+    pub fn new(m: [[f32; 3]; 3]) -> Self {
+        Self { m }
+    }
+
+    pub fn from_cols(c0: Vec3, c1: Vec3, c2: Vec3) -> Self {
+        Self::new([
+            [c0.x, c1.x, c2.x],
+            [c0.y, c1.y, c2.y],
+            [c0.z, c1.z, c2.z],
+        ])
+    }
+
+    pub fn row(&self, i: usize) -> Vec3 {
+        Vec3::new(self.m[i][0], self.m[i][1], self.m[i][2])
+    }
+
+    pub fn scale(s: Vec3) -> Self {
+        Self::new([[s.x, 0.0, 0.0], [0.0, s.y, 0.0], [0.0, 0.0, s.z]])
+    }
+
+    pub fn transpose(&self) -> Self {
+        let m = &self.m;
+        Self::new([
+            [m[0][0], m[1][0], m[2][0]],
+            [m[0][1], m[1][1], m[2][1]],
+            [m[0][2], m[1][2], m[2][2]],
+        ])
+    }
+
+    pub fn determinant(&self) -> f32 {
+        let m = &self.m;
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    }
+
+    /// Inversa, o `None` si la matriz es singular.
+    pub fn inverse(&self) -> Option<Self> {
+        let det = self.determinant();
+        if det.abs() < 1e-12 {
+            return None;
+        }
+
+        // Las columnas de la adjunta transpuesta son productos cruz de las filas
+        let r0 = self.row(0);
+        let r1 = self.row(1);
+        let r2 = self.row(2);
+        let c0 = Vec3::cross(r1, r2) / det;
+        let c1 = Vec3::cross(r2, r0) / det;
+        let c2 = Vec3::cross(r0, r1) / det;
+
+        Some(Self::from_cols(c0, c1, c2))
+    }
+}
+
+impl Mul for Mat3 {
+    type Output = Mat3;
+    fn mul(self, o: Mat3) -> Mat3 {
+        let mut r = [[0.0; 3]; 3];
+        for (i, row) in r.iter_mut().enumerate() {
+            for (j, cell) in row.iter_mut().enumerate() {
+                *cell = (0..3).map(|k| self.m[i][k] * o.m[k][j]).sum();
+            }
+        }
+        Mat3::new(r)
+    }
+}
+
+impl Mul<Vec3> for Mat3 {
+    type Output = Vec3;
+    fn mul(self, v: Vec3) -> Vec3 {
+        Vec3::new(self.row(0).dot(v), self.row(1).dot(v), self.row(2).dot(v))
+    }
+}
+
+/// Matriz 4x4 por filas (`m[fila][columna]`); multiplica vectores columna.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Mat4 {
+    pub m: [[f32; 4]; 4],
+}
+
+impl Mat4 {
+    pub fn new(m: [[f32; 4]; 4]) -> Self {
+        Self { m }
+    }
+
+    pub fn from_mat3(r: &Mat3) -> Self {
+        let m = &r.m;
+        Self::new([
+            [m[0][0], m[0][1], m[0][2], 0.0],
+            [m[1][0], m[1][1], m[1][2], 0.0],
+            [m[2][0], m[2][1], m[2][2], 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    pub fn to_mat3(self) -> Mat3 {
+        let m = &self.m;
+        Mat3::new([
+            [m[0][0], m[0][1], m[0][2]],
+            [m[1][0], m[1][1], m[1][2]],
+            [m[2][0], m[2][1], m[2][2]],
+        ])
+    }
+
+    pub fn translation(t: Vec3) -> Self {
+        Self::new([
+            [1.0, 0.0, 0.0, t.x],
+            [0.0, 1.0, 0.0, t.y],
+            [0.0, 0.0, 1.0, t.z],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    pub fn scale(s: Vec3) -> Self {
+        Self::from_mat3(&Mat3::scale(s))
+    }
+
+    /// Proyección en perspectiva estilo OpenGL (cámara mirando a -Z, NDC z en [-1, 1]).
+    /// La `w` resultante es la distancia a lo largo de la vista (`-z` de cámara).
+    pub fn perspective(fov_y: f32, aspect: f32, near: f32, far: f32) -> Self {
+        let f = 1.0 / (fov_y * 0.5).tan();
+        let nf = 1.0 / (near - far);
+        Self::new([
+            [f / aspect, 0.0, 0.0, 0.0],
+            [0.0, f, 0.0, 0.0],
+            [0.0, 0.0, (far + near) * nf, 2.0 * far * near * nf],
+            [0.0, 0.0, -1.0, 0.0],
+        ])
+    }
+
+    /// Vista desde `eye` con la base ortonormal dada (la cámara mira hacia -`back`).
+    pub fn view_from_basis(eye: Vec3, right: Vec3, up: Vec3, back: Vec3) -> Self {
+        Self::new([
+            [right.x, right.y, right.z, -right.dot(eye)],
+            [up.x, up.y, up.z, -up.dot(eye)],
+            [back.x, back.y, back.z, -back.dot(eye)],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    /// Inversa general por cofactores, o `None` si la matriz es singular. Solo
+    /// la usan las pruebas para deshacer transformaciones.
+    #[cfg(test)]
+    pub fn inverse(&self) -> Option<Self> {
+        let a = &self.m;
+
+        let s0 = a[0][0] * a[1][1] - a[1][0] * a[0][1];
+        let s1 = a[0][0] * a[1][2] - a[1][0] * a[0][2];
+        let s2 = a[0][0] * a[1][3] - a[1][0] * a[0][3];
+        let s3 = a[0][1] * a[1][2] - a[1][1] * a[0][2];
+        let s4 = a[0][1] * a[1][3] - a[1][1] * a[0][3];
+        let s5 = a[0][2] * a[1][3] - a[1][2] * a[0][3];
+
+        let c5 = a[2][2] * a[3][3] - a[3][2] * a[2][3];
+        let c4 = a[2][1] * a[3][3] - a[3][1] * a[2][3];
+        let c3 = a[2][1] * a[3][2] - a[3][1] * a[2][2];
+        let c2 = a[2][0] * a[3][3] - a[3][0] * a[2][3];
+        let c1 = a[2][0] * a[3][2] - a[3][0] * a[2][2];
+        let c0 = a[2][0] * a[3][1] - a[3][0] * a[2][1];
+
+        let det = s0 * c5 - s1 * c4 + s2 * c3 + s3 * c2 - s4 * c1 + s5 * c0;
+        if det.abs() < 1e-12 {
+            return None;
+        }
+        let inv = 1.0 / det;
+
+        Some(Self::new([
+            [
+                (a[1][1] * c5 - a[1][2] * c4 + a[1][3] * c3) * inv,
+                (-a[0][1] * c5 + a[0][2] * c4 - a[0][3] * c3) * inv,
+                (a[3][1] * s5 - a[3][2] * s4 + a[3][3] * s3) * inv,
+                (-a[2][1] * s5 + a[2][2] * s4 - a[2][3] * s3) * inv,
+            ],
+            [
+                (-a[1][0] * c5 + a[1][2] * c2 - a[1][3] * c1) * inv,
+                (a[0][0] * c5 - a[0][2] * c2 + a[0][3] * c1) * inv,
+                (-a[3][0] * s5 + a[3][2] * s2 - a[3][3] * s1) * inv,
+                (a[2][0] * s5 - a[2][2] * s2 + a[2][3] * s1) * inv,
+            ],
+            [
+                (a[1][0] * c4 - a[1][1] * c2 + a[1][3] * c0) * inv,
+                (-a[0][0] * c4 + a[0][1] * c2 - a[0][3] * c0) * inv,
+                (a[3][0] * s4 - a[3][1] * s2 + a[3][3] * s0) * inv,
+                (-a[2][0] * s4 + a[2][1] * s2 - a[2][3] * s0) * inv,
+            ],
+            [
+                (-a[1][0] * c3 + a[1][1] * c1 - a[1][2] * c0) * inv,
+                (a[0][0] * c3 - a[0][1] * c1 + a[0][2] * c0) * inv,
+                (-a[3][0] * s3 + a[3][1] * s1 - a[3][2] * s0) * inv,
+                (a[2][0] * s3 - a[2][1] * s1 + a[2][2] * s0) * inv,
+            ],
+        ]))
+    }
+
+    /// Transforma un punto (w = 1) sin dividir por w.
+    pub fn transform_point(&self, p: Vec3) -> Vec3 {
+        (*self * p.extend(1.0)).xyz()
+    }
+}
+
+impl Mul for Mat4 {
+    type Output = Mat4;
+    fn mul(self, o: Mat4) -> Mat4 {
+        let mut r = [[0.0; 4]; 4];
+        for (i, row) in r.iter_mut().enumerate() {
+            for (j, cell) in row.iter_mut().enumerate() {
+                *cell = (0..4).map(|k| self.m[i][k] * o.m[k][j]).sum();
+            }
+        }
+        Mat4::new(r)
+    }
+}
+
+impl Mul<Vec4> for Mat4 {
+    type Output = Vec4;
+    fn mul(self, v: Vec4) -> Vec4 {
+        let row = |i: usize| Vec4::new(self.m[i][0], self.m[i][1], self.m[i][2], self.m[i][3]);
+        Vec4::new(row(0).dot(v), row(1).dot(v), row(2).dot(v), row(3).dot(v))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::Quat;
+    use std::f32::consts::FRAC_PI_2;
+
+    fn assert_close(a: f32, b: f32, eps: f32) {
+        assert!((a - b).abs() <= eps, "{} != {} (±{})", a, b, eps);
+    }
+
+    fn assert_identity(m: &Mat4, eps: f32) {
+        for (i, row) in m.m.iter().enumerate() {
+            for (j, &cell) in row.iter().enumerate() {
+                assert_close(cell, if i == j { 1.0 } else { 0.0 }, eps);
+            }
+        }
+    }
+
+    fn ndc(m: &Mat4, p: Vec3) -> Vec3 {
+        (*m * p.extend(1.0)).perspective_divide()
+    }
+
+    #[test]
+    fn perspective_maps_frustum_to_ndc() {
+        let (fov, aspect, near, far) = (1.0f32, 1.5, 0.1, 5000.0);
+        let m = Mat4::perspective(fov, aspect, near, far);
+
+        // Los planos cercano y lejano van a z = -1 y z = +1
+        assert_close(ndc(&m, Vec3::new(0.0, 0.0, -near)).z, -1.0, 1e-5);
+        assert_close(ndc(&m, Vec3::new(0.0, 0.0, -far)).z, 1.0, 1e-3);
+
+        // Los bordes del campo visual caen en ±1, con el ancho escalado por el aspecto
+        let d = 10.0;
+        let half_h = (fov * 0.5).tan() * d;
+        let top = ndc(&m, Vec3::new(0.0, half_h, -d));
+        let right = ndc(&m, Vec3::new(half_h * aspect, 0.0, -d));
+        assert_close(top.y, 1.0, 1e-5);
+        assert_close(right.x, 1.0, 1e-5);
+
+        // w es la distancia a lo largo de la vista
+        assert_close((m * Vec3::new(3.0, -2.0, -d).extend(1.0)).w, d, 1e-5);
+    }
+
+    #[test]
+    fn inverse_round_trips() {
+        let model = Mat4::translation(Vec3::new(3.0, -7.0, 12.0))
+            * Quat::from_axis_angle(Vec3::up(), 0.7).to_mat4()
+            * Mat4::scale(Vec3::new(2.0, 0.5, 3.0));
+        let inv = model.inverse().expect("la matriz de modelo es invertible");
+        assert_identity(&(model * inv), 1e-5);
+        assert_identity(&(inv * model), 1e-5);
+
+        let p = Vec3::new(-1.5, 4.0, 0.25);
+        let back = inv.transform_point(model.transform_point(p));
+        assert_close(back.x, p.x, 1e-4);
+        assert_close(back.y, p.y, 1e-4);
+        assert_close(back.z, p.z, 1e-4);
+    }
+
+    #[test]
+    fn perspective_inverse_unprojects() {
+        let proj = Mat4::perspective(FRAC_PI_2, 4.0 / 3.0, 0.1, 100.0);
+        let inv = proj.inverse().expect("la proyección es invertible");
+        assert_identity(&(proj * inv), 1e-4);
+
+        // De clip de vuelta a cámara
+        let p = Vec3::new(2.0, -1.0, -25.0);
+        let clip = proj * p.extend(1.0);
+        let back = inv * clip;
+        assert_close(back.x / back.w, p.x, 1e-3);
+        assert_close(back.y / back.w, p.y, 1e-3);
+        assert_close(back.z / back.w, p.z, 1e-3);
+    }
+
+    #[test]
+    fn singular_matrix_has_no_inverse() {
+        assert!(Mat4::scale(Vec3::new(1.0, 0.0, 1.0)).inverse().is_none());
+    }
+}
@@ -1,4 +1,18 @@
 // mod.rs
+pub mod mat;
+pub mod noise;
+pub mod quat;
+pub mod rng;
 pub mod vec;
 
-pub use vec::{Vec2, Vec3};
+pub use mat::{Mat3, Mat4};
+pub use noise::Noise3;
+pub use quat::Quat;
+pub use rng::Rng;
+pub use vec::{Vec2, Vec3, Vec4};
+
+/// Transición suave (Hermite) de 0 a 1 entre `edge0` y `edge1`.
+pub fn smoothstep(edge0: f32, edge1: f32, x: f32) -> f32 {
+    let t = ((x - edge0) / (edge1 - edge0)).clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
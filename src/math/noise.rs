@@ -0,0 +1,90 @@
+use super::{Rng, Vec3};
+
+/// Ruido de gradiente 3D (estilo Perlin) con la tabla de permutación sacada
+/// de una semilla. Devuelve valores aproximadamente en [-1, 1].
+#[derive(Clone, Debug)]
+pub struct Noise3 {
+    perm: [u8; 512],
+}
+
+const GRADIENTS: [[f32; 3]; 12] = [
+    [1.0, 1.0, 0.0],
+    [-1.0, 1.0, 0.0],
+    [1.0, -1.0, 0.0],
+    [-1.0, -1.0, 0.0],
+    [1.0, 0.0, 1.0],
+    [-1.0, 0.0, 1.0],
+    [1.0, 0.0, -1.0],
+    [-1.0, 0.0, -1.0],
+    [0.0, 1.0, 1.0],
+    [0.0, -1.0, 1.0],
+    [0.0, 1.0, -1.0],
+    [0.0, -1.0, -1.0],
+];
+
+impl Noise3 {
+    pub fn new(seed: u64) -> Self {
+        let mut rng = Rng::new(seed);
+        let mut table: [u8; 256] = std::array::from_fn(|i| i as u8);
+        // Fisher-Yates
+        for i in (1..256).rev() {
+            let j = (rng.next_u32() as usize) % (i + 1);
+            table.swap(i, j);
+        }
+
+        let mut perm = [0u8; 512];
+        for (i, p) in perm.iter_mut().enumerate() {
+            *p = table[i & 255];
+        }
+        Self { perm }
+    }
+
+    pub fn sample(&self, p: Vec3) -> f32 {
+        let (xi, xf) = split(p.x);
+        let (yi, yf) = split(p.y);
+        let (zi, zf) = split(p.z);
+        let (u, v, w) = (fade(xf), fade(yf), fade(zf));
+
+        let corner = |dx: usize, dy: usize, dz: usize| -> f32 {
+            let h = self.perm[self.perm[self.perm[xi + dx] as usize + yi + dy] as usize + zi + dz];
+            let g = GRADIENTS[h as usize % 12];
+            g[0] * (xf - dx as f32) + g[1] * (yf - dy as f32) + g[2] * (zf - dz as f32)
+        };
+
+        let x00 = lerp(corner(0, 0, 0), corner(1, 0, 0), u);
+        let x10 = lerp(corner(0, 1, 0), corner(1, 1, 0), u);
+        let x01 = lerp(corner(0, 0, 1), corner(1, 0, 1), u);
+        let x11 = lerp(corner(0, 1, 1), corner(1, 1, 1), u);
+        lerp(lerp(x00, x10, v), lerp(x01, x11, v), w)
+    }
+
+    /// Suma de `octaves` capas de ruido, cada una al doble de frecuencia y la
+    /// mitad de amplitud. Normalizada para quedar en [-1, 1] aproximadamente.
+    pub fn fbm(&self, p: Vec3, octaves: u32) -> f32 {
+        let mut sum = 0.0;
+        let mut amplitude = 1.0;
+        let mut frequency = 1.0;
+        let mut total = 0.0;
+        for _ in 0..octaves {
+            sum += self.sample(p * frequency) * amplitude;
+            total += amplitude;
+            amplitude *= 0.5;
+            frequency *= 2.0;
+        }
+        sum / total
+    }
+}
+
+/// Parte entera (módulo 256, para indexar la tabla) y fraccionaria.
+fn split(x: f32) -> (usize, f32) {
+    let floor = x.floor();
+    ((floor as i64).rem_euclid(256) as usize, x - floor)
+}
+
+fn fade(t: f32) -> f32 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+fn lerp(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
@@ -0,0 +1,296 @@
+use std::ops::Mul;
+
+use super::{Mat3, Mat4, Vec3};
+
+/// Cuaternión unitario para orientaciones (w + xi + yj + zk).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Quat {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub w: f32,
+}
+
+impl Quat {
+    pub fn new(x: f32, y: f32, z: f32, w: f32) -> Self {
+        Self { x, y, z, w }
+    }
+
+    pub fn identity() -> Self {
+        Self::new(0.0, 0.0, 0.0, 1.0)
+    }
+
+    /// Rotación de `angle` radianes alrededor de `axis` (regla de la mano derecha).
+    pub fn from_axis_angle(axis: Vec3, angle: f32) -> Self {
+        let axis = axis.normalized();
+        let (s, c) = (angle * 0.5).sin_cos();
+        Self::new(axis.x * s, axis.y * s, axis.z * s, c)
+    }
+
+    /// Orientación a partir de una base ortonormal (columnas right, up, back).
+    pub fn from_mat3(m: &Mat3) -> Self {
+        let m = &m.m;
+        let trace = m[0][0] + m[1][1] + m[2][2];
+
+        let q = if trace > 0.0 {
+            let s = (trace + 1.0).sqrt() * 2.0;
+            Self::new(
+                (m[2][1] - m[1][2]) / s,
+                (m[0][2] - m[2][0]) / s,
+                (m[1][0] - m[0][1]) / s,
+                0.25 * s,
+            )
+        } else if m[0][0] > m[1][1] && m[0][0] > m[2][2] {
+            let s = (1.0 + m[0][0] - m[1][1] - m[2][2]).sqrt() * 2.0;
+            Self::new(
+                0.25 * s,
+                (m[0][1] + m[1][0]) / s,
+                (m[0][2] + m[2][0]) / s,
+                (m[2][1] - m[1][2]) / s,
+            )
+        } else if m[1][1] > m[2][2] {
+            let s = (1.0 + m[1][1] - m[0][0] - m[2][2]).sqrt() * 2.0;
+            Self::new(
+                (m[0][1] + m[1][0]) / s,
+                0.25 * s,
+                (m[1][2] + m[2][1]) / s,
+                (m[0][2] - m[2][0]) / s,
+            )
+        } else {
+            let s = (1.0 + m[2][2] - m[0][0] - m[1][1]).sqrt() * 2.0;
+            Self::new(
+                (m[0][2] + m[2][0]) / s,
+                (m[1][2] + m[2][1]) / s,
+                0.25 * s,
+                (m[1][0] - m[0][1]) / s,
+            )
+        };
+
+        q.normalized()
+    }
+
+    /// Orientación que mira hacia `forward` con `up` como referencia
+    /// (convención de cámara: mira hacia -Z local).
+    pub fn look_rotation(forward: Vec3, up: Vec3) -> Self {
+        let back = (-forward).normalized();
+        let mut right = Vec3::cross(up, back);
+        if right.length() < 1e-6 {
+            // forward paralelo a up: cualquier perpendicular sirve
+            right = Vec3::cross(Vec3::new(1.0, 0.0, 0.0), back);
+            if right.length() < 1e-6 {
+                right = Vec3::cross(Vec3::new(0.0, 0.0, 1.0), back);
+            }
+        }
+        let right = right.normalized();
+        let up = Vec3::cross(back, right);
+
+        Self::from_mat3(&Mat3::from_cols(right, up, back))
+    }
+
+    pub fn dot(self, other: Quat) -> f32 {
+        self.x * other.x + self.y * other.y + self.z * other.z + self.w * other.w
+    }
+
+    pub fn length(self) -> f32 {
+        self.dot(self).sqrt()
+    }
+
+    pub fn normalized(self) -> Self {
+        let len = self.length();
+        if len == 0.0 {
+            Self::identity()
+        } else {
+            Self::new(self.x / len, self.y / len, self.z / len, self.w / len)
+        }
+    }
+
+    pub fn conjugate(self) -> Self {
+        Self::new(-self.x, -self.y, -self.z, self.w)
+    }
+
+    pub fn rotate(self, v: Vec3) -> Vec3 {
+        // v' = v + 2w(q x v) + 2 q x (q x v)
+        let q = Vec3::new(self.x, self.y, self.z);
+        let t = Vec3::cross(q, v) * 2.0;
+        v + t * self.w + Vec3::cross(q, t)
+    }
+
+    pub fn slerp(self, target: Quat, t: f32) -> Quat {
+        let mut target = target;
+        let mut cos_theta = self.dot(target);
+
+        // Camino corto
+        if cos_theta < 0.0 {
+            target = Quat::new(-target.x, -target.y, -target.z, -target.w);
+            cos_theta = -cos_theta;
+        }
+
+        if cos_theta > 0.9995 {
+            let q = Quat::new(
+                self.x + (target.x - self.x) * t,
+                self.y + (target.y - self.y) * t,
+                self.z + (target.z - self.z) * t,
+                self.w + (target.w - self.w) * t,
+            );
+            return q.normalized();
+        }
+
+        let theta = cos_theta.acos();
+        let sin_theta = theta.sin();
+        let a = ((1.0 - t) * theta).sin() / sin_theta;
+        let b = (t * theta).sin() / sin_theta;
+
+        Quat::new(
+            self.x * a + target.x * b,
+            self.y * a + target.y * b,
+            self.z * a + target.z * b,
+            self.w * a + target.w * b,
+        )
+    }
+
+    pub fn to_mat3(self) -> Mat3 {
+        let q = self.normalized();
+        let (x, y, z, w) = (q.x, q.y, q.z, q.w);
+
+        Mat3::new([
+            [1.0 - 2.0 * (y * y + z * z), 2.0 * (x * y - w * z), 2.0 * (x * z + w * y)],
+            [2.0 * (x * y + w * z), 1.0 - 2.0 * (x * x + z * z), 2.0 * (y * z - w * x)],
+            [2.0 * (x * z - w * y), 2.0 * (y * z + w * x), 1.0 - 2.0 * (x * x + y * y)],
+        ])
+    }
+
+    pub fn to_mat4(self) -> Mat4 {
+        Mat4::from_mat3(&self.to_mat3())
+    }
+}
+
+impl Mul for Quat {
+    type Output = Quat;
+    /// Composición: `a * b` aplica primero `b` y después `a`.
+    fn mul(self, o: Quat) -> Quat {
+        Quat::new(
+            self.w * o.x + self.x * o.w + self.y * o.z - self.z * o.y,
+            self.w * o.y - self.x * o.z + self.y * o.w + self.z * o.x,
+            self.w * o.z + self.x * o.y - self.y * o.x + self.z * o.w,
+            self.w * o.w - self.x * o.x - self.y * o.y - self.z * o.z,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+    fn assert_vec(a: Vec3, b: Vec3, eps: f32) {
+        assert!((a - b).length() <= eps, "{:?} != {:?} (±{})", a, b, eps);
+    }
+
+    /// Mismo giro aunque el signo del cuaternión sea el opuesto.
+    fn assert_same_rotation(a: Quat, b: Quat, eps: f32) {
+        assert!(1.0 - a.dot(b).abs() <= eps, "{:?} != {:?}", a, b);
+    }
+
+    /// Verifica que `q` mire hacia `forward` con una base ortonormal y sin NaN.
+    fn assert_looks_at(q: Quat, forward: Vec3) {
+        let (right, up, look) = (
+            q.rotate(Vec3::new(1.0, 0.0, 0.0)),
+            q.rotate(Vec3::up()),
+            q.rotate(Vec3::new(0.0, 0.0, -1.0)),
+        );
+        assert!(q.x.is_finite() && q.y.is_finite() && q.z.is_finite() && q.w.is_finite());
+        assert!((q.length() - 1.0).abs() < 1e-5);
+        assert_vec(look, forward.normalized(), 1e-5);
+        assert!(right.dot(up).abs() < 1e-5 && right.dot(look).abs() < 1e-5 && up.dot(look).abs() < 1e-5);
+    }
+
+    #[test]
+    fn rotate_follows_right_hand_rule() {
+        let yaw = Quat::from_axis_angle(Vec3::up(), FRAC_PI_2);
+        assert_vec(yaw.rotate(Vec3::new(1.0, 0.0, 0.0)), Vec3::new(0.0, 0.0, -1.0), 1e-6);
+        assert_vec(yaw.rotate(Vec3::new(0.0, 0.0, -1.0)), Vec3::new(-1.0, 0.0, 0.0), 1e-6);
+
+        let pitch = Quat::from_axis_angle(Vec3::new(1.0, 0.0, 0.0), FRAC_PI_2);
+        assert_vec(pitch.rotate(Vec3::up()), Vec3::new(0.0, 0.0, 1.0), 1e-6);
+
+        // El eje no cambia y las longitudes se conservan
+        let q = Quat::from_axis_angle(Vec3::new(1.0, 2.0, -0.5), 1.3);
+        assert_vec(q.rotate(Vec3::new(1.0, 2.0, -0.5)), Vec3::new(1.0, 2.0, -0.5), 1e-5);
+        assert!((q.rotate(Vec3::new(3.0, -4.0, 12.0)).length() - 13.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn rotate_matches_matrix_and_composition_order() {
+        let a = Quat::from_axis_angle(Vec3::up(), 0.4);
+        let b = Quat::from_axis_angle(Vec3::new(1.0, 0.0, 0.0), -1.1);
+        let v = Vec3::new(0.3, -2.0, 5.0);
+
+        assert_vec(a.rotate(v), a.to_mat3() * v, 1e-5);
+        // `a * b` aplica primero `b`
+        assert_vec((a * b).rotate(v), a.rotate(b.rotate(v)), 1e-5);
+        assert_vec(a.conjugate().rotate(a.rotate(v)), v, 1e-5);
+    }
+
+    #[test]
+    fn look_rotation_points_forward() {
+        let identity = Quat::look_rotation(Vec3::new(0.0, 0.0, -1.0), Vec3::up());
+        assert_same_rotation(identity, Quat::identity(), 1e-6);
+
+        for forward in [
+            Vec3::new(1.0, 0.0, 0.0),
+            Vec3::new(0.0, 0.0, 1.0),
+            Vec3::new(-3.0, 2.0, 1.0),
+            Vec3::new(0.2, -5.0, 0.1),
+        ] {
+            let q = Quat::look_rotation(forward, Vec3::up());
+            assert_looks_at(q, forward);
+            // Sin alabeo: la derecha queda horizontal
+            assert!(q.rotate(Vec3::new(1.0, 0.0, 0.0)).y.abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn look_rotation_straight_up_and_down() {
+        // `forward` paralelo a `up`: no hay producto cruz útil y tiene que elegir otra referencia
+        assert_looks_at(Quat::look_rotation(Vec3::up(), Vec3::up()), Vec3::up());
+        assert_looks_at(Quat::look_rotation(-Vec3::up(), Vec3::up()), -Vec3::up());
+        assert_looks_at(Quat::look_rotation(Vec3::new(0.0, 7.0, 0.0), Vec3::up()), Vec3::up());
+    }
+
+    #[test]
+    fn slerp_interpolates_on_the_short_arc() {
+        let a = Quat::identity();
+        let b = Quat::from_axis_angle(Vec3::up(), FRAC_PI_2);
+
+        assert_same_rotation(a.slerp(b, 0.0), a, 1e-6);
+        assert_same_rotation(a.slerp(b, 1.0), b, 1e-6);
+        assert_same_rotation(a.slerp(b, 0.5), Quat::from_axis_angle(Vec3::up(), FRAC_PI_4), 1e-6);
+        assert!((a.slerp(b, 0.3).length() - 1.0).abs() < 1e-5);
+
+        // Con el objetivo negado (el mismo giro) también va por el camino corto
+        let neg_b = Quat::new(-b.x, -b.y, -b.z, -b.w);
+        assert_same_rotation(a.slerp(neg_b, 0.5), Quat::from_axis_angle(Vec3::up(), FRAC_PI_4), 1e-6);
+
+        // 3/4 de vuelta: el camino corto es girar un cuarto de vuelta para el otro lado
+        let c = Quat::from_axis_angle(Vec3::up(), 1.5 * PI);
+        assert_same_rotation(a.slerp(c, 0.5), Quat::from_axis_angle(Vec3::up(), -FRAC_PI_4), 1e-6);
+
+        // Casi iguales: rama lineal, sigue normalizado
+        let d = Quat::from_axis_angle(Vec3::up(), 1e-3);
+        let mid = a.slerp(d, 0.5);
+        assert!((mid.length() - 1.0).abs() < 1e-6);
+        assert_same_rotation(mid, Quat::from_axis_angle(Vec3::up(), 5e-4), 1e-6);
+    }
+
+    #[test]
+    fn slerp_between_up_and_down_views() {
+        let up = Quat::look_rotation(Vec3::up(), Vec3::up());
+        let down = Quat::look_rotation(-Vec3::up(), Vec3::up());
+        for i in 0..=10 {
+            let q = up.slerp(down, i as f32 / 10.0);
+            assert!((q.length() - 1.0).abs() < 1e-5);
+            assert!(q.rotate(Vec3::new(0.0, 0.0, -1.0)).x.is_finite());
+        }
+        assert_looks_at(up.slerp(down, 1.0), -Vec3::up());
+    }
+}
@@ -0,0 +1,58 @@
+use std::f32::consts::PI;
+
+use super::Vec3;
+
+/// Generador pseudoaleatorio con semilla (SplitMix64). Siempre da la misma
+/// secuencia para la misma semilla, en cualquier máquina.
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    pub fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    /// Número en [0, 1).
+    pub fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    /// Número en [min, max).
+    pub fn range(&mut self, min: f32, max: f32) -> f32 {
+        min + (max - min) * self.next_f32()
+    }
+
+    /// Normal con media 0 y desvío 1 (Box-Muller).
+    pub fn gaussian(&mut self) -> f32 {
+        let u1 = self.next_f32().max(1e-7);
+        let u2 = self.next_f32();
+        (-2.0 * u1.ln()).sqrt() * (2.0 * PI * u2).cos()
+    }
+
+    /// Dirección uniforme sobre la esfera unitaria.
+    pub fn unit_vector(&mut self) -> Vec3 {
+        let y = self.range(-1.0, 1.0);
+        let theta = self.range(0.0, 2.0 * PI);
+        let r = (1.0 - y * y).sqrt();
+        Vec3::new(r * theta.cos(), y, r * theta.sin())
+    }
+}
+
+/// Semilla estable a partir de un texto (FNV-1a), p. ej. el nombre de un cuerpo.
+pub fn seed_from_str(s: &str) -> u64 {
+    s.bytes().fold(0xCBF2_9CE4_8422_2325, |h, b| (h ^ b as u64).wrapping_mul(0x0100_0000_01B3))
+}
@@ -1,4 +1,4 @@
-use std::ops::{Add, Sub, Mul, Div};
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
 
 #[derive(Clone, Copy, Debug)]
 pub struct Vec2 {
@@ -14,34 +14,9 @@ impl Vec2 {
     pub fn zero() -> Self {
         Self { x: 0.0, y: 0.0 }
     }
-
-    pub fn add(self, other: Vec2) -> Self {
-        Self::new(self.x + other.x, self.y + other.y)
-    }
-
-    pub fn sub(self, other: Vec2) -> Self {
-        Self::new(self.x - other.x, self.y - other.y)
-    }
-
-    pub fn mul(self, s: f32) -> Self {
-        Self::new(self.x * s, self.y * s)
-    }
-
-    pub fn length(self) -> f32 {
-        (self.x * self.x + self.y * self.y).sqrt()
-    }
-
-    pub fn normalized(self) -> Self {
-        let len = self.length();
-        if len == 0.0 {
-            Self::zero()
-        } else {
-            self.mul(1.0 / len)
-        }
-    }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct Vec3 {
     pub x: f32,
     pub y: f32,
@@ -87,14 +62,13 @@ impl Vec3 {
         )
     }
 
-    // Opcional: método de instancia, por si querés usar forward.crossm(up)
-    pub fn crossm(self, other: Vec3) -> Vec3 {
-        Vec3::cross(self, other)
-    }
-
     pub fn lerp(self, target: Vec3, t: f32) -> Vec3 {
         self + (target - self) * t
     }
+
+    pub fn extend(self, w: f32) -> Vec4 {
+        Vec4::new(self.x, self.y, self.z, w)
+    }
 }
 
 impl Add for Vec3 {
@@ -124,3 +98,75 @@ impl Div<f32> for Vec3 {
         Vec3::new(self.x / s, self.y / s, self.z / s)
     }
 }
+
+impl Neg for Vec3 {
+    type Output = Vec3;
+    fn neg(self) -> Vec3 {
+        Vec3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl AddAssign for Vec3 {
+    fn add_assign(&mut self, other: Vec3) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Vec3 {
+    fn sub_assign(&mut self, other: Vec3) {
+        *self = *self - other;
+    }
+}
+
+/// Vector homogéneo (x, y, z, w) para las transformaciones con `Mat4`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Vec4 {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub w: f32,
+}
+
+impl Vec4 {
+    pub fn new(x: f32, y: f32, z: f32, w: f32) -> Self {
+        Self { x, y, z, w }
+    }
+
+    pub fn xyz(self) -> Vec3 {
+        Vec3::new(self.x, self.y, self.z)
+    }
+
+    pub fn dot(self, other: Vec4) -> f32 {
+        self.x * other.x + self.y * other.y + self.z * other.z + self.w * other.w
+    }
+
+    /// División de perspectiva: de coordenadas de clip a NDC.
+    pub fn perspective_divide(self) -> Vec3 {
+        Vec3::new(self.x / self.w, self.y / self.w, self.z / self.w)
+    }
+
+    pub fn lerp(self, target: Vec4, t: f32) -> Vec4 {
+        self + (target - self) * t
+    }
+}
+
+impl Add for Vec4 {
+    type Output = Vec4;
+    fn add(self, o: Vec4) -> Vec4 {
+        Vec4::new(self.x + o.x, self.y + o.y, self.z + o.z, self.w + o.w)
+    }
+}
+
+impl Sub for Vec4 {
+    type Output = Vec4;
+    fn sub(self, o: Vec4) -> Vec4 {
+        Vec4::new(self.x - o.x, self.y - o.y, self.z - o.z, self.w - o.w)
+    }
+}
+
+impl Mul<f32> for Vec4 {
+    type Output = Vec4;
+    fn mul(self, s: f32) -> Vec4 {
+        Vec4::new(self.x * s, self.y * s, self.z * s, self.w * s)
+    }
+}
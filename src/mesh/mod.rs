@@ -0,0 +1,109 @@
+pub mod obj;
+pub mod procgen;
+
+use std::fmt;
+
+use crate::math::{Vec2, Vec3};
+
+pub use obj::ObjError;
+pub use procgen::AsteroidParams;
+
+/// Error al cargar una malla desde los assets.
+#[derive(Debug)]
+pub enum MeshError {
+    NotFound(String),
+    Parse(ObjError),
+}
+
+impl fmt::Display for MeshError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MeshError::NotFound(key) => write!(f, "no se encontró el asset {}", key),
+            MeshError::Parse(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for MeshError {}
+
+impl From<ObjError> for MeshError {
+    fn from(e: ObjError) -> Self {
+        MeshError::Parse(e)
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Vertex {
+    pub position: Vec3,
+    pub normal: Vec3,
+    pub uv: Vec2,
+}
+
+#[derive(Clone, Debug)]
+pub struct Material {
+    pub name: String,
+    /// Color difuso (`Kd`) en [0, 1].
+    pub diffuse: Vec3,
+    /// Clave de la textura difusa (`map_Kd`) relativa a la raíz de assets.
+    pub texture: Option<String>,
+}
+
+impl Material {
+    pub fn default_material() -> Self {
+        Self {
+            name: "default".into(),
+            diffuse: Vec3::new(0.8, 0.8, 0.8),
+            texture: None,
+        }
+    }
+}
+
+/// Triángulos que comparten material.
+#[derive(Clone, Debug)]
+pub struct MeshGroup {
+    pub material: usize,
+    pub triangles: Vec<[usize; 3]>,
+}
+
+/// Malla indexada en coordenadas de modelo.
+#[derive(Clone, Debug)]
+pub struct Mesh {
+    pub vertices: Vec<Vertex>,
+    pub groups: Vec<MeshGroup>,
+    pub materials: Vec<Material>,
+    /// Problemas que no impidieron cargarla (p. ej. un MTL que falta).
+    pub warnings: Vec<ObjError>,
+}
+
+impl Mesh {
+    /// Normales suaves: promedio de las normales de cara (ponderadas por área)
+    /// de los triángulos que usan cada vértice.
+    pub fn compute_normals(&mut self) {
+        let all = vec![true; self.vertices.len()];
+        self.fill_normals(&all);
+    }
+
+    /// Como `compute_normals`, pero solo para los vértices marcados en `missing`;
+    /// el resto conserva la normal que ya tenía.
+    pub fn fill_normals(&mut self, missing: &[bool]) {
+        for (v, _) in self.vertices.iter_mut().zip(missing).filter(|(_, &m)| m) {
+            v.normal = Vec3::zero();
+        }
+        for group in &self.groups {
+            for &[a, b, c] in &group.triangles {
+                let pa = self.vertices[a].position;
+                let pb = self.vertices[b].position;
+                let pc = self.vertices[c].position;
+                let n = Vec3::cross(pb - pa, pc - pa);
+                for i in [a, b, c] {
+                    if missing[i] {
+                        self.vertices[i].normal += n;
+                    }
+                }
+            }
+        }
+        for (v, _) in self.vertices.iter_mut().zip(missing).filter(|(_, &m)| m) {
+            v.normal = v.normal.normalized();
+        }
+    }
+}
@@ -0,0 +1,316 @@
+//! Lector de Wavefront OBJ/MTL: posiciones, normales, UV, caras poligonales
+//! (se triangulan en abanico), `usemtl`, `mtllib` con `Kd` y `map_Kd`.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use super::{Material, Mesh, MeshGroup, Vertex};
+use crate::math::{Vec2, Vec3};
+
+#[derive(Clone, Debug)]
+pub struct ObjError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl ObjError {
+    fn new(line: usize, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ObjError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "línea {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ObjError {}
+
+/// Lee un OBJ. `load_mtl` recibe el nombre de cada `mtllib` y devuelve su
+/// contenido (o `None` si no se encuentra: se sigue con el material por defecto).
+/// Un MTL que falta o no se entiende y un material desconocido no cortan la
+/// carga: quedan en `Mesh::warnings` para que los informe quien la pidió.
+/// Los vértices sin `vn` reciben normales suaves; los demás conservan la suya.
+pub fn parse_obj(src: &str, mut load_mtl: impl FnMut(&str) -> Option<String>) -> Result<Mesh, ObjError> {
+    let mut positions: Vec<Vec3> = Vec::new();
+    let mut normals: Vec<Vec3> = Vec::new();
+    let mut uvs: Vec<Vec2> = Vec::new();
+
+    let mut materials = vec![Material::default_material()];
+    let mut warnings = Vec::new();
+    let mut vertices: Vec<Vertex> = Vec::new();
+    let mut groups: Vec<MeshGroup> = Vec::new();
+    let mut current_material = 0;
+
+    // (posición, uv, normal) -> índice de vértice, para no duplicar
+    let mut dedup: HashMap<(usize, Option<usize>, Option<usize>), usize> = HashMap::new();
+    // Por vértice: si no trajo `vn` y hay que calcularle la normal
+    let mut missing_normals: Vec<bool> = Vec::new();
+
+    for (i, raw) in src.lines().enumerate() {
+        let line_no = i + 1;
+        let line = raw.split('#').next().unwrap_or("").trim();
+        let mut parts = line.split_whitespace();
+        let Some(tag) = parts.next() else {
+            continue;
+        };
+        let rest: Vec<&str> = parts.collect();
+
+        match tag {
+            "v" => positions.push(parse_vec3(&rest, line_no)?),
+            "vn" => normals.push(parse_vec3(&rest, line_no)?.normalized()),
+            "vt" => {
+                let u = parse_f32(rest.first(), line_no)?;
+                let v = parse_f32(rest.get(1), line_no).unwrap_or(0.0);
+                // En OBJ v = 0 es abajo; en las texturas la fila 0 es arriba
+                uvs.push(Vec2::new(u, 1.0 - v));
+            }
+            "f" => {
+                if rest.len() < 3 {
+                    return Err(ObjError::new(line_no, "una cara necesita al menos 3 vértices"));
+                }
+
+                let mut face = Vec::with_capacity(rest.len());
+                for token in &rest {
+                    let key = parse_face_vertex(token, positions.len(), uvs.len(), normals.len(), line_no)?;
+                    let index = *dedup.entry(key).or_insert_with(|| {
+                        vertices.push(Vertex {
+                            position: positions[key.0],
+                            normal: key.2.map(|n| normals[n]).unwrap_or_else(Vec3::zero),
+                            uv: key.1.map(|t| uvs[t]).unwrap_or_else(Vec2::zero),
+                        });
+                        missing_normals.push(key.2.is_none());
+                        vertices.len() - 1
+                    });
+                    face.push(index);
+                }
+
+                let group = match groups.last_mut() {
+                    Some(g) if g.material == current_material => g,
+                    _ => {
+                        groups.push(MeshGroup {
+                            material: current_material,
+                            triangles: Vec::new(),
+                        });
+                        groups.last_mut().unwrap()
+                    }
+                };
+                for k in 1..face.len() - 1 {
+                    group.triangles.push([face[0], face[k], face[k + 1]]);
+                }
+            }
+            "mtllib" => {
+                for name in &rest {
+                    match load_mtl(name).map(|mtl| parse_mtl(&mtl)) {
+                        Some(Ok(parsed)) => materials.extend(parsed),
+                        Some(Err(e)) => warnings.push(ObjError::new(line_no, format!("{} inválido ({})", name, e))),
+                        None => warnings.push(ObjError::new(line_no, format!("no se encontró {}", name))),
+                    }
+                }
+            }
+            "usemtl" => {
+                let name = rest.join(" ");
+                current_material = materials.iter().position(|m| m.name == name).unwrap_or_else(|| {
+                    warnings.push(ObjError::new(line_no, format!("material desconocido {}", name)));
+                    0
+                });
+            }
+            // Objetos, grupos y suavizado no cambian la geometría que dibujamos
+            "o" | "g" | "s" => {}
+            _ => {}
+        }
+    }
+
+    if groups.is_empty() {
+        return Err(ObjError::new(src.lines().count(), "el archivo no tiene caras"));
+    }
+
+    let mut mesh = Mesh {
+        vertices,
+        groups,
+        materials,
+        warnings,
+    };
+    if missing_normals.contains(&true) {
+        mesh.fill_normals(&missing_normals);
+    }
+    Ok(mesh)
+}
+
+/// Lee un MTL. Solo se usan `newmtl`, `Kd` y `map_Kd`.
+pub fn parse_mtl(src: &str) -> Result<Vec<Material>, ObjError> {
+    let mut materials: Vec<Material> = Vec::new();
+
+    for (i, raw) in src.lines().enumerate() {
+        let line_no = i + 1;
+        let line = raw.split('#').next().unwrap_or("").trim();
+        let mut parts = line.split_whitespace();
+        let Some(tag) = parts.next() else {
+            continue;
+        };
+        let rest: Vec<&str> = parts.collect();
+
+        match tag {
+            "newmtl" => materials.push(Material {
+                name: rest.join(" "),
+                ..Material::default_material()
+            }),
+            "Kd" | "map_Kd" => {
+                let material = materials
+                    .last_mut()
+                    .ok_or_else(|| ObjError::new(line_no, format!("`{}` antes de `newmtl`", tag)))?;
+                if tag == "Kd" {
+                    material.diffuse = parse_vec3(&rest, line_no)?;
+                } else {
+                    // Las opciones (-s, -o, ...) van antes; el archivo es lo último
+                    material.texture = rest.last().map(|s| s.to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(materials)
+}
+
+fn parse_f32(token: Option<&&str>, line: usize) -> Result<f32, ObjError> {
+    let token = token.ok_or_else(|| ObjError::new(line, "faltan números"))?;
+    token
+        .parse()
+        .map_err(|_| ObjError::new(line, format!("no es un número: {}", token)))
+}
+
+fn parse_vec3(rest: &[&str], line: usize) -> Result<Vec3, ObjError> {
+    Ok(Vec3::new(
+        parse_f32(rest.first(), line)?,
+        parse_f32(rest.get(1), line)?,
+        parse_f32(rest.get(2), line)?,
+    ))
+}
+
+/// `v`, `v/vt`, `v//vn` o `v/vt/vn`, con índices desde 1 o negativos (relativos al final).
+fn parse_face_vertex(
+    token: &str,
+    n_pos: usize,
+    n_uv: usize,
+    n_norm: usize,
+    line: usize,
+) -> Result<(usize, Option<usize>, Option<usize>), ObjError> {
+    let mut fields = token.split('/');
+
+    let resolve = |field: Option<&str>, count: usize| -> Result<Option<usize>, ObjError> {
+        let Some(field) = field.filter(|f| !f.is_empty()) else {
+            return Ok(None);
+        };
+        let idx: i64 = field
+            .parse()
+            .map_err(|_| ObjError::new(line, format!("índice inválido: {}", token)))?;
+        let resolved = if idx > 0 { idx - 1 } else { count as i64 + idx };
+        if resolved < 0 || resolved >= count as i64 {
+            return Err(ObjError::new(line, format!("índice fuera de rango: {}", token)));
+        }
+        Ok(Some(resolved as usize))
+    };
+
+    let pos = resolve(fields.next(), n_pos)?
+        .ok_or_else(|| ObjError::new(line, format!("vértice sin posición: {}", token)))?;
+    let uv = resolve(fields.next(), n_uv)?;
+    let normal = resolve(fields.next(), n_norm)?;
+
+    Ok((pos, uv, normal))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SQUARE: &str = "
+v 0 0 0
+v 1 0 0
+v 1 1 0
+v 0 1 0
+vt 0 0
+vt 1 0
+vt 1 1
+vt 0 1
+vn 0 0 1
+";
+
+    fn parse(src: &str) -> Mesh {
+        parse_obj(src, |_| None).unwrap()
+    }
+
+    fn triangles(mesh: &Mesh) -> Vec<[usize; 3]> {
+        mesh.groups.iter().flat_map(|g| g.triangles.iter().copied()).collect()
+    }
+
+    #[test]
+    fn negative_indices_count_from_the_end() {
+        let absolute = parse(&format!("{}f 1 2 3\n", SQUARE));
+        let relative = parse(&format!("{}f -4 -3 -2\n", SQUARE));
+        let positions = |m: &Mesh| m.vertices.iter().map(|v| v.position).collect::<Vec<_>>();
+        assert_eq!(positions(&absolute), positions(&relative));
+    }
+
+    #[test]
+    fn reads_every_face_vertex_form() {
+        for face in ["f 1 2 3", "f 1/1 2/2 3/3", "f 1//1 2//1 3//1", "f 1/1/1 2/2/1 3/3/1"] {
+            let mesh = parse(&format!("{}{}\n", SQUARE, face));
+            assert_eq!(mesh.vertices.len(), 3, "{}", face);
+            assert_eq!(mesh.vertices[1].position, Vec3::new(1.0, 0.0, 0.0), "{}", face);
+            // La normal es la de `vn` o la calculada: en los dos casos +Z
+            assert!((mesh.vertices[1].normal - Vec3::new(0.0, 0.0, 1.0)).length() < 1e-6, "{}", face);
+        }
+        // Con UV, v se da vuelta: la fila 0 de la textura es arriba
+        let mesh = parse(&format!("{}f 1/1 2/2 3/3\n", SQUARE));
+        assert_eq!((mesh.vertices[2].uv.x, mesh.vertices[2].uv.y), (1.0, 0.0));
+    }
+
+    #[test]
+    fn polygons_become_a_fan() {
+        let mesh = parse(&format!("{}v 0.5 1.5 0\nf 1 2 3 5 4\n", SQUARE));
+        assert_eq!(triangles(&mesh), vec![[0, 1, 2], [0, 2, 3], [0, 3, 4]]);
+    }
+
+    #[test]
+    fn usemtl_starts_a_new_group() {
+        let mtl = "newmtl red\nKd 1 0 0\nnewmtl blue\nKd 0 0 1\nmap_Kd -s 1 1 1 blue.png\n";
+        let src = format!("mtllib box.mtl\n{}usemtl red\nf 1 2 3\nf 1 3 4\nusemtl blue\nf 1 2 4\n", SQUARE);
+        let mesh = parse_obj(&src, |name| (name == "box.mtl").then(|| mtl.to_string())).unwrap();
+
+        assert!(mesh.warnings.is_empty());
+        let names: Vec<&str> = mesh.groups.iter().map(|g| mesh.materials[g.material].name.as_str()).collect();
+        assert_eq!(names, ["red", "blue"]);
+        assert_eq!(mesh.groups[0].triangles.len(), 2);
+        assert_eq!(mesh.materials[mesh.groups[1].material].texture.as_deref(), Some("blue.png"));
+    }
+
+    #[test]
+    fn missing_or_invalid_mtllib_is_a_warning() {
+        let src = format!("mtllib missing.mtl broken.mtl\n{}usemtl red\nf 1 2 3\n", SQUARE);
+        let mesh = parse_obj(&src, |name| (name == "broken.mtl").then(|| "Kd 1 0 0\n".to_string())).unwrap();
+
+        // Falta uno, el otro tiene `Kd` antes de `newmtl` y `red` no existe
+        assert_eq!(mesh.warnings.len(), 3);
+        assert!(mesh.warnings.iter().all(|w| w.line == 1 || w.line == 12));
+        assert_eq!(mesh.materials[mesh.groups[0].material].name, "default");
+    }
+
+    #[test]
+    fn keeps_authored_normals_when_some_are_missing() {
+        // Una normal escrita a propósito inclinada y una cara sin `vn`
+        let src = format!("{}vn 1 0 0\nf 1//2 2//2 3//2\nf 1 3 4\n", SQUARE);
+        let mesh = parse(&src);
+
+        for v in &mesh.vertices[..3] {
+            assert_eq!(v.normal, Vec3::new(1.0, 0.0, 0.0));
+        }
+        for v in &mesh.vertices[3..] {
+            assert!((v.normal - Vec3::new(0.0, 0.0, 1.0)).length() < 1e-6);
+        }
+    }
+}
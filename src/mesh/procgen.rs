@@ -0,0 +1,334 @@
+//! Mallas procedurales: icosferas deformadas con ruido y cráteres para
+//! asteroides y lunas pequeñas. La misma semilla da siempre la misma malla.
+
+use std::collections::HashMap;
+use std::f32::consts::PI;
+
+use super::{Material, Mesh, MeshGroup, Vertex};
+use crate::math::{Noise3, Rng, Vec2, Vec3};
+
+/// Parámetros de un asteroide. El radio medio es 1; se escala con el cuerpo.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AsteroidParams {
+    pub seed: u64,
+    /// Veces que se subdivide el icosaedro (cada una multiplica las caras por 4).
+    pub subdivisions: u32,
+    /// Cuánto se aparta de la esfera: 0 = esfera, 0.5 = papa muy deforme.
+    pub lumpiness: f32,
+    pub craters: u32,
+    /// Radio angular máximo de los cráteres (radianes).
+    pub crater_size: f32,
+}
+
+impl AsteroidParams {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            subdivisions: 3,
+            lumpiness: 0.3,
+            craters: 10,
+            crater_size: 0.5,
+        }
+    }
+}
+
+/// Icosfera de radio 1 con `subdivisions` niveles, sin normales ni UV calculadas.
+pub fn icosphere(subdivisions: u32) -> (Vec<Vec3>, Vec<[usize; 3]>) {
+    let t = (1.0 + 5.0f32.sqrt()) / 2.0;
+    let mut points: Vec<Vec3> = [
+        (-1.0, t, 0.0),
+        (1.0, t, 0.0),
+        (-1.0, -t, 0.0),
+        (1.0, -t, 0.0),
+        (0.0, -1.0, t),
+        (0.0, 1.0, t),
+        (0.0, -1.0, -t),
+        (0.0, 1.0, -t),
+        (t, 0.0, -1.0),
+        (t, 0.0, 1.0),
+        (-t, 0.0, -1.0),
+        (-t, 0.0, 1.0),
+    ]
+    .iter()
+    .map(|&(x, y, z)| Vec3::new(x, y, z).normalized())
+    .collect();
+
+    let mut faces: Vec<[usize; 3]> = vec![
+        [0, 11, 5],
+        [0, 5, 1],
+        [0, 1, 7],
+        [0, 7, 10],
+        [0, 10, 11],
+        [1, 5, 9],
+        [5, 11, 4],
+        [11, 10, 2],
+        [10, 7, 6],
+        [7, 1, 8],
+        [3, 9, 4],
+        [3, 4, 2],
+        [3, 2, 6],
+        [3, 6, 8],
+        [3, 8, 9],
+        [4, 9, 5],
+        [2, 4, 11],
+        [6, 2, 10],
+        [8, 6, 7],
+        [9, 8, 1],
+    ];
+
+    for _ in 0..subdivisions {
+        // Punto medio de cada arista, compartido entre las dos caras que la usan
+        let mut midpoints: HashMap<(usize, usize), usize> = HashMap::new();
+        let mut midpoint = |a: usize, b: usize, points: &mut Vec<Vec3>| -> usize {
+            let key = (a.min(b), a.max(b));
+            *midpoints.entry(key).or_insert_with(|| {
+                points.push(((points[a] + points[b]) * 0.5).normalized());
+                points.len() - 1
+            })
+        };
+
+        let mut next = Vec::with_capacity(faces.len() * 4);
+        for &[a, b, c] in &faces {
+            let ab = midpoint(a, b, &mut points);
+            let bc = midpoint(b, c, &mut points);
+            let ca = midpoint(c, a, &mut points);
+            next.push([a, ab, ca]);
+            next.push([b, bc, ab]);
+            next.push([c, ca, bc]);
+            next.push([ab, bc, ca]);
+        }
+        faces = next;
+    }
+
+    (points, faces)
+}
+
+/// Asteroide irregular: icosfera deformada con ruido fractal de baja frecuencia
+/// (la forma de papa) y cráteres con borde levantado.
+pub fn asteroid(params: &AsteroidParams) -> Mesh {
+    let (points, faces) = icosphere(params.subdivisions);
+    let noise = Noise3::new(params.seed);
+    let mut rng = Rng::new(params.seed ^ 0xA57E_401D);
+
+    // Estiramiento en cada eje para que no todos parezcan una esfera abollada
+    let stretch = Vec3::new(
+        1.0 + params.lumpiness * rng.range(-0.3, 0.6),
+        1.0 + params.lumpiness * rng.range(-0.4, 0.2),
+        1.0 + params.lumpiness * rng.range(-0.3, 0.3),
+    );
+    let offset = rng.unit_vector() * 10.0;
+
+    let craters: Vec<(Vec3, f32, f32)> = (0..params.craters)
+        .map(|_| {
+            let center = rng.unit_vector();
+            // Muchos chicos y pocos grandes
+            let size = params.crater_size * rng.next_f32().powi(2).max(0.08);
+            let depth = size * rng.range(0.15, 0.3);
+            (center, size, depth)
+        })
+        .collect();
+
+    let vertices = points
+        .iter()
+        .map(|&dir| {
+            let lumps = noise.fbm(dir * 1.5 + offset, 4) * params.lumpiness;
+            let detail = noise.fbm(dir * 6.0 - offset, 2) * params.lumpiness * 0.15;
+            let dents: f32 = craters
+                .iter()
+                .map(|&(center, size, depth)| crater_profile(dir.dot(center).clamp(-1.0, 1.0).acos() / size) * depth)
+                .sum();
+
+            let radius = (1.0 + lumps + detail + dents).max(0.3);
+            let p = dir * radius;
+            Vertex {
+                position: Vec3::new(p.x * stretch.x, p.y * stretch.y, p.z * stretch.z),
+                normal: Vec3::zero(),
+                uv: Vec2::new(0.5 + dir.z.atan2(dir.x) / (2.0 * PI), dir.y.clamp(-1.0, 1.0).acos() / PI),
+            }
+        })
+        .collect();
+
+    let mut mesh = Mesh {
+        vertices,
+        groups: vec![MeshGroup {
+            material: 0,
+            triangles: faces,
+        }],
+        materials: vec![Material::default_material()],
+        warnings: Vec::new(),
+    };
+    // Las normales se calculan antes de cortar la costura, así los vértices
+    // duplicados quedan con la misma normal y no se ve el corte en la luz
+    mesh.compute_normals();
+    split_seam(&mut mesh);
+    mesh
+}
+
+/// Corta la costura de la proyección equirectangular: en los triángulos que
+/// cruzan u = 0/1 los vértices del lado u < 0.5 se reemplazan por copias con
+/// u + 1, para que la UV interpolada no recorra la textura entera al revés.
+/// La textura se repite en u, así que u > 1 se muestrea bien. En los polos u
+/// no está definida: cada triángulo recibe su propia copia del polo con el u
+/// promedio de los otros dos vértices.
+fn split_seam(mesh: &mut Mesh) {
+    const POLE: f32 = 1e-3;
+
+    let mut copies: HashMap<usize, usize> = HashMap::new();
+    for group in &mut mesh.groups {
+        for triangle in &mut group.triangles {
+            let pole = triangle.map(|i| {
+                let v = mesh.vertices[i].uv.y;
+                !(POLE..=1.0 - POLE).contains(&v)
+            });
+            let mut us = triangle.map(|i| mesh.vertices[i].uv.x);
+
+            let others = || (0..3).filter(|&k| !pole[k]);
+            let lo = others().map(|k| us[k]).fold(f32::MAX, f32::min);
+            let hi = others().map(|k| us[k]).fold(f32::MIN, f32::max);
+            if hi - lo > 0.5 {
+                let low: Vec<usize> = others().filter(|&k| us[k] < 0.5).collect();
+                for k in low {
+                    let index = triangle[k];
+                    triangle[k] = *copies.entry(index).or_insert_with(|| {
+                        let mut vertex = mesh.vertices[index];
+                        vertex.uv.x += 1.0;
+                        mesh.vertices.push(vertex);
+                        mesh.vertices.len() - 1
+                    });
+                    us[k] += 1.0;
+                }
+            }
+
+            if let Some(k) = (0..3).find(|&k| pole[k]) {
+                let mut vertex = mesh.vertices[triangle[k]];
+                vertex.uv.x = others().map(|j| us[j]).sum::<f32>() / others().count() as f32;
+                mesh.vertices.push(vertex);
+                triangle[k] = mesh.vertices.len() - 1;
+            }
+        }
+    }
+}
+
+/// Altura relativa de un cráter a distancia `x` del centro (1 = borde):
+/// cuenco parabólico adentro y un labio que cae suave hacia afuera.
+fn crater_profile(x: f32) -> f32 {
+    const RIM_WIDTH: f32 = 0.6;
+    const RIM_HEIGHT: f32 = 0.25;
+
+    if x < 1.0 {
+        x * x - 1.0 + RIM_HEIGHT * x.powi(6)
+    } else if x < 1.0 + RIM_WIDTH {
+        let t = 1.0 - (x - 1.0) / RIM_WIDTH;
+        RIM_HEIGHT * t * t
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Todos los números de la malla, para comparar bit a bit.
+    fn fingerprint(mesh: &Mesh) -> Vec<u32> {
+        let mut out = Vec::new();
+        for v in &mesh.vertices {
+            for x in [v.position.x, v.position.y, v.position.z, v.normal.x, v.normal.y, v.normal.z, v.uv.x, v.uv.y] {
+                out.push(x.to_bits());
+            }
+        }
+        for group in &mesh.groups {
+            out.extend(group.triangles.iter().flatten().map(|&i| i as u32));
+        }
+        out
+    }
+
+    #[test]
+    fn same_seed_gives_same_asteroid() {
+        let params = AsteroidParams::new(1234);
+        let a = asteroid(&params);
+        let b = asteroid(&params);
+        assert!(!a.vertices.is_empty());
+        assert_eq!(fingerprint(&a), fingerprint(&b));
+    }
+
+    #[test]
+    fn radii_stay_within_the_lumpiness_bound() {
+        // El ruido queda en [-1, 1]: los bultos suman hasta 1.15 * lumpiness y
+        // el estiramiento por eje está entre 1 - 0.4 y 1 + 0.6 veces lumpiness
+        for (seed, lumpiness) in [(1, 0.1), (2, 0.3), (3, 0.5)] {
+            let params = AsteroidParams {
+                lumpiness,
+                craters: 0,
+                ..AsteroidParams::new(seed)
+            };
+            let min = (1.0 - 1.15 * lumpiness) * (1.0 - 0.4 * lumpiness);
+            let max = (1.0 + 1.15 * lumpiness) * (1.0 + 0.6 * lumpiness);
+            for v in &asteroid(&params).vertices {
+                let r = v.position.length();
+                assert!(r >= min && r <= max, "lumpiness {}: radio {} fuera de [{}, {}]", lumpiness, r, min, max);
+            }
+        }
+    }
+
+    #[test]
+    fn craters_dent_the_surface() {
+        let sphere = |craters, crater_size| AsteroidParams {
+            lumpiness: 0.0,
+            craters,
+            crater_size,
+            ..AsteroidParams::new(11)
+        };
+        let radii = |params: &AsteroidParams| -> Vec<f32> {
+            asteroid(params).vertices.iter().map(|v| v.position.length()).collect()
+        };
+        let changed = |r: &[f32]| r.iter().filter(|&&r| (r - 1.0).abs() > 1e-4).count();
+        let deepest = |r: &[f32]| r.iter().fold(f32::MAX, |m, &r| m.min(r));
+
+        // Más cráteres tocan más vértices (los primeros cráteres son los mismos)
+        let few = radii(&sphere(5, 0.5));
+        let many = radii(&sphere(20, 0.5));
+        assert!(changed(&few) > 0);
+        assert!(changed(&many) > changed(&few));
+
+        // Cráteres más grandes son también más hondos
+        let small = radii(&sphere(10, 0.3));
+        let large = radii(&sphere(10, 0.6));
+        assert!(deepest(&small) < 1.0);
+        assert!(deepest(&large) < deepest(&small));
+    }
+
+    #[test]
+    fn seam_triangles_do_not_wrap_around_the_texture() {
+        let params = AsteroidParams::new(5);
+        let mesh = asteroid(&params);
+        let (points, _) = icosphere(params.subdivisions);
+        assert!(mesh.vertices.len() > points.len(), "no se duplicó ningún vértice de la costura");
+
+        for triangle in &mesh.groups[0].triangles {
+            let us = triangle.map(|i| mesh.vertices[i].uv.x);
+            let span = us.iter().fold(0.0f32, |m, &u| m.max(u)) - us.iter().fold(2.0f32, |m, &u| m.min(u));
+            assert!(span < 0.5, "el triángulo {:?} cruza la costura: u = {:?}", triangle, us);
+        }
+        // Las copias son el mismo punto con la misma normal; fuera de los polos, con u + 1
+        for v in &mesh.vertices[points.len()..] {
+            let original = mesh.vertices[..points.len()].iter().find(|o| o.position == v.position).unwrap();
+            assert_eq!(original.normal, v.normal);
+            if v.uv.y > 1e-3 && v.uv.y < 1.0 - 1e-3 {
+                assert_eq!(original.uv.x + 1.0, v.uv.x);
+            }
+        }
+    }
+
+    #[test]
+    fn zero_lumpiness_without_craters_is_a_sphere() {
+        let params = AsteroidParams {
+            lumpiness: 0.0,
+            craters: 0,
+            ..AsteroidParams::new(7)
+        };
+        for v in &asteroid(&params).vertices {
+            assert!((v.position.length() - 1.0).abs() < 1e-4);
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use super::{color, Light, Occluder, Renderer};
+use crate::camera::{Camera, NEAR};
+use crate::math::{smoothstep, Vec3};
+use crate::world::Atmosphere;
+
+/// Color del cielo con el sol bajo (lado iluminado del terminador).
+const SUNSET: Vec3 = Vec3 { x: 1.0, y: 0.45, z: 0.18 };
+/// Escala de la profundidad óptica: con densidad 1 el centro del disco apenas
+/// se vela y el borde queda bastante opaco.
+const OPTICAL_SCALE: f32 = 0.15;
+
+impl Renderer {
+    /// Dibuja la atmósfera de un cuerpo ya dibujado como una cáscara trazada por
+    /// píxel. La opacidad sale del largo del rayo dentro de la cáscara (por eso
+    /// el borde brilla más que el centro) y el color de la iluminación en el
+    /// punto medio de ese tramo: la luz pasa un poco al lado nocturno y se vuelve
+    /// anaranjada cerca del terminador. Se compone sobre la superficie y el
+    /// fondo sin escribir profundidad. `casters` apagan la atmósfera en los eclipses.
+    pub fn draw_atmosphere(
+        &mut self,
+        camera: &Camera,
+        atmosphere: &Atmosphere,
+        center: Vec3,
+        radius: f32,
+        light: &Light,
+        casters: &[Occluder],
+    ) {
+        let outer = radius + atmosphere.thickness;
+        let Some((min_x, max_x, min_y, max_y)) = self.sphere_bounds(camera, center, outer) else {
+            return;
+        };
+
+        let (right, up, forward) = camera.basis();
+        let eye = camera.position;
+        let half_h = (camera.fov_y * 0.5).tan();
+        let half_w = half_h * self.aspect();
+        let oc = eye - center;
+        let c = oc.dot(oc) - outer * outer;
+        let base = color::to_rgb(atmosphere.color);
+
+        for y in min_y..=max_y {
+            let ndc_y = 1.0 - (y as f32 + 0.5) / self.height as f32 * 2.0;
+            for x in min_x..=max_x {
+                let ndc_x = (x as f32 + 0.5) / self.width as f32 * 2.0 - 1.0;
+
+                // Mismo rayo que `draw_sphere`: el parámetro es la profundidad de vista
+                let dir = forward + right * (ndc_x * half_w) + up * (ndc_y * half_h);
+                let a = dir.dot(dir);
+                let b = oc.dot(dir);
+                let disc = b * b - a * c;
+                if disc <= 0.0 {
+                    continue;
+                }
+                let root = disc.sqrt();
+                let near = ((-b - root) / a).max(NEAR);
+                // El tramo termina donde sale de la cáscara o donde hay algo
+                // (la superficie del propio cuerpo o lo que esté delante)
+                let stored = self.fb.depth[y as usize * self.width + x as usize];
+                let far = ((-b + root) / a).min(stored);
+                if far <= near {
+                    continue;
+                }
+
+                let length = (far - near) * a.sqrt();
+                let opacity = 1.0 - (-atmosphere.density * OPTICAL_SCALE * length / atmosphere.thickness).exp();
+
+                let middle = eye + dir * ((near + far) * 0.5);
+                let normal = (middle - center).normalized();
+                let sun = normal.dot((light.position - middle).normalized());
+                let mut day = smoothstep(-0.3, 0.35, sun);
+                if day > 0.0 && !casters.is_empty() {
+                    day *= light.visibility(middle, casters);
+                }
+                if day <= 0.0 {
+                    continue;
+                }
+                let dusk = (1.0 - smoothstep(0.0, 0.4, sun)) * smoothstep(-0.25, 0.05, sun);
+                let tint = base.lerp(SUNSET, dusk * 0.8);
+
+                let alpha = (opacity * day).min(1.0);
+                let i = y as usize * self.width + x as usize;
+                let under = color::to_rgb(self.fb.pixels[i]);
+                // La luz dispersada se suma, pero también vela un poco lo de atrás
+                self.fb.pixels[i] = color::from_rgb(under * (1.0 - 0.5 * alpha) + tint * alpha);
+            }
+        }
+    }
+}
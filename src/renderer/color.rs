@@ -0,0 +1,32 @@
+//! Utilidades de color. Los colores del framebuffer son `0xAARRGGBB`; para
+//! mezclar e interpolar se pasan a `Vec3` con componentes en [0, 1].
+
+use crate::math::Vec3;
+
+pub fn to_rgb(color: u32) -> Vec3 {
+    Vec3::new(
+        ((color >> 16) & 0xFF) as f32 / 255.0,
+        ((color >> 8) & 0xFF) as f32 / 255.0,
+        (color & 0xFF) as f32 / 255.0,
+    )
+}
+
+/// Vuelve a `0xFFRRGGBB`, saturando cada componente en [0, 1].
+pub fn from_rgb(rgb: Vec3) -> u32 {
+    let c = |v: f32| (v.clamp(0.0, 1.0) * 255.0 + 0.5) as u32;
+    0xFF00_0000 | (c(rgb.x) << 16) | (c(rgb.y) << 8) | c(rgb.z)
+}
+
+/// Brillo percibido de un color lineal en [0, 1].
+pub fn luminance(rgb: Vec3) -> f32 {
+    0.2126 * rgb.x + 0.7152 * rgb.y + 0.0722 * rgb.z
+}
+
+pub fn alpha(color: u32) -> f32 {
+    ((color >> 24) & 0xFF) as f32 / 255.0
+}
+
+/// Producto componente a componente (p. ej. textura × color de vértice).
+pub fn modulate(a: Vec3, b: Vec3) -> Vec3 {
+    Vec3::new(a.x * b.x, a.y * b.y, a.z * b.z)
+}
@@ -1,4 +1,5 @@
 use super::framebuffer::FrameBuffer;
+use crate::math::{Vec2, Vec3};
 
 pub struct Draw2D<'a> {
     fb: &'a mut FrameBuffer,
@@ -9,45 +10,6 @@ impl<'a> Draw2D<'a> {
         Self { fb }
     }
 
-    pub fn filled_circle(&mut self, center: (i32, i32), radius: i32, color: u32) {
-        let (cx, cy) = center;
-        let r2 = radius * radius;
-
-        for dy in -radius..=radius {
-            for dx in -radius..=radius {
-                if dx * dx + dy * dy <= r2 {
-                    self.fb.put_pixel(cx + dx, cy + dy, color);
-                }
-            }
-        }
-    }
-
-    pub fn circle(&mut self, center: (i32, i32), radius: i32, color: u32) {
-        let (cx, cy) = center;
-        let mut x = radius;
-        let mut y = 0;
-        let mut err = 0;
-
-        while x >= y {
-            self.fb.put_pixel(cx + x, cy + y, color);
-            self.fb.put_pixel(cx + y, cy + x, color);
-            self.fb.put_pixel(cx - y, cy + x, color);
-            self.fb.put_pixel(cx - x, cy + y, color);
-            self.fb.put_pixel(cx - x, cy - y, color);
-            self.fb.put_pixel(cx - y, cy - x, color);
-            self.fb.put_pixel(cx + y, cy - x, color);
-            self.fb.put_pixel(cx + x, cy - y, color);
-
-            y += 1;
-            if err <= 0 {
-                err += 2 * y + 1;
-            } else {
-                x -= 1;
-                err -= 2 * x + 1;
-            }
-        }
-    }
-
     pub fn line(&mut self, p0: (i32, i32), p1: (i32, i32), color: u32) {
         let (mut x0, mut y0) = p0;
         let (x1, y1) = p1;
@@ -77,9 +39,272 @@ impl<'a> Draw2D<'a> {
         }
     }
 
-    pub fn triangle(&mut self, p0: (i32, i32), p1: (i32, i32), p2: (i32, i32), color: u32) {
-        self.line(p0, p1, color);
-        self.line(p1, p2, color);
-        self.line(p2, p0, color);
+    /// Rasterizador general por funciones de arista.
+    ///
+    /// Muestrea en el centro de cada píxel con regla de relleno arriba-izquierda
+    /// (dos triángulos que comparten una arista no pintan dos veces el mismo
+    /// píxel) e interpola profundidad, color y UV con corrección de perspectiva.
+    /// `shade` recibe cada fragmento y devuelve el color final, o `None` para descartarlo.
+    pub fn raster_triangle<F>(&mut self, verts: [RasterVertex; 3], options: RasterOptions, mut shade: F)
+    where
+        F: FnMut(&Fragment) -> Option<u32>,
+    {
+        let [v0, mut v1, mut v2] = verts;
+
+        // En pantalla (y hacia abajo) un triángulo antihorario visto de frente da área negativa
+        let area = edge(&v0, &v1, v2.x, v2.y);
+        if area == 0.0 || !area.is_finite() {
+            return;
+        }
+        let front_facing = area < 0.0;
+        if options.cull == Cull::Back && !front_facing {
+            return;
+        }
+
+        // Se normaliza a área positiva para que "adentro" sea siempre w >= 0
+        if area < 0.0 {
+            std::mem::swap(&mut v1, &mut v2);
+        }
+        let area = area.abs();
+
+        let min_x = v0.x.min(v1.x).min(v2.x).floor().max(0.0) as i32;
+        let max_x = v0.x.max(v1.x).max(v2.x).ceil().min(self.fb.width as f32 - 1.0) as i32;
+        let min_y = v0.y.min(v1.y).min(v2.y).floor().max(0.0) as i32;
+        let max_y = v0.y.max(v1.y).max(v2.y).ceil().min(self.fb.height as f32 - 1.0) as i32;
+        if min_x > max_x || min_y > max_y {
+            return;
+        }
+
+        let tl0 = is_top_left(&v1, &v2);
+        let tl1 = is_top_left(&v2, &v0);
+        let tl2 = is_top_left(&v0, &v1);
+
+        // 1/w se interpola linealmente en pantalla; los atributos van divididos por w
+        let inv_w = [1.0 / v0.depth, 1.0 / v1.depth, 1.0 / v2.depth];
+
+        for y in min_y..=max_y {
+            let py = y as f32 + 0.5;
+            for x in min_x..=max_x {
+                let px = x as f32 + 0.5;
+
+                let w0 = edge(&v1, &v2, px, py);
+                let w1 = edge(&v2, &v0, px, py);
+                let w2 = edge(&v0, &v1, px, py);
+
+                let inside = (w0 > 0.0 || (w0 == 0.0 && tl0))
+                    && (w1 > 0.0 || (w1 == 0.0 && tl1))
+                    && (w2 > 0.0 || (w2 == 0.0 && tl2));
+                if !inside {
+                    continue;
+                }
+
+                let l = [w0 / area, w1 / area, w2 / area];
+                let pw = [l[0] * inv_w[0], l[1] * inv_w[1], l[2] * inv_w[2]];
+                let sum = pw[0] + pw[1] + pw[2];
+                let depth = 1.0 / sum;
+
+                if options.depth_test && !self.fb.depth_test(x, y, depth) {
+                    continue;
+                }
+
+                // Baricéntricas corregidas por perspectiva
+                let b = [pw[0] / sum, pw[1] / sum, pw[2] / sum];
+
+                let frag = Fragment {
+                    color: v0.color * b[0] + v1.color * b[1] + v2.color * b[2],
+                    uv: Vec2::new(
+                        v0.uv.x * b[0] + v1.uv.x * b[1] + v2.uv.x * b[2],
+                        v0.uv.y * b[0] + v1.uv.y * b[1] + v2.uv.y * b[2],
+                    ),
+                };
+
+                if let Some(color) = shade(&frag) {
+                    if options.blend {
+                        self.fb.blend_pixel(x, y, color);
+                    } else {
+                        self.fb.put_pixel(x, y, color);
+                    }
+                    if options.depth_write {
+                        self.fb.set_depth(x, y, depth);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Vértice ya proyectado a pantalla, listo para rasterizar.
+#[derive(Clone, Copy, Debug)]
+pub struct RasterVertex {
+    /// Posición en píxeles.
+    pub x: f32,
+    pub y: f32,
+    /// Profundidad de vista (la `w` de clip); tiene que ser > 0.
+    pub depth: f32,
+    /// Color RGB en [0, 1].
+    pub color: Vec3,
+    pub uv: Vec2,
+}
+
+impl RasterVertex {
+    pub fn new(x: f32, y: f32, depth: f32, color: Vec3, uv: Vec2) -> Self {
+        Self { x, y, depth, color, uv }
+    }
+
+    /// Vértice sin atributos (profundidad 1, blanco, UV 0).
+    pub fn flat(x: f32, y: f32) -> Self {
+        Self::new(x, y, 1.0, Vec3::new(1.0, 1.0, 1.0), Vec2::zero())
+    }
+}
+
+/// Atributos interpolados de un píxel cubierto por el triángulo.
+pub struct Fragment {
+    pub color: Vec3,
+    pub uv: Vec2,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Cull {
+    None,
+    /// Descarta las caras que se ven de atrás (sentido horario en pantalla).
+    Back,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct RasterOptions {
+    pub cull: Cull,
+    pub depth_test: bool,
+    pub depth_write: bool,
+    /// Mezcla el color devuelto con el del framebuffer según su alfa.
+    pub blend: bool,
+}
+
+impl RasterOptions {
+    /// Geometría opaca: culling de caras traseras y z-buffer completo.
+    pub fn opaque() -> Self {
+        Self {
+            cull: Cull::Back,
+            depth_test: true,
+            depth_write: true,
+            blend: false,
+        }
+    }
+
+    /// Superficie semitransparente: se prueba contra el z-buffer pero no se
+    /// escribe, y el color se mezcla por alfa. Se dibuja después de lo opaco.
+    pub fn transparent() -> Self {
+        Self {
+            cull: Cull::None,
+            depth_test: true,
+            depth_write: false,
+            blend: true,
+        }
+    }
+}
+
+fn edge(a: &RasterVertex, b: &RasterVertex, px: f32, py: f32) -> f32 {
+    (b.x - a.x) * (py - a.y) - (b.y - a.y) * (px - a.x)
+}
+
+/// Con área positiva en pantalla (y hacia abajo): una arista es "izquierda" si
+/// sube y "superior" si es horizontal y va hacia +x.
+fn is_top_left(a: &RasterVertex, b: &RasterVertex) -> bool {
+    let dy = b.y - a.y;
+    let dx = b.x - a.x;
+    dy < 0.0 || (dy == 0.0 && dx > 0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIZE: usize = 10;
+
+    fn plain() -> RasterOptions {
+        RasterOptions {
+            cull: Cull::None,
+            depth_test: false,
+            depth_write: false,
+            blend: false,
+        }
+    }
+
+    /// Cuántas veces se sombreó cada píxel al dibujar `triangles`.
+    fn coverage(triangles: &[[RasterVertex; 3]]) -> Vec<u32> {
+        let mut fb = FrameBuffer::new(SIZE, SIZE);
+        let mut draw = Draw2D::new(&mut fb);
+        let mut hits = vec![0; SIZE * SIZE];
+        for &tri in triangles {
+            // El shader no sabe en qué píxel está: se marca por la UV, que es la posición
+            draw.raster_triangle(tri, plain(), |frag| {
+                hits[frag.uv.y as usize * SIZE + frag.uv.x as usize] += 1;
+                None
+            });
+        }
+        hits
+    }
+
+    /// Vértice cuya UV es su posición en pantalla (sin perspectiva da el píxel).
+    fn at(x: f32, y: f32) -> RasterVertex {
+        RasterVertex::new(x, y, 1.0, Vec3::zero(), Vec2::new(x, y))
+    }
+
+    #[test]
+    fn shared_edges_paint_each_pixel_once() {
+        // Las aristas compartidas pasan justo por centros de píxel
+        let (a, b, c, d) = (at(0.5, 0.5), at(8.5, 0.5), at(8.5, 8.5), at(0.5, 8.5));
+        let diagonal = coverage(&[[a, b, c], [a, c, d]]);
+        let (m, n) = (at(4.5, 0.5), at(4.5, 8.5));
+        let vertical = coverage(&[[a, m, n], [a, n, d], [m, b, c], [m, c, n]]);
+
+        for hits in [diagonal, vertical] {
+            assert!(hits.iter().all(|&h| h <= 1));
+            // Adentro del cuadrado no queda ningún hueco
+            for y in 1..8 {
+                for x in 1..8 {
+                    assert_eq!(hits[y * SIZE + x], 1, "({}, {})", x, y);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn uv_is_perspective_correct() {
+        // v1 está cuatro veces más lejos: su UV pesa menos de lo que ocupa en pantalla
+        let near = |x, y| RasterVertex::new(x, y, 1.0, Vec3::zero(), Vec2::zero());
+        let far = RasterVertex::new(16.0, 0.0, 4.0, Vec3::zero(), Vec2::new(1.0, 0.0));
+        let mut fb = FrameBuffer::new(16, 16);
+        let mut u = None;
+        let mut pixel = 0;
+        Draw2D::new(&mut fb).raster_triangle([near(0.0, 0.0), far, near(0.0, 16.0)], plain(), |frag| {
+            // El primer píxel es el de la fila 0; el octavo, el centro (7.5, 0.5)
+            if pixel == 7 {
+                u = Some(frag.uv.x);
+            }
+            pixel += 1;
+            None
+        });
+
+        let (l1, l2) = (7.5 / 16.0, 0.5 / 16.0);
+        let l0 = 1.0 - l1 - l2;
+        let expected = (l1 / 4.0) / (l0 + l1 / 4.0 + l2);
+        let u = u.unwrap();
+        assert!((u - expected).abs() < 1e-5, "{} != {}", u, expected);
+        // La interpolación afín daría l1
+        assert!((u - l1).abs() > 0.2);
+    }
+
+    #[test]
+    fn back_faces_are_culled() {
+        // Horario en pantalla (y hacia abajo): derecha y después abajo a la izquierda
+        let tri = [RasterVertex::flat(0.0, 0.0), RasterVertex::flat(8.0, 0.0), RasterVertex::flat(0.0, 8.0)];
+        let count = |cull| {
+            let mut fb = FrameBuffer::new(SIZE, SIZE);
+            Draw2D::new(&mut fb).raster_triangle(tri, RasterOptions { cull, ..plain() }, |_| Some(0xFFFFFFFF));
+            fb.pixels.iter().filter(|&&p| p == 0xFFFFFFFF).count()
+        };
+
+        assert_eq!(count(Cull::Back), 0);
+        assert!(count(Cull::None) > 0);
     }
 }
@@ -1,7 +1,12 @@
+use super::color;
+use crate::math::Vec3;
+
 pub struct FrameBuffer {
     pub width: usize,
     pub height: usize,
     pub pixels: Vec<u32>,
+    /// Profundidad de vista por píxel (más chico = más cerca). `INFINITY` = vacío.
+    pub depth: Vec<f32>,
 }
 
 impl FrameBuffer {
@@ -10,6 +15,7 @@ impl FrameBuffer {
             width,
             height,
             pixels: vec![0x000000; width * height],
+            depth: vec![f32::INFINITY; width * height],
         }
     }
 
@@ -17,6 +23,13 @@ impl FrameBuffer {
         for p in &mut self.pixels {
             *p = color;
         }
+        self.clear_depth();
+    }
+
+    pub fn clear_depth(&mut self) {
+        for d in &mut self.depth {
+            *d = f32::INFINITY;
+        }
     }
 
     pub fn put_pixel(&mut self, x: i32, y: i32, color: u32) {
@@ -32,4 +45,38 @@ impl FrameBuffer {
 
         self.pixels[y * self.width + x] = color;
     }
+
+    /// Mezcla `color` (ARGB) sobre el píxel según su alfa.
+    pub fn blend_pixel(&mut self, x: i32, y: i32, color: u32) {
+        if x < 0 || y < 0 || x as usize >= self.width || y as usize >= self.height {
+            return;
+        }
+        let i = y as usize * self.width + x as usize;
+        let a = color::alpha(color);
+        self.pixels[i] = color::from_rgb(color::to_rgb(self.pixels[i]).lerp(color::to_rgb(color), a));
+    }
+
+    /// Suma `rgb` al píxel (mezcla aditiva, para brillos), saturando en blanco.
+    pub fn add_pixel(&mut self, x: i32, y: i32, rgb: Vec3) {
+        if x < 0 || y < 0 || x as usize >= self.width || y as usize >= self.height {
+            return;
+        }
+        let i = y as usize * self.width + x as usize;
+        self.pixels[i] = color::from_rgb(color::to_rgb(self.pixels[i]) + rgb);
+    }
+
+    /// `true` si `depth` está delante de lo que ya hay en (x, y).
+    pub fn depth_test(&self, x: i32, y: i32, depth: f32) -> bool {
+        if x < 0 || y < 0 || x as usize >= self.width || y as usize >= self.height {
+            return false;
+        }
+        depth < self.depth[y as usize * self.width + x as usize]
+    }
+
+    pub fn set_depth(&mut self, x: i32, y: i32, depth: f32) {
+        if x < 0 || y < 0 || x as usize >= self.width || y as usize >= self.height {
+            return;
+        }
+        self.depth[y as usize * self.width + x as usize] = depth;
+    }
 }
@@ -0,0 +1,95 @@
+use std::f32::consts::PI;
+
+use super::Renderer;
+use crate::camera::Camera;
+use crate::math::{Noise3, Vec3};
+
+/// Hasta dónde llega la corona, en radios de la estrella por fuera del disco.
+const CORONA_EXTENT: f32 = 0.9;
+/// Radio del halo respecto del de la estrella.
+const HALO_SCALE: f32 = 4.0;
+
+impl Renderer {
+    /// Píxeles por unidad de mundo a profundidad 1 (para pasar radios a pantalla).
+    pub fn focal_length(&self, camera: &Camera) -> f32 {
+        self.height as f32 * 0.5 / (camera.fov_y * 0.5).tan()
+    }
+
+    /// Brillo aditivo circular centrado en `center` (mundo), con radio `radius`
+    /// en unidades de mundo y caída suave hacia el borde. Se oculta detrás de lo
+    /// que ya está en el z-buffer pero no escribe profundidad.
+    pub fn draw_glow(&mut self, camera: &Camera, center: Vec3, radius: f32, rgb: Vec3) {
+        let view_proj = self.view_projection(camera);
+        let Some((sx, sy, depth)) = self.project_with(&view_proj, center) else {
+            return;
+        };
+        let radius_px = radius * self.focal_length(camera) / depth;
+        if radius_px < 0.5 {
+            return;
+        }
+
+        let r = radius_px.ceil() as i32;
+        let (cx, cy) = (sx as i32, sy as i32);
+        for py in -r..=r {
+            for px in -r..=r {
+                let d = ((px * px + py * py) as f32).sqrt() / radius_px;
+                if d >= 1.0 {
+                    continue;
+                }
+                let (x, y) = (cx + px, cy + py);
+                if !self.fb.depth_test(x, y, depth) {
+                    continue;
+                }
+                let falloff = (1.0 - d) * (1.0 - d);
+                self.fb.add_pixel(x, y, rgb * falloff);
+            }
+        }
+    }
+
+    /// Emisión de una estrella alrededor de su disco: un halo aditivo amplio y una
+    /// corona de rayos que cambian con `time`. Las dos toman el color `rgb`
+    /// (el de `Body::color`) y quedan detrás de lo que esté delante de la estrella.
+    pub fn draw_star_glow(&mut self, camera: &Camera, center: Vec3, radius: f32, rgb: Vec3, time: f32) {
+        self.draw_glow(camera, center, radius * HALO_SCALE, rgb * 0.55);
+        self.draw_corona(camera, center, radius, rgb, time);
+    }
+
+    fn draw_corona(&mut self, camera: &Camera, center: Vec3, radius: f32, rgb: Vec3, time: f32) {
+        let view_proj = self.view_projection(camera);
+        let Some((sx, sy, depth)) = self.project_with(&view_proj, center) else {
+            return;
+        };
+        let radius_px = radius * self.focal_length(camera) / depth;
+        if radius_px < 1.0 {
+            return;
+        }
+
+        // Semilla fija: la forma de los rayos solo cambia con el tiempo
+        let noise = Noise3::new(0xC0_20_4A);
+        let outer = radius_px * (1.0 + CORONA_EXTENT);
+        let r = outer.ceil() as i32;
+        let (cx, cy) = (sx as i32, sy as i32);
+
+        for py in -r..=r {
+            for px in -r..=r {
+                let dist = ((px * px + py * py) as f32).sqrt();
+                if dist < radius_px * 0.95 || dist >= outer {
+                    continue;
+                }
+                let (x, y) = (cx + px, cy + py);
+                if !self.fb.depth_test(x, y, depth) {
+                    continue;
+                }
+
+                let theta = (py as f32).atan2(px as f32);
+                let around = Vec3::new(theta.cos() * 2.5, theta.sin() * 2.5, time * 0.25);
+                let rays = 0.5 + 0.5 * noise.fbm(around, 3);
+                let flicker = 0.5 + 0.5 * noise.sample(Vec3::new(theta * 8.0 / PI, time * 1.3, 7.0));
+
+                let t = ((dist - radius_px) / (outer - radius_px)).clamp(0.0, 1.0);
+                let falloff = (1.0 - t).powi(3);
+                self.fb.add_pixel(x, y, rgb * (falloff * (0.35 + 0.9 * rays * rays) * (0.7 + 0.3 * flicker)));
+            }
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use std::rc::Rc;
+
+use super::{Cull, RasterOptions, RasterVertex, Renderer};
+use crate::camera::{Camera, NEAR};
+use crate::math::{Mat4, Vec2, Vec3, Vec4};
+use crate::mesh::Mesh;
+use crate::texture::Texture;
+
+/// Luz mínima en las caras que no ve el sol.
+const AMBIENT: f32 = 0.15;
+
+/// Vértice en coordenadas de clip con sus atributos ya iluminados.
+#[derive(Clone, Copy)]
+pub(super) struct ClipVertex {
+    pub clip: Vec4,
+    pub color: Vec3,
+    pub uv: Vec2,
+}
+
+impl ClipVertex {
+    fn lerp(a: &ClipVertex, b: &ClipVertex, t: f32) -> ClipVertex {
+        ClipVertex {
+            clip: a.clip.lerp(b.clip, t),
+            color: a.color.lerp(b.color, t),
+            uv: Vec2::new(a.uv.x + (b.uv.x - a.uv.x) * t, a.uv.y + (b.uv.y - a.uv.y) * t),
+        }
+    }
+}
+
+impl Renderer {
+    /// Dibuja una malla con z-buffer: transforma con `model`, ilumina por vértice
+    /// (Lambert respecto de `light_pos`), recorta contra el plano cercano y rasteriza.
+    /// `textures[i]` es la textura difusa del material `i`, si tiene.
+    pub fn draw_mesh(
+        &mut self,
+        mesh: &Mesh,
+        textures: &[Option<Rc<Texture>>],
+        model: &Mat4,
+        camera: &Camera,
+        light_pos: Vec3,
+    ) {
+        let mvp = self.view_projection(camera) * *model;
+        // Las normales se transforman con la inversa transpuesta (por si hay escala no uniforme)
+        let normal_matrix = model
+            .to_mat3()
+            .inverse()
+            .map(|m| m.transpose())
+            .unwrap_or_else(|| model.to_mat3());
+
+        for group in &mesh.groups {
+            let material = &mesh.materials[group.material];
+            let texture = textures.get(group.material).and_then(|t| t.as_deref());
+
+            for tri in &group.triangles {
+                let verts = tri.map(|i| {
+                    let v = &mesh.vertices[i];
+                    let world = model.transform_point(v.position);
+                    let normal = (normal_matrix * v.normal).normalized();
+                    let to_light = (light_pos - world).normalized();
+                    let intensity = AMBIENT + (1.0 - AMBIENT) * normal.dot(to_light).max(0.0);
+
+                    ClipVertex {
+                        clip: mvp * v.position.extend(1.0),
+                        color: material.diffuse * intensity,
+                        uv: v.uv,
+                    }
+                });
+
+                let polygon = clip_near(&verts);
+                if polygon.len() < 3 {
+                    continue;
+                }
+
+                let screen: Vec<RasterVertex> = polygon.iter().map(|v| self.to_raster(v)).collect();
+                for k in 1..screen.len() - 1 {
+                    self.draw_shaded_triangle(
+                        [screen[0], screen[k], screen[k + 1]],
+                        RasterOptions {
+                            cull: Cull::Back,
+                            ..RasterOptions::opaque()
+                        },
+                        texture,
+                    );
+                }
+            }
+        }
+    }
+
+    pub(super) fn to_raster(&self, v: &ClipVertex) -> RasterVertex {
+        let ndc = v.clip.perspective_divide();
+        RasterVertex::new(
+            (ndc.x + 1.0) * 0.5 * self.width as f32,
+            (1.0 - ndc.y) * 0.5 * self.height as f32,
+            v.clip.w,
+            v.color,
+            v.uv,
+        )
+    }
+}
+
+/// Sutherland-Hodgman contra el plano w = NEAR: deja solo la parte del
+/// triángulo que está delante de la cámara (hasta 4 vértices).
+pub(super) fn clip_near(tri: &[ClipVertex; 3]) -> Vec<ClipVertex> {
+    let mut out = Vec::with_capacity(4);
+
+    for i in 0..3 {
+        let a = &tri[i];
+        let b = &tri[(i + 1) % 3];
+        let da = a.clip.w - NEAR;
+        let db = b.clip.w - NEAR;
+
+        if da >= 0.0 {
+            out.push(*a);
+        }
+        if (da >= 0.0) != (db >= 0.0) {
+            let t = da / (da - db);
+            out.push(ClipVertex::lerp(a, b, t));
+        }
+    }
+
+    out
+}
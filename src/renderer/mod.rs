@@ -1,11 +1,23 @@
 pub mod framebuffer;
 pub mod draw2d;
+pub mod color;
+pub mod atmosphere;
+pub mod glow;
+pub mod mesh;
+pub mod planet;
+pub mod postfx;
+pub mod shader;
+pub mod shadow;
+pub mod sphere;
 
 use framebuffer::FrameBuffer;
 use draw2d::Draw2D;
+pub use draw2d::{Cull, Fragment, RasterOptions, RasterVertex};
+pub use planet::BodyTextures;
+pub use shadow::{Light, Occluder};
 
-use crate::camera::Camera;
-use crate::math::{Vec2, Vec3};
+use crate::camera::{Camera, NEAR};
+use crate::math::{Mat4, Vec3};
 use crate::texture::Texture;
 
 pub struct Renderer {
@@ -35,14 +47,9 @@ impl Renderer {
         self.fb.put_pixel(x, y, color);
     }
 
-    pub fn draw_filled_circle(&mut self, center: (i32, i32), radius: i32, color: u32) {
-        let mut d = Draw2D::new(&mut self.fb);
-        d.filled_circle(center, radius, color);
-    }
-
-    pub fn draw_circle(&mut self, center: (i32, i32), radius: i32, color: u32) {
-        let mut d = Draw2D::new(&mut self.fb);
-        d.circle(center, radius, color);
+    /// Suma luz al píxel (mezcla aditiva), para estrellas y brillos.
+    pub fn add_pixel(&mut self, x: i32, y: i32, rgb: Vec3) {
+        self.fb.add_pixel(x, y, rgb);
     }
 
     pub fn draw_line(&mut self, p0: (i32, i32), p1: (i32, i32), color: u32) {
@@ -50,160 +57,166 @@ impl Renderer {
         d.line(p0, p1, color);
     }
 
-    pub fn draw_triangle(&mut self, p0: (i32, i32), p1: (i32, i32), p2: (i32, i32), color: u32) {
+    pub fn aspect(&self) -> f32 {
+        self.width as f32 / self.height as f32
+    }
+
+    /// Matriz mundo -> clip de la cámara para este framebuffer.
+    pub fn view_projection(&self, camera: &Camera) -> Mat4 {
+        camera.view_projection(self.aspect())
+    }
+
+    /// Rasteriza un triángulo ya proyectado llamando a `shade` por cada fragmento.
+    pub fn raster_triangle<F>(&mut self, verts: [RasterVertex; 3], options: RasterOptions, shade: F)
+    where
+        F: FnMut(&Fragment) -> Option<u32>,
+    {
         let mut d = Draw2D::new(&mut self.fb);
-        d.triangle(p0, p1, p2, color);
+        d.raster_triangle(verts, options, shade);
+    }
+
+    /// Triángulo con color por vértice, opcionalmente multiplicado por una textura
+    /// muestreada con las UV interpoladas. Los texels casi transparentes se descartan.
+    pub fn draw_shaded_triangle(&mut self, verts: [RasterVertex; 3], options: RasterOptions, texture: Option<&Texture>) {
+        self.raster_triangle(verts, options, |frag| match texture {
+            None => Some(color::from_rgb(frag.color)),
+            Some(tex) => {
+                let texel = tex.sample(frag.uv.x, frag.uv.y);
+                if color::alpha(texel) < 0.04 {
+                    return None;
+                }
+                Some(color::from_rgb(color::modulate(color::to_rgb(texel), frag.color)))
+            }
+        });
     }
 
     /// Proyección 3D: mundo -> pantalla
     pub fn project_point(&self, world: Vec3, camera: &Camera) -> Option<(i32, i32)> {
-        let rel = world - camera.position;
-
-        let forward = camera.forward();
-        // ⬇⬇⬇ AQUÍ EL CAMBIO IMPORTANTE ⬇⬇⬇
-        let right = Vec3::cross(forward, Vec3::up()).normalized();
-        let up    = Vec3::cross(right, forward).normalized();
+        self.project_with(&self.view_projection(camera), world)
+            .map(|(sx, sy, _)| (sx as i32, sy as i32))
+    }
 
-        let x_cam = rel.dot(right);
-        let y_cam = rel.dot(up);
-        let z_cam = -rel.dot(forward);
+    /// Proyecta con una matriz vista-proyección ya calculada (para no rehacerla
+    /// por cada punto). Devuelve (x, y) en píxeles y la profundidad de vista.
+    pub fn project_with(&self, view_proj: &Mat4, world: Vec3) -> Option<(f32, f32, f32)> {
+        let clip = *view_proj * world.extend(1.0);
 
         // Si está demasiado cerca o detrás, no se dibuja
-        if z_cam <= 0.1 {
+        if clip.w <= NEAR {
             return None;
         }
 
-        let f = (self.height as f32 / 2.0) / (camera.fov_y * 0.5).tan();
+        let ndc = clip.perspective_divide();
+        let sx = (ndc.x + 1.0) * 0.5 * self.width as f32;
+        let sy = (1.0 - ndc.y) * 0.5 * self.height as f32;
 
-        let sx = self.width as f32 / 2.0 + x_cam * f / z_cam;
-        let sy = self.height as f32 / 2.0 - y_cam * f / z_cam;
-
-        Some((sx as i32, sy as i32))
-    }
-
-    pub fn world_to_screen_2d(&self, world: Vec2, camera_pos: Vec2, zoom: f32) -> (i32, i32) {
-        let sx = (world.x - camera_pos.x) * zoom + (self.width as f32 / 2.0);
-        let sy = (world.y - camera_pos.y) * zoom + (self.height as f32 / 2.0);
-
-        (sx as i32, sy as i32)
+        Some((sx, sy, clip.w))
     }
 
-    /// Dibuja un planeta como DISCO 2D texturizado.
-    pub fn draw_textured_sphere(
-        &mut self,
-        tex: &Texture,
-        center: (i32, i32),
-        radius: i32,
-        rotation: f32,
-    ) {
-        if radius <= 0 {
-            return;
-        }
-
-        let (cx, cy) = center;
-        let r = radius as f32;
-        let r2 = r * r;
-
-        let cos_a = rotation.cos();
-        let sin_a = rotation.sin();
-
-        for py in -radius..=radius {
-            let sy = cy + py;
-            if sy < 0 || sy >= self.height as i32 {
-                continue;
-            }
-
-            for px in -radius..=radius {
-                let sx = cx + px;
-                if sx < 0 || sx >= self.width as i32 {
-                    continue;
+    /// Punto cuadrado de `size` píxeles con prueba y escritura de profundidad.
+    pub fn draw_point(&mut self, center: (f32, f32), depth: f32, size: i32, color: u32) {
+        let x0 = (center.0 - size as f32 * 0.5).round() as i32;
+        let y0 = (center.1 - size as f32 * 0.5).round() as i32;
+        for y in y0..y0 + size {
+            for x in x0..x0 + size {
+                if self.fb.depth_test(x, y, depth) {
+                    self.fb.set_depth(x, y, depth);
+                    self.fb.put_pixel(x, y, color);
                 }
-
-                let x = px as f32;
-                let y = py as f32;
-                let dist2 = x * x + y * y;
-                if dist2 > r2 {
-                    continue; // fuera del círculo
-                }
-
-                // Coord. normalizadas [-1, 1]
-                let nx = x / r;
-                let ny = y / r;
-
-                // Rotar en 2D para que la textura "gire" sobre el planeta
-                let rx = nx * cos_a - ny * sin_a;
-                let ry = nx * sin_a + ny * cos_a;
-
-                // Mapear a [0,1]
-                let u = (rx + 1.0) * 0.5;        // 0..1
-                let v = 1.0 - (ry + 1.0) * 0.5;  // 0..1 (invertimos Y)
-
-                if u < 0.0 || u > 1.0 || v < 0.0 || v > 1.0 {
-                    continue;
-                }
-
-                let tx = (u * (tex.width as f32 - 1.0)) as usize;
-                let ty = (v * (tex.height as f32 - 1.0)) as usize;
-
-                if tx >= tex.width || ty >= tex.height {
-                    continue;
-                }
-
-                let color = tex.pixels[ty * tex.width + tx];
-                let a = (color >> 24) & 0xFF;
-                if a < 10 {
-                    continue;
-                }
-
-                self.put_pixel(sx, sy, color);
             }
         }
     }
 
-    /// Blit cuadrado genérico (sprites 2D, HUD, etc.).
-    pub fn blit_sprite(&mut self, tex: &Texture, center: (i32, i32), size: i32) {
-        if size <= 0 {
-            return;
-        }
+}
 
-        let half = size / 2;
-        let start_x = center.0 - half;
-        let start_y = center.1 - half;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::Quat;
 
-        for y in 0..size {
-            let sy = start_y + y;
-            if sy < 0 || sy >= self.height as i32 {
-                continue;
-            }
+    const W: usize = 800;
+    const H: usize = 600;
 
-            let v = y as f32 / size as f32;
-            let ty = (v * tex.height as f32) as usize;
-            if ty >= tex.height {
-                continue;
-            }
+    /// Cámara en el origen mirando hacia -Z (lo que hace la identidad).
+    fn camera_at_origin() -> Camera {
+        let mut camera = Camera::new();
+        camera.position = Vec3::zero();
+        camera.orientation = Quat::identity();
+        camera
+    }
 
-            for x in 0..size {
-                let sx = start_x + x;
-                if sx < 0 || sx >= self.width as i32 {
-                    continue;
-                }
+    fn project(camera: &Camera, p: Vec3) -> Option<(f32, f32, f32)> {
+        let renderer = Renderer::new(W, H);
+        renderer.project_with(&renderer.view_projection(camera), p)
+    }
 
-                let u = x as f32 / size as f32;
-                let tx = (u * tex.width as f32) as usize;
-                if tx >= tex.width {
-                    continue;
-                }
+    #[test]
+    fn point_in_front_projects_to_center() {
+        let (x, y, depth) = project(&camera_at_origin(), Vec3::new(0.0, 0.0, -10.0)).expect("está delante");
+        assert!((x - W as f32 / 2.0).abs() < 1e-3 && (y - H as f32 / 2.0).abs() < 1e-3);
+        assert!((depth - 10.0).abs() < 1e-4);
+    }
 
-                let idx = ty * tex.width + tx;
-                let color = tex.pixels[idx];
-                let a = (color >> 24) & 0xFF;
+    #[test]
+    fn point_behind_is_rejected() {
+        // El `project_point` original aceptaba justamente estos (profundidad de vista negativa)
+        let camera = camera_at_origin();
+        assert!(project(&camera, Vec3::new(0.0, 0.0, 10.0)).is_none());
+        assert!(project(&camera, Vec3::new(3.0, -2.0, 0.5)).is_none());
+        assert!(Renderer::new(W, H).project_point(Vec3::new(0.0, 0.0, 10.0), &camera).is_none());
+
+        // Más cerca que el plano cercano tampoco
+        assert!(project(&camera, Vec3::new(0.0, 0.0, -NEAR * 0.5)).is_none());
+        assert!(project(&camera, Vec3::new(0.0, 0.0, -NEAR * 2.0)).is_some());
+    }
 
-                if a < 10 {
-                    continue;
-                }
+    #[test]
+    fn screen_axes_and_fov_edges() {
+        let camera = camera_at_origin();
+        let d = 10.0;
+        let half_h = (camera.fov_y * 0.5).tan() * d;
+        let aspect = W as f32 / H as f32;
+
+        // +X de cámara a la derecha, +Y arriba (la y de pantalla crece hacia abajo)
+        let (rx, ry, _) = project(&camera, Vec3::new(1.0, 0.0, -d)).unwrap();
+        let (ux, uy, _) = project(&camera, Vec3::new(0.0, 1.0, -d)).unwrap();
+        assert!(rx > W as f32 / 2.0 && (ry - H as f32 / 2.0).abs() < 1e-3);
+        assert!(uy < H as f32 / 2.0 && (ux - W as f32 / 2.0).abs() < 1e-3);
+
+        // Los bordes del campo visual caen en los bordes de la pantalla
+        let (_, top, _) = project(&camera, Vec3::new(0.0, half_h, -d)).unwrap();
+        let (right, _, _) = project(&camera, Vec3::new(half_h * aspect, 0.0, -d)).unwrap();
+        assert!(top.abs() < 1e-2);
+        assert!((right - W as f32).abs() < 1e-2);
+    }
 
-                self.put_pixel(sx, sy, color);
-            }
-        }
+    #[test]
+    fn follows_camera_position_and_orientation() {
+        let mut camera = camera_at_origin();
+        camera.position = Vec3::new(5.0, 1.0, -3.0);
+        camera.orientation = Quat::look_rotation(Vec3::new(1.0, 0.0, 0.0), Vec3::up());
+
+        let ahead = camera.position + Vec3::new(20.0, 0.0, 0.0);
+        let behind = camera.position - Vec3::new(20.0, 0.0, 0.0);
+        let (x, y, depth) = project(&camera, ahead).expect("está delante");
+        assert!((x - W as f32 / 2.0).abs() < 1e-2 && (y - H as f32 / 2.0).abs() < 1e-2);
+        assert!((depth - 20.0).abs() < 1e-3);
+        assert!(project(&camera, behind).is_none());
+
+        // Mirando recto hacia arriba la matriz sigue siendo válida
+        camera.orientation = Quat::look_rotation(Vec3::up(), Vec3::up());
+        let (x, y, _) = project(&camera, camera.position + Vec3::new(0.0, 8.0, 0.0)).expect("está delante");
+        assert!((x - W as f32 / 2.0).abs() < 1e-2 && (y - H as f32 / 2.0).abs() < 1e-2);
+        assert!(project(&camera, camera.position - Vec3::new(0.0, 8.0, 0.0)).is_none());
+    }
+
+    #[test]
+    fn project_point_matches_project_with() {
+        let mut camera = Camera::new();
+        camera.position = Vec3::new(0.0, 30.0, 80.0);
+        let renderer = Renderer::new(W, H);
+        let p = Vec3::new(12.0, 4.0, 10.0);
+        let (x, y, _) = project(&camera, p).unwrap();
+        assert_eq!(renderer.project_point(p, &camera), Some((x as i32, y as i32)));
     }
 }
@@ -0,0 +1,350 @@
+use std::f32::consts::PI;
+
+use super::mesh::{clip_near, ClipVertex};
+use super::shader::{ShaderInput, SurfaceShader};
+use super::sphere::equirect_uv;
+use super::{color, Fragment, Light, RasterOptions, RasterVertex, Renderer};
+use crate::camera::Camera;
+use crate::math::{Quat, Vec2, Vec3};
+use crate::texture::Texture;
+use crate::world::{Body, BodyKind, CloudLayer, Ring};
+
+/// Luz mínima del lado nocturno.
+const AMBIENT: f32 = 0.08;
+/// Fracción de luz que queda dentro de la sombra del planeta sobre su anillo.
+const RING_SHADOW: f32 = 0.12;
+/// Intensidad máxima del reflejo especular.
+const SPECULAR: f32 = 0.6;
+/// Cuánta luz quita una nube completamente densa a la superficie de abajo.
+const CLOUD_SHADOW: f32 = 0.7;
+/// Separación (en texels) de las muestras que suavizan la sombra de las nubes.
+const CLOUD_SHADOW_BLUR: f32 = 3.0;
+/// Segmentos del polígono con el que se rasteriza el anillo.
+const RING_SEGMENTS: usize = 128;
+
+/// Texturas con las que se pinta un cuerpo. Solo `surface` es obligatoria.
+pub struct BodyTextures<'a> {
+    /// Color de la superficie (equirectangular).
+    pub surface: &'a Texture,
+    /// Franja radial del anillo, si el cuerpo tiene uno.
+    pub ring: Option<&'a Texture>,
+    /// Relieve: mapa de normales en espacio tangente o, si no hay, de alturas.
+    pub normal: Option<&'a Texture>,
+    pub height: Option<&'a Texture>,
+    /// Máscara de brillo especular.
+    pub specular: Option<&'a Texture>,
+    /// Luces del lado nocturno.
+    pub emissive: Option<&'a Texture>,
+    /// Densidad de la capa de nubes.
+    pub clouds: Option<&'a Texture>,
+    /// Shader procedural; si está, reemplaza a `surface`.
+    pub shader: Option<&'a dyn SurfaceShader>,
+}
+
+impl Renderer {
+    /// Dibuja un cuerpo como esfera 3D con z-buffer. El color sale de la textura
+    /// o de un shader procedural (animado con `time`). Las estrellas brillan solas;
+    /// el resto se ilumina con `light`, con sombra de los cuerpos que la tapan
+    /// (eclipses), relieve de los mapas de normales o alturas y reflejo
+    /// especular donde marca la máscara. Del lado oscuro se encienden las luces
+    /// del mapa emisivo. Encima van las nubes (con su sombra sobre el suelo) y
+    /// la atmósfera, si tiene.
+    /// Si tiene anillo, se dibuja después con transparencia y cada uno proyecta
+    /// su sombra sobre el otro.
+    pub fn draw_body(
+        &mut self,
+        camera: &Camera,
+        body: &Body,
+        center: Vec3,
+        textures: &BodyTextures,
+        light: &Light,
+        time: f32,
+    ) {
+        let light_pos = light.position;
+        let eye = camera.position;
+        let spin = body.orientation();
+        let emissive = matches!(body.kind, BodyKind::Star);
+        let ring = body.ring.as_ref().zip(textures.ring);
+        let clouds = body.clouds.as_ref().zip(textures.clouds);
+        // Las nubes giran por su cuenta alrededor del mismo eje que el cuerpo
+        let cloud_spin = clouds.map_or(Quat::identity(), |(layer, _)| body.pole() * Quat::from_axis_angle(Vec3::up(), layer.angle));
+        let shell = body.radius + clouds.map_or(0.0, |(c, _)| c.altitude);
+        let casters = if emissive { Vec::new() } else { light.casters_for(center, shell) };
+        let (bump_strength, shininess) = body
+            .surface
+            .as_ref()
+            .map_or((1.0, 40.0), |s| (s.bump_strength, s.shininess));
+
+        self.draw_sphere(camera, center, body.radius, spin, RasterOptions::opaque(), |frag| {
+            let to_light = (light_pos - frag.position).normalized();
+            let (texel, glow) = match textures.shader {
+                Some(shader) => {
+                    let sample = shader.shade(&ShaderInput {
+                        normal: frag.normal,
+                        local: frag.local,
+                        light_dir: to_light,
+                        view_dir: (eye - frag.position).normalized(),
+                        time,
+                    });
+                    (sample.albedo, sample.emission)
+                }
+                None => (color::to_rgb(textures.surface.sample(frag.uv.x, frag.uv.y)), Vec3::zero()),
+            };
+            if emissive {
+                return Some(color::from_rgb(texel + glow));
+            }
+
+            let facing = frag.normal.dot(to_light);
+
+            // Relieve: la normal se inclina en la base tangente (este, norte, afuera)
+            let normal = match (textures.normal, textures.height) {
+                (Some(map), _) => spin.rotate(normal_from_map(map, frag.local, frag.uv, bump_strength)),
+                (None, Some(map)) => spin.rotate(normal_from_height(map, frag.local, frag.uv, bump_strength)),
+                (None, None) => frag.normal,
+            };
+
+            // La normal inclinada no puede iluminar el lado nocturno: se apaga
+            // suave en el terminador geométrico
+            let mut intensity = normal.dot(to_light).max(0.0) * (facing * 8.0).clamp(0.0, 1.0);
+
+            // Eclipses: cuánto del disco de la estrella se ve desde este punto
+            if intensity > 0.0 && !casters.is_empty() {
+                intensity *= light.visibility(frag.position, &casters);
+            }
+
+            // Sombra del anillo: el rayo hacia la luz atraviesa la franja
+            if let Some((ring, strip)) = ring {
+                if intensity > 0.0 {
+                    if let Some(r) = ring.intersect(center, frag.position, to_light) {
+                        intensity *= 1.0 - color::alpha(sample_strip(strip, r));
+                    }
+                }
+            }
+
+            // Sombra de las nubes: donde el rayo hacia la luz cruza la cáscara
+            if let Some((layer, density)) = clouds {
+                if intensity > 0.0 {
+                    let cover = cloud_shadow(layer, density, cloud_spin, center, body.radius, frag.position, to_light);
+                    intensity *= 1.0 - CLOUD_SHADOW * layer.opacity * cover;
+                }
+            }
+
+            let mut rgb = texel * (AMBIENT + (1.0 - AMBIENT) * intensity) + glow;
+
+            // Luces nocturnas: se apagan en cuanto llega algo de sol
+            if let Some(map) = textures.emissive {
+                let night = (1.0 - intensity * 4.0).clamp(0.0, 1.0);
+                if night > 0.0 {
+                    rgb += color::to_rgb(map.sample(frag.uv.x, frag.uv.y)) * night;
+                }
+            }
+
+            // Blinn-Phong donde la máscara lo permite
+            if let Some(mask) = textures.specular {
+                let gloss = color::luminance(color::to_rgb(mask.sample(frag.uv.x, frag.uv.y)));
+                if gloss > 0.0 && intensity > 0.0 {
+                    let half = (to_light + (eye - frag.position).normalized()).normalized();
+                    let highlight = normal.dot(half).max(0.0).powf(shininess);
+                    rgb += Vec3::new(1.0, 1.0, 1.0) * (SPECULAR * gloss * highlight * intensity.min(1.0).sqrt());
+                }
+            }
+
+            Some(color::from_rgb(rgb))
+        });
+
+        if let Some(clouds) = clouds.filter(|_| !emissive) {
+            self.draw_clouds(camera, clouds, cloud_spin, center, shell, light);
+        }
+
+        if let Some(atmosphere) = body.atmosphere.as_ref().filter(|_| !emissive) {
+            self.draw_atmosphere(camera, atmosphere, center, body.radius, light, &casters);
+        }
+
+        if let Some((ring, strip)) = ring {
+            self.draw_ring(camera, ring, center, body.radius, strip, light_pos);
+        }
+    }
+
+    /// Cáscara de nubes semitransparente con su propio giro. Se ilumina como la
+    /// superficie y no escribe profundidad, así la atmósfera se compone encima.
+    fn draw_clouds(
+        &mut self,
+        camera: &Camera,
+        (layer, density): (&CloudLayer, &Texture),
+        spin: Quat,
+        center: Vec3,
+        radius: f32,
+        light: &Light,
+    ) {
+        let casters = light.casters_for(center, radius);
+        self.draw_sphere(camera, center, radius, spin, RasterOptions::transparent(), |frag| {
+            let alpha = layer.opacity * cloud_density(density, frag.uv.x, frag.uv.y);
+            if alpha < 0.01 {
+                return None;
+            }
+
+            let to_light = (light.position - frag.position).normalized();
+            let mut intensity = frag.normal.dot(to_light).max(0.0);
+            if intensity > 0.0 && !casters.is_empty() {
+                intensity *= light.visibility(frag.position, &casters);
+            }
+
+            let rgb = Vec3::new(1.0, 1.0, 1.0) * (AMBIENT + (1.0 - AMBIENT) * intensity);
+            Some((color::from_rgb(rgb) & 0x00FF_FFFF) | (((alpha.min(1.0) * 255.0) as u32) << 24))
+        });
+    }
+
+    /// Rasteriza el anillo como un polígono plano. Las UV llevan la posición en
+    /// el plano del anillo, así cada fragmento sabe su radio exacto y los bordes
+    /// quedan circulares aunque la geometría sea poligonal.
+    fn draw_ring(&mut self, camera: &Camera, ring: &Ring, center: Vec3, planet_radius: f32, strip: &Texture, light_pos: Vec3) {
+        let view_proj = self.view_projection(camera);
+        let orientation = ring.orientation();
+        let axis_u = orientation.rotate(Vec3::new(1.0, 0.0, 0.0));
+        let axis_v = orientation.rotate(Vec3::new(0.0, 0.0, 1.0));
+
+        // El polígono externo circunscribe al círculo; el interno queda adentro del hueco
+        let outer = ring.outer_radius / (PI / RING_SEGMENTS as f32).cos();
+        let inner = ring.inner_radius;
+
+        let vertex = |radius: f32, angle: f32| {
+            let (lu, lv) = (radius * angle.cos(), radius * angle.sin());
+            ClipVertex {
+                clip: view_proj * (center + axis_u * lu + axis_v * lv).extend(1.0),
+                color: Vec3::zero(),
+                uv: Vec2::new(lu, lv),
+            }
+        };
+
+        let shade = |frag: &Fragment| {
+            let (lu, lv) = (frag.uv.x, frag.uv.y);
+            let r = ring.radial((lu * lu + lv * lv).sqrt())?;
+            let texel = sample_strip(strip, r);
+            let alpha = color::alpha(texel);
+            if alpha < 0.01 {
+                return None;
+            }
+
+            // Sombra del planeta sobre el anillo
+            let position = center + axis_u * lu + axis_v * lv;
+            let to_light = (light_pos - position).normalized();
+            let light = if ray_hits_sphere(position, to_light, center, planet_radius) {
+                RING_SHADOW
+            } else {
+                1.0
+            };
+
+            let rgb = color::to_rgb(texel) * light;
+            Some((color::from_rgb(rgb) & 0x00FF_FFFF) | (texel & 0xFF00_0000))
+        };
+
+        for i in 0..RING_SEGMENTS {
+            let a0 = i as f32 / RING_SEGMENTS as f32 * 2.0 * PI;
+            let a1 = (i + 1) as f32 / RING_SEGMENTS as f32 * 2.0 * PI;
+            let quad = [vertex(inner, a0), vertex(outer, a0), vertex(outer, a1), vertex(inner, a1)];
+
+            for tri in [[quad[0], quad[1], quad[2]], [quad[0], quad[2], quad[3]]] {
+                let polygon = clip_near(&tri);
+                if polygon.len() < 3 {
+                    continue;
+                }
+                let screen: Vec<RasterVertex> = polygon.iter().map(|v| self.to_raster(v)).collect();
+                for k in 1..screen.len() - 1 {
+                    self.raster_triangle([screen[0], screen[k], screen[k + 1]], RasterOptions::transparent(), shade);
+                }
+            }
+        }
+    }
+}
+
+/// Texel de la franja radial en `r` (0 = borde interno, 1 = externo).
+fn sample_strip(strip: &Texture, r: f32) -> u32 {
+    // `sample` repite en u, así que el borde externo no puede llegar a 1
+    strip.sample(r.min(0.999), 0.5)
+}
+
+/// `true` si el rayo `origin + dir·t` (t > 0, `dir` normalizado) toca la esfera.
+fn ray_hits_sphere(origin: Vec3, dir: Vec3, center: Vec3, radius: f32) -> bool {
+    let oc = origin - center;
+    let b = oc.dot(dir);
+    let c = oc.dot(oc) - radius * radius;
+    // Desde adentro siempre toca; desde afuera, solo si la esfera está adelante
+    c <= 0.0 || (b < 0.0 && b * b - c >= 0.0)
+}
+
+/// Base tangente de la esfera en `local`: (este, norte), en los ejes del cuerpo.
+fn tangent_basis(local: Vec3) -> (Vec3, Vec3) {
+    let east = Vec3::cross(Vec3::up(), local);
+    // En los polos el este no está definido: cualquier dirección sirve
+    let east = if east.length() < 1e-4 { Vec3::new(0.0, 0.0, -1.0) } else { east.normalized() };
+    (east, Vec3::cross(local, east))
+}
+
+/// Normal (en los ejes del cuerpo) leída de un mapa de normales en espacio tangente.
+fn normal_from_map(map: &Texture, local: Vec3, uv: Vec2, strength: f32) -> Vec3 {
+    let texel = color::to_rgb(map.sample(uv.x, uv.y));
+    let (east, north) = tangent_basis(local);
+    let (x, y, z) = (texel.x * 2.0 - 1.0, texel.y * 2.0 - 1.0, texel.z * 2.0 - 1.0);
+    (east * (x * strength) + north * (y * strength) + local * z.max(0.0)).normalized()
+}
+
+/// Normal (en los ejes del cuerpo) a partir de la pendiente de un mapa de
+/// alturas. La pendiente se mide por radián, así el relieve no depende de la
+/// resolución del mapa.
+fn normal_from_height(map: &Texture, local: Vec3, uv: Vec2, strength: f32) -> Vec3 {
+    let (du, dv) = (1.0 / map.width as f32, 1.0 / map.height as f32);
+    let height = |u: f32, v: f32| height_at(map, u, v);
+
+    // v crece hacia el sur y una vuelta en u son 2π radianes de longitud,
+    // que cerca de los polos se achican con el seno de la colatitud
+    let ring = (uv.y * PI).sin().max(0.05);
+    let east_slope = (height(uv.x + du, uv.y) - height(uv.x - du, uv.y)) / (2.0 * du * 2.0 * PI * ring);
+    let north_slope = (height(uv.x, uv.y - dv) - height(uv.x, uv.y + dv)) / (2.0 * dv * PI);
+
+    let (east, north) = tangent_basis(local);
+    (local - east * (east_slope * strength) - north * (north_slope * strength)).normalized()
+}
+
+/// Altura en [0, 1] con filtrado bilineal (con vecino más cercano la pendiente
+/// sale escalonada).
+fn height_at(map: &Texture, u: f32, v: f32) -> f32 {
+    let x = (u - u.floor()) * map.width as f32 - 0.5;
+    let y = (v.clamp(0.0, 1.0) * map.height as f32 - 0.5).clamp(0.0, (map.height - 1) as f32);
+    let (x0, y0) = (x.floor(), y.floor());
+    let (tx, ty) = (x - x0, y - y0);
+
+    let texel = |x: f32, y: f32| {
+        let xi = (x as i64).rem_euclid(map.width as i64) as usize;
+        let yi = (y as usize).min(map.height - 1);
+        color::luminance(color::to_rgb(map.pixels[yi * map.width + xi]))
+    };
+    let top = texel(x0, y0) * (1.0 - tx) + texel(x0 + 1.0, y0) * tx;
+    let bottom = texel(x0, y0 + 1.0) * (1.0 - tx) + texel(x0 + 1.0, y0 + 1.0) * tx;
+    top * (1.0 - ty) + bottom * ty
+}
+
+/// Cuánta nube hay en (u, v): el gris por el alfa, así sirven tanto texturas
+/// en escala de grises como con transparencia.
+fn cloud_density(map: &Texture, u: f32, v: f32) -> f32 {
+    let texel = map.sample(u, v);
+    color::luminance(color::to_rgb(texel)) * color::alpha(texel)
+}
+
+/// Cobertura de nubes en [0, 1] sobre el punto `position` de la superficie,
+/// vista hacia la luz. Promedia varias muestras alrededor del cruce con la
+/// cáscara para que el borde de la sombra quede suave.
+fn cloud_shadow(layer: &CloudLayer, map: &Texture, spin: Quat, center: Vec3, radius: f32, position: Vec3, to_light: Vec3) -> f32 {
+    // Desde adentro de la cáscara el rayo siempre la cruza una vez
+    let shell = radius + layer.altitude;
+    let oc = position - center;
+    let b = oc.dot(to_light);
+    let c = oc.dot(oc) - shell * shell;
+    let t = -b + (b * b - c).max(0.0).sqrt();
+
+    let local = spin.conjugate().rotate((oc + to_light * t) / shell);
+    let uv = equirect_uv(local);
+    let (du, dv) = (CLOUD_SHADOW_BLUR / map.width as f32, CLOUD_SHADOW_BLUR / map.height as f32);
+
+    let taps = [(0.0, 0.0), (du, 0.0), (-du, 0.0), (0.0, dv), (0.0, -dv)];
+    taps.iter().map(|(ou, ov)| cloud_density(map, uv.x + ou, uv.y + ov)).sum::<f32>() / taps.len() as f32
+}
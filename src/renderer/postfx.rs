@@ -0,0 +1,92 @@
+//! Efectos sobre el framebuffer terminado.
+
+use super::{color, Renderer};
+use crate::math::Vec3;
+
+/// Pasadas de desenfoque de caja (tres aproximan bien una gaussiana).
+const BLUR_PASSES: usize = 3;
+
+impl Renderer {
+    /// Bloom: junta los píxeles más brillantes que `threshold` (luminancia en
+    /// [0, 1]), los desenfoca a media resolución y los vuelve a sumar por encima
+    /// multiplicados por `strength`, así las zonas brillantes se derraman.
+    pub fn bloom(&mut self, threshold: f32, strength: f32, radius: usize) {
+        if strength <= 0.0 {
+            return;
+        }
+
+        let (w, h) = (self.width / 2, self.height / 2);
+        if w == 0 || h == 0 {
+            return;
+        }
+
+        // Extracción de lo brillante a media resolución (promedio de 2x2)
+        let mut bright = vec![Vec3::zero(); w * h];
+        for y in 0..h {
+            for x in 0..w {
+                let mut sum = Vec3::zero();
+                for (dx, dy) in [(0, 0), (1, 0), (0, 1), (1, 1)] {
+                    sum += color::to_rgb(self.fb.pixels[(y * 2 + dy) * self.width + x * 2 + dx]);
+                }
+                let rgb = sum * 0.25;
+                let excess = color::luminance(rgb) - threshold;
+                if excess > 0.0 {
+                    bright[y * w + x] = rgb * (excess / (1.0 - threshold).max(1e-3));
+                }
+            }
+        }
+
+        let mut scratch = vec![Vec3::zero(); w * h];
+        for _ in 0..BLUR_PASSES {
+            box_blur(&bright, &mut scratch, w, h, radius, true);
+            box_blur(&scratch, &mut bright, w, h, radius, false);
+        }
+
+        // Se suma de vuelta con filtrado bilineal para no ver los bloques de 2x2
+        for y in 0..self.height {
+            let fy = (y as f32 - 0.5) * 0.5;
+            for x in 0..self.width {
+                let fx = (x as f32 - 0.5) * 0.5;
+                let glow = bilinear(&bright, w, h, fx, fy);
+                if glow.x + glow.y + glow.z <= 0.0 {
+                    continue;
+                }
+                let i = y * self.width + x;
+                self.fb.pixels[i] = color::from_rgb(color::to_rgb(self.fb.pixels[i]) + glow * strength);
+            }
+        }
+    }
+}
+
+/// Promedio móvil de `2·radius + 1` muestras en una dirección (O(1) por píxel).
+fn box_blur(src: &[Vec3], dst: &mut [Vec3], w: usize, h: usize, radius: usize, horizontal: bool) {
+    let (len, lines) = if horizontal { (w, h) } else { (h, w) };
+    let index = |line: usize, k: usize| if horizontal { line * w + k } else { k * w + line };
+    let norm = 1.0 / (2 * radius + 1) as f32;
+
+    for line in 0..lines {
+        // Los bordes se extienden repitiendo el primer y el último valor
+        let at = |k: isize| src[index(line, k.clamp(0, len as isize - 1) as usize)];
+        let mut sum = Vec3::zero();
+        for k in -(radius as isize)..=radius as isize {
+            sum += at(k);
+        }
+        for k in 0..len {
+            dst[index(line, k)] = sum * norm;
+            sum += at(k as isize + radius as isize + 1);
+            sum -= at(k as isize - radius as isize);
+        }
+    }
+}
+
+fn bilinear(buf: &[Vec3], w: usize, h: usize, x: f32, y: f32) -> Vec3 {
+    let x = x.clamp(0.0, (w - 1) as f32);
+    let y = y.clamp(0.0, (h - 1) as f32);
+    let (x0, y0) = (x as usize, y as usize);
+    let (x1, y1) = ((x0 + 1).min(w - 1), (y0 + 1).min(h - 1));
+    let (tx, ty) = (x - x0 as f32, y - y0 as f32);
+
+    let top = buf[y0 * w + x0].lerp(buf[y0 * w + x1], tx);
+    let bottom = buf[y1 * w + x0].lerp(buf[y1 * w + x1], tx);
+    top.lerp(bottom, ty)
+}
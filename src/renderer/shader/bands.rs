@@ -0,0 +1,59 @@
+use super::{ShaderInput, SurfaceSample, SurfaceShader};
+use crate::math::{smoothstep, Noise3, Quat, Vec3};
+use crate::renderer::color;
+
+/// Gigante gaseoso: bandas de latitud deformadas por turbulencia, con
+/// rotación diferencial (cada latitud gira a su ritmo) y una gran tormenta.
+pub struct GasGiantShader {
+    noise: Noise3,
+    light: Vec3,
+    dark: Vec3,
+    storm: Vec3,
+    /// Centro de la tormenta (latitud, longitud en radianes).
+    storm_at: (f32, f32),
+}
+
+impl GasGiantShader {
+    pub fn new(seed: u64, color: u32) -> Self {
+        let base = color::to_rgb(color);
+        let noise = Noise3::new(seed);
+        let jitter = noise.sample(Vec3::new(0.5, 1.5, 2.5));
+        Self {
+            noise,
+            light: base.lerp(Vec3::new(1.0, 0.97, 0.9), 0.45),
+            dark: color::modulate(base, Vec3::new(0.65, 0.5, 0.4)),
+            storm: color::modulate(base, Vec3::new(1.1, 0.55, 0.4)),
+            storm_at: (-0.38 + 0.1 * jitter, 1.0 + 2.0 * jitter),
+        }
+    }
+}
+
+impl SurfaceShader for GasGiantShader {
+    fn shade(&self, input: &ShaderInput) -> SurfaceSample {
+        let p = input.local;
+        let lat = p.y.clamp(-1.0, 1.0).asin();
+
+        // Rotación diferencial: las bandas se deslizan unas contra otras
+        let shear = Quat::from_axis_angle(Vec3::up(), input.time * 0.04 * (lat * 5.0).sin());
+        let q = shear.rotate(p);
+
+        let turbulence = self.noise.fbm(Vec3::new(q.x * 3.0, q.y * 12.0, q.z * 3.0), 4);
+        let band = 0.5 + 0.5 * (lat * 14.0 + turbulence * 2.2).sin();
+        let fine = 0.5 + 0.5 * self.noise.sample(Vec3::new(q.x * 2.0, q.y * 40.0, q.z * 2.0));
+        let mut albedo = self.dark.lerp(self.light, smoothstep(0.2, 0.8, band)) * (0.9 + 0.15 * fine);
+
+        // Tormenta ovalada (más ancha que alta) que rota con su latitud
+        let (storm_lat, storm_lon) = self.storm_at;
+        let lon = q.z.atan2(q.x);
+        let mut dlon = lon - storm_lon;
+        dlon = (dlon + std::f32::consts::PI).rem_euclid(2.0 * std::f32::consts::PI) - std::f32::consts::PI;
+        let d = ((dlon * storm_lat.cos() / 0.22).powi(2) + ((lat - storm_lat) / 0.09).powi(2)).sqrt();
+        if d < 1.2 {
+            let swirl = 0.5 + 0.5 * (d * 9.0 + turbulence * 3.0).sin();
+            let strength = 1.0 - smoothstep(0.7, 1.2, d);
+            albedo = albedo.lerp(self.storm * (0.85 + 0.25 * swirl), strength);
+        }
+
+        SurfaceSample::lit(albedo)
+    }
+}
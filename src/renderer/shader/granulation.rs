@@ -0,0 +1,47 @@
+use super::{ShaderInput, SurfaceSample, SurfaceShader};
+use crate::math::{smoothstep, Noise3, Vec3};
+use crate::renderer::color;
+
+/// Superficie de una estrella: celdas de convección que hierven con el tiempo,
+/// manchas oscuras y oscurecimiento hacia el borde del disco.
+pub struct GranulationShader {
+    noise: Noise3,
+    spots: Noise3,
+    color: Vec3,
+}
+
+impl GranulationShader {
+    pub fn new(seed: u64, color: u32) -> Self {
+        Self {
+            noise: Noise3::new(seed),
+            spots: Noise3::new(seed ^ 0x5107),
+            color: color::to_rgb(color),
+        }
+    }
+}
+
+impl SurfaceShader for GranulationShader {
+    fn shade(&self, input: &ShaderInput) -> SurfaceSample {
+        let p = input.local;
+        let t = input.time;
+
+        // Gránulos: ruido de alta frecuencia que se desplaza en la cuarta dimensión
+        // (el tiempo empuja la muestra en una dirección fija)
+        let drift = Vec3::new(0.31, 0.17, 0.23) * (t * 0.15);
+        let cells = 1.0 - self.noise.sample(p * 22.0 + drift).abs() * 2.0;
+        let boil = self.noise.fbm(p * 6.0 - drift * 0.5, 3);
+        let granules = 0.8 + 0.2 * cells + 0.1 * boil;
+
+        // Manchas: zonas grandes que casi no cambian, lejos de los polos
+        let spot = smoothstep(0.28, 0.4, self.spots.fbm(p * 3.0 + drift * 0.05, 3)) * (1.0 - p.y.abs());
+
+        // Oscurecimiento del borde: el borde se ve más frío
+        let mu = input.normal.dot(input.view_dir).max(0.0);
+        let limb = 0.55 + 0.45 * mu.sqrt();
+
+        SurfaceSample {
+            albedo: Vec3::zero(),
+            emission: self.color * (granules * limb * (1.0 - 0.6 * spot)),
+        }
+    }
+}
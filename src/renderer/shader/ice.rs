@@ -0,0 +1,45 @@
+use super::{ShaderInput, SurfaceSample, SurfaceShader};
+use crate::math::{smoothstep, Noise3, Vec3};
+use crate::renderer::color;
+
+/// Luna helada: hielo claro con vetas rojizas (tipo Europa) y destellos donde
+/// el sol se refleja hacia la cámara.
+pub struct IceShader {
+    noise: Noise3,
+    color: Vec3,
+}
+
+/// Color de las vetas (sales y polvo atrapados en las grietas).
+const LINEAE: Vec3 = Vec3 { x: 0.55, y: 0.32, z: 0.2 };
+
+impl IceShader {
+    pub fn new(seed: u64, color: u32) -> Self {
+        Self {
+            noise: Noise3::new(seed),
+            color: color::to_rgb(color),
+        }
+    }
+}
+
+impl SurfaceShader for IceShader {
+    fn shade(&self, input: &ShaderInput) -> SurfaceSample {
+        let p = input.local;
+        let frost = 0.85 + 0.2 * self.noise.fbm(p * 4.0, 4);
+        let mut albedo = self.color.lerp(Vec3::new(0.95, 0.98, 1.0), 0.4) * frost;
+
+        // Vetas: dos familias de líneas finas que se cruzan
+        for (scale, offset) in [(5.0, Vec3::zero()), (9.0, Vec3::new(4.1, 2.3, 7.7))] {
+            let line = 1.0 - self.noise.sample(p * scale + offset).abs() * 12.0;
+            albedo = albedo.lerp(LINEAE, smoothstep(0.0, 1.0, line) * 0.55);
+        }
+
+        // Destellos del hielo: un reflejo especular muy cerrado
+        let half = (input.light_dir + input.view_dir).normalized();
+        let glint = input.normal.dot(half).max(0.0).powf(120.0) * input.normal.dot(input.light_dir).max(0.0);
+
+        SurfaceSample {
+            albedo,
+            emission: Vec3::new(1.0, 1.0, 1.0) * (0.5 * glint),
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use super::{ShaderInput, SurfaceSample, SurfaceShader};
+use crate::math::{smoothstep, Noise3, Vec3};
+use crate::renderer::color;
+
+/// Mundo de lava: corteza oscura partida por grietas que brillan solas y
+/// laten despacio; la lava fluye con el tiempo.
+pub struct LavaShader {
+    noise: Noise3,
+    crust: Vec3,
+}
+
+/// Color de la lava más caliente (el centro de las grietas).
+const HOT: Vec3 = Vec3 { x: 1.0, y: 0.85, z: 0.35 };
+/// Color de la lava que se enfría (el borde de las grietas).
+const WARM: Vec3 = Vec3 { x: 0.9, y: 0.22, z: 0.02 };
+
+impl LavaShader {
+    pub fn new(seed: u64, color: u32) -> Self {
+        Self {
+            noise: Noise3::new(seed),
+            crust: color::to_rgb(color) * 0.35,
+        }
+    }
+}
+
+impl SurfaceShader for LavaShader {
+    fn shade(&self, input: &ShaderInput) -> SurfaceSample {
+        let p = input.local;
+        let flow = Vec3::new(0.0, 0.0, input.time * 0.03);
+
+        // Grietas: donde el ruido cruza el cero, afinadas con una potencia
+        let warp = self.noise.fbm(p * 3.0 + flow, 3);
+        let ridge = 1.0 - self.noise.sample(p * 7.0 + Vec3::new(warp, warp, warp) * 0.8 + flow).abs();
+        let cracks = smoothstep(0.86, 0.98, ridge);
+
+        let pulse = 0.8 + 0.2 * (input.time * 1.3 + warp * 6.0).sin();
+        let glow = WARM.lerp(HOT, smoothstep(0.95, 1.0, ridge)) * (cracks * pulse * 1.4);
+
+        let rough = 0.7 + 0.5 * self.noise.fbm(p * 16.0, 3);
+        SurfaceSample {
+            albedo: self.crust * rough * (1.0 - cracks),
+            emission: glow,
+        }
+    }
+}
@@ -0,0 +1,65 @@
+//! Shaders procedurales de superficie: en vez de leer una imagen, calculan el
+//! color de cada fragmento de la esfera con ruido, la posición y el tiempo.
+
+pub mod bands;
+pub mod granulation;
+pub mod ice;
+pub mod lava;
+pub mod rock;
+
+use crate::math::Vec3;
+use crate::world::ShaderKind;
+
+pub use bands::GasGiantShader;
+pub use granulation::GranulationShader;
+pub use ice::IceShader;
+pub use lava::LavaShader;
+pub use rock::RockShader;
+
+/// Lo que un shader sabe de cada fragmento.
+pub struct ShaderInput {
+    /// Normal en coordenadas de mundo.
+    pub normal: Vec3,
+    /// Normal en los ejes del cuerpo (gira con él): es la coordenada para el ruido.
+    pub local: Vec3,
+    /// Dirección hacia la luz (mundo, normalizada).
+    pub light_dir: Vec3,
+    /// Dirección hacia la cámara (mundo, normalizada).
+    pub view_dir: Vec3,
+    /// Segundos de simulación, para animar.
+    pub time: f32,
+}
+
+/// Resultado de un shader: el color que se ilumina y la luz propia que se
+/// suma sin importar el sol (lava, superficie de una estrella).
+pub struct SurfaceSample {
+    pub albedo: Vec3,
+    pub emission: Vec3,
+}
+
+impl SurfaceSample {
+    pub fn lit(albedo: Vec3) -> Self {
+        Self {
+            albedo,
+            emission: Vec3::zero(),
+        }
+    }
+}
+
+/// Superficie calculada por fragmento. Se evalúa una vez por píxel visible,
+/// así que tiene que ser barata.
+pub trait SurfaceShader {
+    fn shade(&self, input: &ShaderInput) -> SurfaceSample;
+}
+
+/// Arma el shader de superficie que eligió un cuerpo, con una semilla (la forma
+/// del ruido) y el color base del cuerpo.
+pub fn build(kind: ShaderKind, seed: u64, color: u32) -> Box<dyn SurfaceShader> {
+    match kind {
+        ShaderKind::Granulation => Box::new(GranulationShader::new(seed, color)),
+        ShaderKind::GasGiant => Box::new(GasGiantShader::new(seed, color)),
+        ShaderKind::Rock => Box::new(RockShader::new(seed, color)),
+        ShaderKind::Lava => Box::new(LavaShader::new(seed, color)),
+        ShaderKind::Ice => Box::new(IceShader::new(seed, color)),
+    }
+}
@@ -0,0 +1,61 @@
+use super::{ShaderInput, SurfaceSample, SurfaceShader};
+use crate::math::{smoothstep, Noise3, Rng, Vec3};
+use crate::renderer::color;
+
+/// Cráteres que se ven en el shader.
+const CRATERS: usize = 60;
+
+/// Roca sin aire: tono con manchas de ruido y cráteres de fondo oscuro, borde
+/// claro y rayos de material eyectado.
+pub struct RockShader {
+    noise: Noise3,
+    color: Vec3,
+    /// (centro en la esfera unitaria, radio angular en radianes)
+    craters: Vec<(Vec3, f32)>,
+}
+
+impl RockShader {
+    pub fn new(seed: u64, color: u32) -> Self {
+        let mut rng = Rng::new(seed);
+        let craters = (0..CRATERS)
+            .map(|_| {
+                // Muchos chicos y pocos grandes
+                let size = rng.next_f32();
+                (rng.unit_vector(), 0.03 + 0.22 * size * size * size)
+            })
+            .collect();
+        Self {
+            noise: Noise3::new(seed),
+            color: color::to_rgb(color),
+            craters,
+        }
+    }
+}
+
+impl SurfaceShader for RockShader {
+    fn shade(&self, input: &ShaderInput) -> SurfaceSample {
+        let p = input.local;
+        let mottling = self.noise.fbm(p * 5.0, 5);
+        let mut shade = 0.8 + 0.35 * mottling;
+
+        for &(center, radius) in &self.craters {
+            let cos = p.dot(center);
+            // Descarte rápido: lejos del cráter y de sus rayos
+            if cos < (radius * 3.0).min(3.1).cos() {
+                continue;
+            }
+            let x = cos.clamp(-1.0, 1.0).acos() / radius;
+            if x < 1.0 {
+                // Fondo más oscuro y borde más claro
+                shade *= 0.75 + 0.45 * smoothstep(0.7, 1.0, x);
+            } else {
+                // Rayos: claros, angostos y que se apagan con la distancia
+                let around = Vec3::cross(center, p).normalized();
+                let rays = self.noise.sample(around * (14.0 / radius.sqrt())).max(0.0);
+                shade += 0.35 * rays * (1.0 - smoothstep(1.0, 3.0, x));
+            }
+        }
+
+        SurfaceSample::lit(self.color * shade)
+    }
+}
@@ -0,0 +1,100 @@
+use std::f32::consts::PI;
+
+use crate::math::Vec3;
+
+/// Esfera que puede tapar la luz de la estrella (un planeta, una luna...).
+#[derive(Clone, Copy, Debug)]
+pub struct Occluder {
+    pub center: Vec3,
+    pub radius: f32,
+}
+
+/// Estrella vista como disco de tamaño finito, junto con los cuerpos que pueden
+/// eclipsarla. Como la luz no sale de un punto, las sombras tienen umbra y
+/// penumbra.
+pub struct Light<'a> {
+    pub position: Vec3,
+    pub radius: f32,
+    pub occluders: &'a [Occluder],
+}
+
+impl Light<'_> {
+    /// Fracción visible del disco de la estrella desde `point`: 1 a pleno sol,
+    /// 0 en la umbra y valores intermedios en la penumbra.
+    pub fn visibility(&self, point: Vec3, occluders: &[Occluder]) -> f32 {
+        let to_light = self.position - point;
+        let distance = to_light.length();
+        if distance <= self.radius {
+            return 1.0;
+        }
+        let dir = to_light / distance;
+        let sun = (self.radius / distance).asin();
+
+        let mut visible = 1.0;
+        for o in occluders {
+            let to_occluder = o.center - point;
+            let d = to_occluder.length();
+            // Detrás de la estrella, o el punto está adentro del cuerpo
+            if d >= distance || d <= o.radius {
+                continue;
+            }
+            let size = (o.radius / d).asin();
+            // atan2 en vez de acos: más precisión con ángulos chicos
+            let separation = Vec3::cross(dir, to_occluder).length().atan2(dir.dot(to_occluder));
+            if separation >= sun + size {
+                continue;
+            }
+            if sun <= 0.0 {
+                return 0.0;
+            }
+            visible -= disc_overlap(sun, size, separation) / (PI * sun * sun);
+        }
+        visible.max(0.0)
+    }
+
+    /// Los oclusores que pueden dar sombra sobre alguna parte de la esfera
+    /// (`center`, `radius`). Sirve para descartar una vez por cuerpo y no por píxel.
+    pub fn casters_for(&self, center: Vec3, radius: f32) -> Vec<Occluder> {
+        let to_light = self.position - center;
+        let distance = to_light.length();
+        if distance <= self.radius + radius {
+            return Vec::new();
+        }
+        let sun = ((self.radius + radius) / distance).min(1.0).asin();
+
+        self.occluders
+            .iter()
+            .filter(|o| {
+                let to_occluder = o.center - center;
+                let d = to_occluder.length();
+                // El propio cuerpo no se eclipsa (su lado nocturno ya es la sombra)
+                if d <= 1e-4 || d >= distance + radius {
+                    return false;
+                }
+                if d <= o.radius + radius {
+                    return true;
+                }
+                // Margen angular agrandado por el radio del cuerpo: cubre todos sus puntos
+                let size = ((o.radius + radius) / d).min(1.0).asin();
+                let separation = Vec3::cross(to_light, to_occluder).length().atan2(to_light.dot(to_occluder));
+                separation < sun + size
+            })
+            .copied()
+            .collect()
+    }
+}
+
+/// Área de la intersección de dos discos de radios `a` y `b` separados por `d`.
+fn disc_overlap(a: f32, b: f32, d: f32) -> f32 {
+    if d >= a + b {
+        return 0.0;
+    }
+    if d <= (a - b).abs() {
+        let r = a.min(b);
+        return PI * r * r;
+    }
+    let alpha = ((d * d + a * a - b * b) / (2.0 * d * a)).clamp(-1.0, 1.0).acos();
+    let beta = ((d * d + b * b - a * a) / (2.0 * d * b)).clamp(-1.0, 1.0).acos();
+    let kite = 0.5 * ((-d + a + b) * (d + a - b) * (d - a + b) * (d + a + b)).max(0.0).sqrt();
+    a * a * alpha + b * b * beta - kite
+}
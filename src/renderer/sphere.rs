@@ -0,0 +1,166 @@
+use std::f32::consts::PI;
+
+use super::{RasterOptions, Renderer};
+use crate::camera::{Camera, NEAR};
+use crate::math::{Quat, Vec2, Vec3};
+
+/// Punto visible de una esfera trazada por píxel.
+pub struct SphereFragment {
+    /// Punto de la superficie en coordenadas de mundo.
+    pub position: Vec3,
+    /// Normal en coordenadas de mundo.
+    pub normal: Vec3,
+    /// Normal en los ejes del cuerpo (sin su rotación), para texturas y patrones.
+    pub local: Vec3,
+    /// Coordenadas equirectangulares: u = longitud, v = 0 en el polo norte.
+    pub uv: Vec2,
+}
+
+impl Renderer {
+    /// Dibuja una esfera de verdad: por cada píxel de su silueta lanza un rayo
+    /// desde la cámara, calcula el punto de impacto y escribe su profundidad,
+    /// así queda bien ordenada contra mallas, anillos y otras esferas.
+    /// `orientation` gira la superficie (textura) respecto del mundo y
+    /// `options` dice cómo usar el z-buffer y si el color se mezcla por alfa
+    /// (el culling no aplica: solo se ve la cara de adelante).
+    pub fn draw_sphere<F>(
+        &mut self,
+        camera: &Camera,
+        center: Vec3,
+        radius: f32,
+        orientation: Quat,
+        options: RasterOptions,
+        mut shade: F,
+    ) where
+        F: FnMut(&SphereFragment) -> Option<u32>,
+    {
+        let (right, up, forward) = camera.basis();
+        let eye = camera.position;
+        let half_h = (camera.fov_y * 0.5).tan();
+        let half_w = half_h * self.aspect();
+
+        let oc = eye - center;
+        let c = oc.dot(oc) - radius * radius;
+        if c <= 0.0 {
+            return; // cámara adentro
+        }
+
+        let Some((min_x, max_x, min_y, max_y)) = self.sphere_bounds(camera, center, radius) else {
+            return;
+        };
+        let inverse = orientation.conjugate();
+
+        for y in min_y..=max_y {
+            let ndc_y = 1.0 - (y as f32 + 0.5) / self.height as f32 * 2.0;
+            for x in min_x..=max_x {
+                let ndc_x = (x as f32 + 0.5) / self.width as f32 * 2.0 - 1.0;
+
+                // Con dir·forward = 1, el parámetro del rayo ya es la profundidad de vista
+                let dir = forward + right * (ndc_x * half_w) + up * (ndc_y * half_h);
+                let a = dir.dot(dir);
+                let b = oc.dot(dir);
+                let disc = b * b - a * c;
+                if disc < 0.0 {
+                    continue;
+                }
+                let depth = (-b - disc.sqrt()) / a;
+                if depth <= NEAR || (options.depth_test && !self.fb.depth_test(x, y, depth)) {
+                    continue;
+                }
+
+                let position = eye + dir * depth;
+                let normal = (position - center) / radius;
+                let local = inverse.rotate(normal);
+                let frag = SphereFragment {
+                    position,
+                    normal,
+                    local,
+                    uv: equirect_uv(local),
+                };
+
+                if let Some(color) = shade(&frag) {
+                    if options.blend {
+                        self.fb.blend_pixel(x, y, color);
+                    } else {
+                        self.fb.put_pixel(x, y, color);
+                    }
+                    if options.depth_write {
+                        self.fb.set_depth(x, y, depth);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Rectángulo en píxeles que contiene la silueta de la esfera (recortado a
+    /// la pantalla), o `None` si no se ve.
+    pub(super) fn sphere_bounds(&self, camera: &Camera, center: Vec3, radius: f32) -> Option<(i32, i32, i32, i32)> {
+        let (right, up, forward) = camera.basis();
+        let rel = center - camera.position;
+        let (vx, vy, vz) = (rel.dot(right), rel.dot(up), rel.dot(forward));
+
+        if vz + radius <= NEAR {
+            return None; // detrás de la cámara
+        }
+
+        let full = (0, self.width as i32 - 1, 0, self.height as i32 - 1);
+        if vz - radius <= NEAR {
+            return Some(full); // cruza el plano cercano: se prueba toda la pantalla
+        }
+
+        let half_h = (camera.fov_y * 0.5).tan();
+        let half_w = half_h * self.aspect();
+
+        // Rectas tangentes a la esfera en cada eje: extremos exactos de x/z e y/z
+        let denom = vz * vz - radius * radius;
+        let extent = |v: f32| -> (f32, f32) {
+            let root = radius * (v * v + denom).sqrt();
+            ((v * vz - root) / denom, (v * vz + root) / denom)
+        };
+        let (x0, x1) = extent(vx);
+        let (y0, y1) = extent(vy);
+
+        let to_px = |t: f32| (t / half_w + 1.0) * 0.5 * self.width as f32;
+        let to_py = |t: f32| (1.0 - t / half_h) * 0.5 * self.height as f32;
+
+        let min_x = to_px(x0).floor().max(0.0) as i32;
+        let max_x = to_px(x1).ceil().min(full.1 as f32) as i32;
+        let min_y = to_py(y1).floor().max(0.0) as i32;
+        let max_y = to_py(y0).ceil().min(full.3 as f32) as i32;
+
+        if min_x > max_x || min_y > max_y {
+            return None;
+        }
+        Some((min_x, max_x, min_y, max_y))
+    }
+}
+
+/// Longitud creciente hacia el este (antihorario visto desde +Y) y v = 0 en el polo norte.
+pub fn equirect_uv(local: Vec3) -> Vec2 {
+    Vec2::new(
+        0.5 + (-local.z).atan2(local.x) / (2.0 * PI),
+        local.y.clamp(-1.0, 1.0).acos() / PI,
+    )
+}
+
+/// Inversa de `equirect_uv`: la dirección en los ejes del cuerpo que cae en (u, v).
+pub fn equirect_direction(u: f32, v: f32) -> Vec3 {
+    let polar = v * PI;
+    let lon = (u - 0.5) * 2.0 * PI;
+    Vec3::new(polar.sin() * lon.cos(), polar.cos(), -polar.sin() * lon.sin())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn equirect_direction_inverts_equirect_uv() {
+        for &(u, v) in &[(0.5, 0.5), (0.1, 0.25), (0.9, 0.75), (0.3, 0.05), (0.7, 0.95)] {
+            let dir = equirect_direction(u, v);
+            assert!((dir.length() - 1.0).abs() < 1e-5);
+            let uv = equirect_uv(dir);
+            assert!((uv.x - u).abs() < 1e-5 && (uv.y - v).abs() < 1e-5, "({}, {}) -> {:?}", u, v, uv);
+        }
+    }
+}
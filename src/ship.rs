@@ -1,59 +1,163 @@
 use crate::camera::Camera;
-use crate::math::Vec3;
-use crate::renderer::Renderer;
+use crate::flight::FlightModel;
+use crate::input::InputState;
+use crate::math::{Mat4, Quat, Vec2, Vec3};
+use crate::renderer::{color, Cull, RasterOptions, RasterVertex, Renderer};
 
+/// Posición de la cámara de persecución en ejes de la nave (detrás y arriba).
+pub const CHASE_OFFSET: Vec3 = Vec3 { x: 0.0, y: 2.5, z: 9.0 };
+
+/// Radio de colisión de la nave.
+pub const SHIP_RADIUS: f32 = 2.0;
+
+// Modelo en ejes locales: la nariz apunta a -Z y +Y es "arriba".
+const VERTICES: [[f32; 3]; 15] = [
+    // Casco (0..6)
+    [0.0, 0.0, -3.0],
+    [0.0, 0.55, 0.4],
+    [-0.8, 0.0, 0.6],
+    [0.8, 0.0, 0.6],
+    [0.0, -0.4, 0.4],
+    [0.0, 0.0, 1.6],
+    // Ala izquierda (6..9)
+    [-0.6, 0.0, 0.2],
+    [-2.6, -0.1, 1.4],
+    [-0.7, 0.0, 1.3],
+    // Ala derecha (9..12)
+    [0.6, 0.0, 0.2],
+    [2.6, -0.1, 1.4],
+    [0.7, 0.0, 1.3],
+    // Aleta (12..15)
+    [0.0, 0.3, 0.9],
+    [0.0, 1.1, 1.7],
+    [0.0, 0.1, 1.5],
+];
+
+struct Face {
+    v: [usize; 3],
+    color: u32,
+    /// Las placas finas (alas, aleta) se ven desde los dos lados.
+    double_sided: bool,
+}
+
+const HULL: u32 = 0xFFD8DCE6;
+const CANOPY: u32 = 0xFF2E4A7A;
+const WING: u32 = 0xFF8A93A6;
+const FIN: u32 = 0xFFC0392B;
+
+const FACES: [Face; 11] = [
+    Face { v: [0, 2, 1], color: CANOPY, double_sided: false },
+    Face { v: [0, 1, 3], color: CANOPY, double_sided: false },
+    Face { v: [0, 4, 2], color: HULL, double_sided: false },
+    Face { v: [0, 3, 4], color: HULL, double_sided: false },
+    Face { v: [5, 1, 2], color: HULL, double_sided: false },
+    Face { v: [5, 3, 1], color: HULL, double_sided: false },
+    Face { v: [5, 2, 4], color: HULL, double_sided: false },
+    Face { v: [5, 4, 3], color: HULL, double_sided: false },
+    Face { v: [6, 7, 8], color: WING, double_sided: true },
+    Face { v: [9, 10, 11], color: WING, double_sided: true },
+    Face { v: [12, 13, 14], color: FIN, double_sided: true },
+];
+
+/// Nave del jugador: entidad propia con posición, orientación y velocidad
+/// (la velocidad la lleva su `FlightModel`).
 pub struct Ship {
     pub position: Vec3,
+    pub orientation: Quat,
+    pub flight: FlightModel,
+    thrusting: bool,
+    time: f32,
 }
 
 impl Ship {
     pub fn new() -> Self {
         Ship {
             position: Vec3::zero(),
+            orientation: Quat::identity(),
+            flight: FlightModel::new(),
+            thrusting: false,
+            time: 0.0,
         }
     }
 
-    pub fn update(&mut self, _dt: f32, camera: &Camera) {
-        // La nave se coloca un poco delante de la cámara
-        let forward = camera.forward();
-        self.position = camera.position + forward * 15.0;
+    /// Nave ubicada delante de la cámara, mirando hacia donde mira ella.
+    pub fn in_front_of(camera: &Camera) -> Self {
+        let mut ship = Self::new();
+        ship.orientation = camera.orientation;
+        ship.position = camera.position + camera.forward() * 15.0;
+        ship
+    }
+
+    /// Con `controlled` la nave responde al teclado; si no, sigue por inercia.
+    pub fn update(&mut self, dt: f32, input: &InputState, controlled: bool) {
+        self.time += dt;
+
+        if controlled {
+            self.flight.update(dt, input, &mut self.position, &mut self.orientation);
+            self.thrusting = FlightModel::is_thrusting(input);
+        } else {
+            self.position += self.flight.velocity * dt;
+            self.thrusting = false;
+        }
     }
 
-    pub fn render(&self, renderer: &mut Renderer, camera: &Camera) {
-        if let Some((cx, cy)) = renderer.project_point(self.position, camera) {
-            let size = 12;
-
-            // Cuerpo principal (blanco)
-            let nose = (cx, cy - size);                // punta
-            let left_bottom = (cx - size / 2, cy + size / 2);
-            let right_bottom = (cx + size / 2, cy + size / 2);
-            renderer.draw_triangle(nose, left_bottom, right_bottom, 0xFFFFFFFF);
-
-            // Aleta izquierda (gris clara)
-            let fin_left_top = (cx - size / 2, cy + size / 4);
-            let fin_left_bottom = (cx - size, cy + size);
-            renderer.draw_triangle(
-                fin_left_top,
-                fin_left_bottom,
-                left_bottom,
-                0xFFAAAAAA,
-            );
-
-            // Aleta derecha (gris clara)
-            let fin_right_top = (cx + size / 2, cy + size / 4);
-            let fin_right_bottom = (cx + size, cy + size);
-            renderer.draw_triangle(
-                fin_right_top,
-                fin_right_bottom,
-                right_bottom,
-                0xFFAAAAAA,
-            );
-
-            // “Fuego” del motor (naranja)
-            let flame_top = (cx, cy + size / 2);
-            let flame_left = (cx - size / 4, cy + size + 4);
-            let flame_right = (cx + size / 4, cy + size + 4);
-            renderer.draw_triangle(flame_top, flame_left, flame_right, 0xFFFF9933);
+    /// Dibuja la nave como modelo 3D relleno y con z-buffer, con sombreado
+    /// plano por cara respecto de la luz en `light_pos`.
+    pub fn render(&self, renderer: &mut Renderer, camera: &Camera, light_pos: Vec3) {
+        let view_proj = renderer.view_projection(camera);
+        let world: Vec<Vec3> = VERTICES
+            .iter()
+            .map(|v| self.position + self.orientation.rotate(Vec3::new(v[0], v[1], v[2])))
+            .collect();
+
+        for face in &FACES {
+            let [a, b, c] = face.v.map(|i| world[i]);
+            let center = (a + b + c) / 3.0;
+            let mut normal = Vec3::cross(b - a, c - a).normalized();
+
+            // Las placas dobles se iluminan por el lado que mira a la cámara
+            if face.double_sided && normal.dot(camera.position - center) < 0.0 {
+                normal = -normal;
+            }
+
+            let to_light = (light_pos - center).normalized();
+            let intensity = 0.25 + 0.75 * normal.dot(to_light).max(0.0);
+            let rgb = color::to_rgb(face.color) * intensity;
+
+            let options = RasterOptions {
+                cull: if face.double_sided { Cull::None } else { Cull::Back },
+                ..RasterOptions::opaque()
+            };
+            if let Some(verts) = project_triangle(renderer, &view_proj, [a, b, c], rgb) {
+                renderer.draw_shaded_triangle(verts, options, None);
+            }
         }
+
+        // Llama del motor: brilla sola, sin luz
+        if self.thrusting {
+            let flicker = 1.0 + 0.25 * (self.time * 40.0).sin();
+            let local = [
+                Vec3::new(-0.3, 0.0, 1.6),
+                Vec3::new(0.3, 0.0, 1.6),
+                Vec3::new(0.0, 0.0, 1.6 + 1.4 * flicker),
+            ];
+            let flame = local.map(|v| self.position + self.orientation.rotate(v));
+            let options = RasterOptions {
+                cull: Cull::None,
+                ..RasterOptions::opaque()
+            };
+            if let Some(verts) = project_triangle(renderer, &view_proj, flame, color::to_rgb(0xFFFF9933)) {
+                renderer.draw_shaded_triangle(verts, options, None);
+            }
+        }
+    }
+}
+
+fn project_triangle(renderer: &Renderer, view_proj: &Mat4, verts: [Vec3; 3], rgb: Vec3) -> Option<[RasterVertex; 3]> {
+    let mut out = [RasterVertex::flat(0.0, 0.0); 3];
+    for (i, v) in verts.iter().enumerate() {
+        let (x, y, w) = renderer.project_with(view_proj, *v)?;
+        out[i] = RasterVertex::new(x, y, w, rgb, Vec2::zero());
     }
+    Some(out)
 }
@@ -0,0 +1,214 @@
+//! Catálogo de estrellas leído de un CSV (formato tipo Hipparcos / HYG / Yale BSC)
+//! y figuras de constelaciones en el formato de Stellarium.
+//!
+//! El CSV necesita una fila de encabezado; las columnas se buscan por nombre:
+//! `ra` (horas), `dec` (grados), `mag` y, opcionales, `bv` (o `ci`, `b-v`),
+//! `hip` (o `id`) y `name` (o `proper`). Las líneas que empiezan con `#` se ignoran.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::math::Vec3;
+use crate::world::solar;
+
+/// Índice de color de las estrellas que no lo traen (parecido al Sol).
+const DEFAULT_BV: f32 = 0.6;
+
+/// Color según B−V (de azul a rojo), tomado de la tabla de colores estelares de Mitchell Charity.
+const BV_COLORS: [(f32, Vec3); 7] = [
+    (-0.4, Vec3 { x: 0.61, y: 0.69, z: 1.0 }),
+    (0.0, Vec3 { x: 0.79, y: 0.84, z: 1.0 }),
+    (0.4, Vec3 { x: 0.97, y: 0.96, z: 1.0 }),
+    (0.65, Vec3 { x: 1.0, y: 0.95, z: 0.88 }),
+    (1.0, Vec3 { x: 1.0, y: 0.85, z: 0.68 }),
+    (1.5, Vec3 { x: 1.0, y: 0.73, z: 0.47 }),
+    (2.0, Vec3 { x: 1.0, y: 0.6, z: 0.33 }),
+];
+
+/// Error de formato en el catálogo o en el archivo de constelaciones.
+#[derive(Debug)]
+pub struct CatalogError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl CatalogError {
+    fn new(line: usize, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for CatalogError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "línea {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for CatalogError {}
+
+pub struct CatalogStar {
+    /// Número de Hipparcos (o el id del catálogo), para las constelaciones.
+    pub id: Option<u32>,
+    pub name: Option<String>,
+    /// Dirección en el mundo (unitaria).
+    pub direction: Vec3,
+    pub magnitude: f32,
+    pub color: Vec3,
+}
+
+#[derive(Default)]
+pub struct StarCatalog {
+    pub stars: Vec<CatalogStar>,
+    /// Segmentos de las constelaciones, como índices en `stars`.
+    pub lines: Vec<(usize, usize)>,
+}
+
+impl StarCatalog {
+    pub fn parse(text: &str) -> Result<Self, CatalogError> {
+        let mut rows = text
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'));
+
+        let (header_line, header) = rows.next().ok_or_else(|| CatalogError::new(1, "el catálogo está vacío"))?;
+        let columns: Vec<String> = header.split(',').map(|c| c.trim().to_lowercase()).collect();
+        let column = |names: &[&str]| columns.iter().position(|c| names.contains(&c.as_str()));
+        let required = |names: &[&str]| {
+            column(names).ok_or_else(|| CatalogError::new(header_line, format!("falta la columna {}", names[0])))
+        };
+
+        let ra = required(&["ra"])?;
+        let dec = required(&["dec"])?;
+        let mag = required(&["mag"])?;
+        let bv = column(&["bv", "ci", "b-v"]);
+        let id = column(&["hip", "id"]);
+        let name = column(&["name", "proper"]);
+
+        let mut stars = Vec::new();
+        for (line, row) in rows {
+            let fields: Vec<&str> = row.split(',').map(str::trim).collect();
+            let field = |index: Option<usize>| index.and_then(|i| fields.get(i).copied()).filter(|f| !f.is_empty());
+            let number = |index: usize, what: &str| -> Result<f32, CatalogError> {
+                let value = field(Some(index)).ok_or_else(|| CatalogError::new(line, format!("falta {}", what)))?;
+                value.parse().map_err(|_| CatalogError::new(line, format!("{} inválido: {}", what, value)))
+            };
+
+            let ra_hours = number(ra, "ra")?;
+            let dec_deg = number(dec, "dec")?;
+            let magnitude = number(mag, "mag")?;
+            let bv = field(bv).and_then(|v| v.parse().ok()).unwrap_or(DEFAULT_BV);
+
+            stars.push(CatalogStar {
+                id: field(id).and_then(|v| v.parse().ok()),
+                name: field(name).map(str::to_string),
+                direction: solar::equatorial_direction(ra_hours as f64 * 15.0, dec_deg as f64),
+                magnitude,
+                color: bv_to_rgb(bv),
+            });
+        }
+
+        Ok(Self { stars, lines: Vec::new() })
+    }
+
+    /// Agrega las figuras de un archivo `constellationship.fab` de Stellarium:
+    /// `Ori 9 27989 26727 ...` (abreviatura, cantidad de segmentos y los pares
+    /// de números de Hipparcos). Los segmentos con estrellas que no están en el
+    /// catálogo se saltean; devuelve cuántos se agregaron.
+    pub fn add_constellations(&mut self, text: &str) -> Result<usize, CatalogError> {
+        let by_id: HashMap<u32, usize> = self
+            .stars
+            .iter()
+            .enumerate()
+            .filter_map(|(i, s)| s.id.map(|id| (id, i)))
+            .collect();
+        let before = self.lines.len();
+
+        for (i, row) in text.lines().enumerate() {
+            let line = i + 1;
+            let row = row.trim();
+            if row.is_empty() || row.starts_with('#') {
+                continue;
+            }
+
+            let mut tokens = row.split_whitespace();
+            let abbr = tokens.next().unwrap_or_default();
+            let count: usize = tokens
+                .next()
+                .and_then(|t| t.parse().ok())
+                .ok_or_else(|| CatalogError::new(line, format!("{}: falta la cantidad de segmentos", abbr)))?;
+            let ids = tokens
+                .map(|t| t.parse::<u32>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|_| CatalogError::new(line, format!("{}: número de estrella inválido", abbr)))?;
+            if ids.len() != count * 2 {
+                return Err(CatalogError::new(
+                    line,
+                    format!("{}: se esperaban {} estrellas y hay {}", abbr, count * 2, ids.len()),
+                ));
+            }
+
+            for pair in ids.chunks(2) {
+                if let (Some(&a), Some(&b)) = (by_id.get(&pair[0]), by_id.get(&pair[1])) {
+                    self.lines.push((a, b));
+                }
+            }
+        }
+        Ok(self.lines.len() - before)
+    }
+
+    /// Estrella con nombre más cercana a `direction` (unitaria), si hay alguna a
+    /// menos de `max_angle` radianes.
+    pub fn star_toward(&self, direction: Vec3, max_angle: f32) -> Option<&CatalogStar> {
+        let min_cos = max_angle.cos();
+        self.stars
+            .iter()
+            .filter(|s| s.name.is_some())
+            .map(|s| (s, s.direction.dot(direction)))
+            .filter(|&(_, cos)| cos >= min_cos)
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(s, _)| s)
+    }
+}
+
+/// Color de una estrella según su índice B−V.
+pub fn bv_to_rgb(bv: f32) -> Vec3 {
+    let bv = bv.clamp(BV_COLORS[0].0, BV_COLORS[BV_COLORS.len() - 1].0);
+    for pair in BV_COLORS.windows(2) {
+        let ((b0, c0), (b1, c1)) = (pair[0], pair[1]);
+        if bv <= b1 {
+            return c0.lerp(c1, (bv - b0) / (b1 - b0));
+        }
+    }
+    BV_COLORS[BV_COLORS.len() - 1].1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CATALOG: &str = "hip,ra,dec,mag,name\n\
+                           32349,6.7525,-16.716,-1.46,Sirius\n\
+                           27989,5.9195,7.407,0.45,Betelgeuse\n\
+                           32000,6.70,-16.0,6.0,\n";
+
+    #[test]
+    fn star_toward_picks_the_nearest_named_star_within_the_cone() {
+        let catalog = StarCatalog::parse(CATALOG).unwrap();
+        let sirius = catalog.stars[0].direction;
+        let unnamed = catalog.stars[2].direction;
+
+        // La estrella sin nombre está justo adelante, pero se informa Sirio
+        let found = catalog.star_toward(unnamed, 0.1).and_then(|s| s.name.as_deref());
+        assert_eq!(found, Some("Sirius"));
+        let found = catalog.star_toward(sirius, 0.01).and_then(|s| s.name.as_deref());
+        assert_eq!(found, Some("Sirius"));
+
+        // Lejos de todo (el polo norte celeste) no hay ninguna
+        let pole = solar::equatorial_direction(0.0, 90.0);
+        assert!(catalog.star_toward(pole, 0.2).is_none());
+    }
+}
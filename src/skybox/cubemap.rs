@@ -0,0 +1,205 @@
+//! Cielo en cubemap: seis caras cuadradas, sin el estiramiento de los polos que
+//! tiene la imagen equirectangular.
+//!
+//! Las caras siguen la convención de OpenGL (+X, −X, +Y, −Y, +Z, −Z), que es la
+//! que usan casi todos los skyboxes publicados: cada cara se ve desde adentro del
+//! cubo con la fila 0 arriba. El filtrado bilineal cruza los bordes: el texel que
+//! falta a un lado de una cara se busca en la cara vecina, así no quedan costuras.
+
+use std::fmt;
+
+use crate::math::Vec3;
+use crate::renderer::color;
+use crate::texture::Texture;
+
+/// Nombres de archivo de las caras, en el orden de `Cubemap::faces`.
+pub const FACE_NAMES: [&str; 6] = ["px", "nx", "py", "ny", "pz", "nz"];
+
+/// Columna y fila de cada cara en una cruz horizontal (4x3).
+const HORIZONTAL_CROSS: [(usize, usize); 6] = [(2, 1), (0, 1), (1, 0), (1, 2), (1, 1), (3, 1)];
+/// Lo mismo en una cruz vertical (3x4); ahí −Z está abajo de todo y dada vuelta.
+const VERTICAL_CROSS: [(usize, usize); 6] = [(2, 1), (0, 1), (1, 0), (1, 2), (1, 1), (1, 3)];
+const NEG_Z: usize = 5;
+
+#[derive(Debug)]
+pub enum CubemapError {
+    /// Una cara no es cuadrada o no mide lo mismo que las demás.
+    FaceSize { face: &'static str, width: usize, height: usize },
+    /// La imagen no tiene proporción 4:3 ni 3:4.
+    CrossLayout { width: usize, height: usize },
+}
+
+impl fmt::Display for CubemapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CubemapError::FaceSize { face, width, height } => {
+                write!(f, "la cara {} mide {}x{}: las seis tienen que ser cuadradas e iguales", face, width, height)
+            }
+            CubemapError::CrossLayout { width, height } => {
+                write!(f, "una cruz tiene que ser de 4x3 o 3x4 caras y la imagen mide {}x{}", width, height)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CubemapError {}
+
+pub struct Cubemap {
+    /// Lado de cada cara en texels.
+    pub size: usize,
+    pub faces: [Texture; 6],
+}
+
+impl Cubemap {
+    /// Arma el cubemap con seis imágenes sueltas (en el orden de `FACE_NAMES`).
+    pub fn from_faces(faces: [&Texture; 6]) -> Result<Self, CubemapError> {
+        let size = faces[0].width;
+        for (i, face) in faces.iter().enumerate() {
+            if face.width != size || face.height != size || size == 0 {
+                return Err(CubemapError::FaceSize {
+                    face: FACE_NAMES[i],
+                    width: face.width,
+                    height: face.height,
+                });
+            }
+        }
+        Ok(Self {
+            size,
+            faces: faces.map(|face| Texture {
+                width: size,
+                height: size,
+                pixels: face.pixels.clone(),
+            }),
+        })
+    }
+
+    /// Recorta las caras de una cruz horizontal (4x3) o vertical (3x4).
+    pub fn from_cross(cross: &Texture) -> Result<Self, CubemapError> {
+        let (layout, size) = if cross.width * 3 == cross.height * 4 {
+            (HORIZONTAL_CROSS, cross.width / 4)
+        } else if cross.width * 4 == cross.height * 3 {
+            (VERTICAL_CROSS, cross.width / 3)
+        } else {
+            return Err(CubemapError::CrossLayout {
+                width: cross.width,
+                height: cross.height,
+            });
+        };
+        if size == 0 {
+            return Err(CubemapError::CrossLayout {
+                width: cross.width,
+                height: cross.height,
+            });
+        }
+
+        let vertical = layout == VERTICAL_CROSS;
+        let faces = std::array::from_fn(|face| {
+            let (col, row) = layout[face];
+            let mut pixels = Vec::with_capacity(size * size);
+            for y in 0..size {
+                for x in 0..size {
+                    let (sx, sy) = if vertical && face == NEG_Z { (size - 1 - x, size - 1 - y) } else { (x, y) };
+                    pixels.push(cross.pixels[(row * size + sy) * cross.width + col * size + sx]);
+                }
+            }
+            Texture {
+                width: size,
+                height: size,
+                pixels,
+            }
+        });
+        Ok(Self { size, faces })
+    }
+
+    /// Genera las caras muestreando `sample` (una dirección del mundo → color)
+    /// con 2x2 muestras por texel.
+    pub fn from_fn(size: usize, sample: impl Fn(Vec3) -> u32) -> Self {
+        let faces = std::array::from_fn(|face| {
+            let mut pixels = Vec::with_capacity(size * size);
+            for y in 0..size {
+                for x in 0..size {
+                    let mut sum = Vec3::zero();
+                    for (ox, oy) in [(0.25, 0.25), (0.75, 0.25), (0.25, 0.75), (0.75, 0.75)] {
+                        let u = (x as f32 + ox) / size as f32;
+                        let v = (y as f32 + oy) / size as f32;
+                        sum += color::to_rgb(sample(face_direction(face, u, v).normalized()));
+                    }
+                    pixels.push(color::from_rgb(sum * 0.25));
+                }
+            }
+            Texture {
+                width: size,
+                height: size,
+                pixels,
+            }
+        });
+        Self { size, faces }
+    }
+
+    /// Color en la dirección `dir` (no hace falta que esté normalizada), con
+    /// filtrado bilineal que sigue de largo en la cara vecina en los bordes.
+    pub fn sample(&self, dir: Vec3) -> u32 {
+        let (face, u, v) = face_uv(dir);
+        let x = u * self.size as f32 - 0.5;
+        let y = v * self.size as f32 - 0.5;
+        let (x0, y0) = (x.floor(), y.floor());
+        let (fx, fy) = (x - x0, y - y0);
+        let (x0, y0) = (x0 as i32, y0 as i32);
+
+        let top = color::to_rgb(self.texel(face, x0, y0)).lerp(color::to_rgb(self.texel(face, x0 + 1, y0)), fx);
+        let bottom = color::to_rgb(self.texel(face, x0, y0 + 1)).lerp(color::to_rgb(self.texel(face, x0 + 1, y0 + 1)), fx);
+        color::from_rgb(top.lerp(bottom, fy))
+    }
+
+    /// Texel (x, y) de una cara; si cae afuera, el que corresponde en la cara vecina.
+    fn texel(&self, face: usize, x: i32, y: i32) -> u32 {
+        let n = self.size as i32;
+        if (0..n).contains(&x) && (0..n).contains(&y) {
+            return self.faces[face].pixels[(y * n + x) as usize];
+        }
+        let dir = face_direction(face, (x as f32 + 0.5) / n as f32, (y as f32 + 0.5) / n as f32);
+        let (face, u, v) = face_uv(dir);
+        let x = ((u * n as f32) as i32).clamp(0, n - 1);
+        let y = ((v * n as f32) as i32).clamp(0, n - 1);
+        self.faces[face].pixels[(y * n + x) as usize]
+    }
+}
+
+/// Cara y coordenadas (0..1, fila 0 arriba) que ve la dirección `dir`: manda el eje
+/// de mayor valor absoluto.
+pub fn face_uv(dir: Vec3) -> (usize, f32, f32) {
+    let (ax, ay, az) = (dir.x.abs(), dir.y.abs(), dir.z.abs());
+    let (face, sc, tc, ma) = if ax >= ay && ax >= az {
+        if dir.x > 0.0 {
+            (0, -dir.z, -dir.y, ax)
+        } else {
+            (1, dir.z, -dir.y, ax)
+        }
+    } else if ay >= az {
+        if dir.y > 0.0 {
+            (2, dir.x, dir.z, ay)
+        } else {
+            (3, dir.x, -dir.z, ay)
+        }
+    } else if dir.z > 0.0 {
+        (4, dir.x, -dir.y, az)
+    } else {
+        (5, -dir.x, -dir.y, az)
+    };
+    let ma = ma.max(1e-12);
+    (face, (sc / ma + 1.0) * 0.5, (tc / ma + 1.0) * 0.5)
+}
+
+/// Inversa de `face_uv`: dirección (sin normalizar) del punto (u, v) de una cara.
+pub fn face_direction(face: usize, u: f32, v: f32) -> Vec3 {
+    let sc = u * 2.0 - 1.0;
+    let tc = v * 2.0 - 1.0;
+    match face {
+        0 => Vec3::new(1.0, -tc, -sc),
+        1 => Vec3::new(-1.0, -tc, sc),
+        2 => Vec3::new(sc, 1.0, tc),
+        3 => Vec3::new(sc, -1.0, -tc),
+        4 => Vec3::new(sc, -tc, 1.0),
+        _ => Vec3::new(-sc, -tc, -1.0),
+    }
+}
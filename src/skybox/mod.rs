@@ -0,0 +1,134 @@
+pub mod catalog;
+pub mod cubemap;
+pub mod rays;
+pub mod starfield;
+
+pub use catalog::StarCatalog;
+pub use cubemap::Cubemap;
+pub use rays::SkyRays;
+pub use starfield::draw_starfield;
+
+use crate::camera::Camera;
+use crate::math::Vec3;
+use crate::renderer::Renderer;
+use crate::texture::Texture;
+use rays::RayBasis;
+use std::f32::consts::PI;
+use std::rc::Rc;
+
+/// Imagen del cielo, en cualquiera de los dos formatos.
+pub enum Sky {
+    Equirect(Rc<Texture>),
+    Cubemap(Rc<Cubemap>),
+}
+
+/// Formato en el que se quiere dibujar el cielo, convirtiendo al cargar si hace falta.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SkyFormat {
+    Equirect,
+    Cubemap,
+}
+
+impl SkyFormat {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "equirect" => Some(SkyFormat::Equirect),
+            "cubemap" => Some(SkyFormat::Cubemap),
+            _ => None,
+        }
+    }
+}
+
+impl Sky {
+    /// El mismo cielo en `format`: las caras del cubemap miden un cuarto del
+    /// ancho de la imagen equirectangular, y al revés.
+    pub fn convert(self, format: SkyFormat) -> Sky {
+        match (self, format) {
+            (Sky::Equirect(texture), SkyFormat::Cubemap) => Sky::Cubemap(Rc::new(equirect_to_cubemap(&texture))),
+            (Sky::Cubemap(cubemap), SkyFormat::Equirect) => Sky::Equirect(Rc::new(cubemap_to_equirect(&cubemap))),
+            (sky, _) => sky,
+        }
+    }
+}
+
+/// Dibuja el cielo con los rayos de `rays`, que se rehacen solos si cambió la
+/// resolución o el FOV.
+pub fn draw_sky(renderer: &mut Renderer, camera: &Camera, sky: &Sky, rays: &mut SkyRays) {
+    match sky {
+        Sky::Equirect(texture) => draw_skybox(renderer, camera, texture, rays),
+        Sky::Cubemap(cubemap) => draw_cubemap(renderer, camera, cubemap, rays),
+    }
+}
+
+pub fn draw_skybox(renderer: &mut Renderer, camera: &Camera, skybox_texture: &Texture, rays: &mut SkyRays) {
+    let w = renderer.width;
+    let basis = RayBasis::new(camera);
+
+    rays.prepare(renderer.width, renderer.height, camera.fov_y);
+
+    for (i, &ray) in rays.camera_rays().iter().enumerate() {
+        let (u, v) = rays.equirect_uv(basis.to_world(ray));
+        let color = sample_texture(skybox_texture, u, v);
+
+        renderer.put_pixel((i % w) as i32, (i / w) as i32, color);
+    }
+}
+
+pub fn draw_cubemap(renderer: &mut Renderer, camera: &Camera, cubemap: &Cubemap, rays: &mut SkyRays) {
+    let w = renderer.width;
+    let basis = RayBasis::new(camera);
+
+    rays.prepare(renderer.width, renderer.height, camera.fov_y);
+
+    for (i, &ray) in rays.camera_rays().iter().enumerate() {
+        let color = cubemap.sample(basis.to_world(ray));
+        renderer.put_pixel((i % w) as i32, (i / w) as i32, color);
+    }
+}
+
+/// Cubemap con el contenido de una imagen equirectangular.
+pub fn equirect_to_cubemap(texture: &Texture) -> Cubemap {
+    Cubemap::from_fn((texture.width / 4).max(1), |dir| {
+        let (u, v) = equirect_uv(dir);
+        sample_texture(texture, u, v)
+    })
+}
+
+/// Imagen equirectangular (2:1) con el contenido de un cubemap.
+pub fn cubemap_to_equirect(cubemap: &Cubemap) -> Texture {
+    let width = cubemap.size * 4;
+    let height = cubemap.size * 2;
+    let mut pixels = Vec::with_capacity(width * height);
+
+    for y in 0..height {
+        for x in 0..width {
+            // Inversa de `equirect_uv` en el centro de cada texel de `sample_texture`
+            let theta = x as f32 / (width - 1) as f32 * 2.0 * PI - PI;
+            let phi = y as f32 / (height - 1) as f32 * PI - PI / 2.0;
+            let dir = Vec3::new(phi.cos() * theta.cos(), phi.sin(), phi.cos() * theta.sin());
+            pixels.push(cubemap.sample(dir));
+        }
+    }
+
+    Texture { width, height, pixels }
+}
+
+/// Coordenadas equirectangulares de una dirección (unitaria).
+fn equirect_uv(dir: Vec3) -> (f32, f32) {
+    let theta = dir.z.atan2(dir.x);
+    let phi = dir.y.asin();
+
+    let u = (theta + PI) / (2.0 * PI);
+    let v = (phi + PI / 2.0) / PI;
+    (u, v)
+}
+
+fn sample_texture(texture: &Texture, u: f32, v: f32) -> u32 {
+    let u = u.clamp(0.0, 1.0);
+    let v = v.clamp(0.0, 1.0);
+
+    let x = (u * (texture.width - 1) as f32) as usize;
+    let y = (v * (texture.height - 1) as f32) as usize;
+
+    texture.pixels[y * texture.width + x]
+}
@@ -0,0 +1,200 @@
+//! Rayos del cielo precalculados. La dirección de cada píxel en ejes de la cámara
+//! solo depende de la resolución y del FOV, así que se guarda en una tabla y se
+//! rehace solo cuando alguno cambia; por cuadro queda girarla con la base de la
+//! cámara. Las coordenadas esféricas salen de una tabla de arcotangente en vez de
+//! `atan2` y `asin`.
+
+use std::f32::consts::{FRAC_PI_2, PI};
+
+use crate::camera::Camera;
+use crate::math::Vec3;
+
+/// Muestras de la tabla de arcotangente en [0, 1]. Con interpolación lineal y el
+/// redondeo de f32 el error queda por debajo de 2e-7 rad, muy lejos de un texel
+/// del cielo.
+const ATAN_SAMPLES: usize = 1024;
+
+pub struct SkyRays {
+    width: usize,
+    height: usize,
+    fov_y: f32,
+    /// Dirección unitaria en ejes de la cámara de cada píxel, fila por fila.
+    rays: Vec<Vec3>,
+    /// atan(t) para t en [0, 1], con una muestra de más para interpolar en t = 1.
+    atan: Vec<f32>,
+}
+
+impl SkyRays {
+    pub fn new() -> Self {
+        Self {
+            width: 0,
+            height: 0,
+            fov_y: 0.0,
+            rays: Vec::new(),
+            atan: (0..=ATAN_SAMPLES + 1)
+                .map(|i| (i as f32 / ATAN_SAMPLES as f32).atan())
+                .collect(),
+        }
+    }
+
+    /// Deja la tabla lista para una pantalla de `width` x `height`; solo se
+    /// recalcula si cambió la resolución o el FOV.
+    pub fn prepare(&mut self, width: usize, height: usize, fov_y: f32) {
+        if width != self.width || height != self.height || fov_y != self.fov_y {
+            self.rebuild(width, height, fov_y);
+        }
+    }
+
+    /// Rayos de la cámara de la última `prepare`, fila por fila.
+    pub fn camera_rays(&self) -> &[Vec3] {
+        &self.rays
+    }
+
+    fn rebuild(&mut self, width: usize, height: usize, fov_y: f32) {
+        let aspect = width as f32 / height as f32;
+        let tan_half_fov = (fov_y * 0.5).tan();
+
+        self.rays.clear();
+        self.rays.reserve(width * height);
+        for y in 0..height {
+            let screen_y = 1.0 - (y as f32 / height as f32) * 2.0;
+            for x in 0..width {
+                let screen_x = (x as f32 / width as f32) * 2.0 - 1.0;
+                let ray = Vec3::new(screen_x * aspect * tan_half_fov, screen_y * tan_half_fov, -1.0);
+                self.rays.push(ray.normalized());
+            }
+        }
+
+        self.width = width;
+        self.height = height;
+        self.fov_y = fov_y;
+    }
+
+    /// Coordenadas equirectangulares de una dirección unitaria, igual que
+    /// `equirect_uv` pero con la tabla. La latitud sale de atan2(y, ρ) y no de
+    /// asin(y), que con tabla pierde precisión cerca de los polos.
+    pub fn equirect_uv(&self, dir: Vec3) -> (f32, f32) {
+        let theta = self.atan2(dir.z, dir.x);
+        let phi = self.atan2(dir.y, (dir.x * dir.x + dir.z * dir.z).sqrt());
+
+        let u = (theta + PI) / (2.0 * PI);
+        let v = (phi + FRAC_PI_2) / PI;
+        (u, v)
+    }
+
+    /// atan2 llevando el cociente a [0, 1] y acomodando el octante.
+    fn atan2(&self, y: f32, x: f32) -> f32 {
+        let (ax, ay) = (x.abs(), y.abs());
+        let (small, large) = if ay <= ax { (ay, ax) } else { (ax, ay) };
+        if large == 0.0 {
+            return 0.0;
+        }
+
+        let t = small / large * ATAN_SAMPLES as f32;
+        let i = t as usize;
+        let a = self.atan[i] + (self.atan[i + 1] - self.atan[i]) * (t - i as f32);
+
+        let a = if ay > ax { FRAC_PI_2 - a } else { a };
+        let a = if x < 0.0 { PI - a } else { a };
+        if y < 0.0 { -a } else { a }
+    }
+}
+
+/// Base de la cámara para girar los rayos de la tabla al mundo:
+/// `x * right + y * up - z * forward` (la cámara mira hacia −Z).
+pub struct RayBasis {
+    right: Vec3,
+    up: Vec3,
+    back: Vec3,
+}
+
+impl RayBasis {
+    pub fn new(camera: &Camera) -> Self {
+        let (right, up, forward) = camera.basis();
+        Self { right, up, back: -forward }
+    }
+
+    pub fn to_world(&self, ray: Vec3) -> Vec3 {
+        self.right * ray.x + self.up * ray.y + self.back * ray.z
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::Quat;
+
+    fn assert_close(a: f32, b: f32, eps: f32) {
+        assert!((a - b).abs() <= eps, "{} != {}", a, b);
+    }
+
+    /// Puntos en los ocho octantes, sobre los ejes y en las diagonales.
+    fn samples() -> Vec<(f32, f32)> {
+        let values = [-3.0, -1.0, -0.7, -0.2, -1e-4, 0.0, 1e-4, 0.2, 0.7, 1.0, 3.0];
+        values.iter().flat_map(|&y| values.iter().map(move |&x| (y, x))).collect()
+    }
+
+    #[test]
+    fn atan2_matches_std_in_every_octant() {
+        let rays = SkyRays::new();
+        for (y, x) in samples() {
+            assert_close(rays.atan2(y, x), y.atan2(x), 1e-6);
+        }
+    }
+
+    #[test]
+    fn atan_table_error_is_below_2e7() {
+        let rays = SkyRays::new();
+        let mut worst = 0.0f64;
+        for i in 0..=100_000 {
+            let t = i as f32 / 100_000.0;
+            let exact = (t as f64).atan();
+            worst = worst.max((rays.atan2(t, 1.0) as f64 - exact).abs());
+        }
+        assert!(worst < 2e-7, "error máximo {}", worst);
+    }
+
+    #[test]
+    fn equirect_uv_matches_asin_version() {
+        let rays = SkyRays::new();
+        let mut dirs: Vec<Vec3> = samples()
+            .into_iter()
+            .flat_map(|(y, x)| [-0.5, 0.0, 0.5].map(|z| Vec3::new(x, y, z)))
+            .filter(|d| d.length() > 0.0)
+            .map(Vec3::normalized)
+            .collect();
+        // Los polos y los ejes exactos
+        dirs.extend([Vec3::up(), -Vec3::up(), Vec3::new(1.0, 0.0, 0.0), Vec3::new(-1.0, 0.0, 0.0), Vec3::new(0.0, 0.0, -1.0)]);
+
+        for dir in dirs {
+            let (u, v) = rays.equirect_uv(dir);
+            let (eu, ev) = super::super::equirect_uv(dir);
+            // En la costura u = 0 y u = 1 son el mismo meridiano; en el polo
+            // exacto cualquier u es el mismo punto
+            if dir.x != 0.0 || dir.z != 0.0 {
+                let du = (u - eu).abs();
+                assert!(du.min(1.0 - du) < 1e-6, "{:?}: u {} != {}", dir, u, eu);
+            }
+            // Cerca de los polos el que pierde precisión es asin: en f32 no
+            // distingue latitudes a menos de ~3e-4 rad del polo
+            assert_close(v, ev, if dir.y.abs() > 0.9999 { 2e-4 } else { 1e-6 });
+        }
+    }
+
+    #[test]
+    fn ray_basis_matches_camera_rotation() {
+        let mut camera = Camera::new();
+        for orientation in [
+            Quat::from_axis_angle(Vec3::new(1.0, 0.0, 0.0), -0.3),
+            Quat::from_axis_angle(Vec3::new(0.3, 1.0, -0.5).normalized(), 2.1),
+            Quat::look_rotation(Vec3::up(), Vec3::new(0.0, 0.0, -1.0)),
+        ] {
+            camera.orientation = orientation;
+            let basis = RayBasis::new(&camera);
+            for ray in [Vec3::new(0.0, 0.0, -1.0), Vec3::new(0.4, -0.2, -0.9).normalized(), Vec3::new(-0.7, 0.5, -0.5).normalized()] {
+                let (a, b) = (basis.to_world(ray), camera.orientation.rotate(ray));
+                assert!((a - b).length() < 1e-5, "{:?} != {:?}", a, b);
+            }
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use crate::camera::Camera;
+use crate::math::Vec3;
+use crate::renderer::Renderer;
+
+use super::catalog::StarCatalog;
+
+/// Magnitud que se dibuja con brillo 1 (las más brillantes saturan y se agrandan).
+const REFERENCE_MAGNITUDE: f32 = 1.5;
+/// Brillo mínimo que vale la pena dibujar (≈ magnitud 7).
+const MIN_BRIGHTNESS: f32 = 0.08;
+/// Radio del sprite de una estrella de brillo 0 y cuánto crece con el brillo (píxeles).
+const BASE_RADIUS: f32 = 0.9;
+const RADIUS_GAIN: f32 = 1.1;
+const CONSTELLATION_COLOR: u32 = 0xFF24345C;
+
+/// Estrellas del catálogo como puntos con brillo por magnitud y color por B−V,
+/// sobre el fondo ya dibujado (mezcla aditiva). Como están en el infinito solo
+/// importa la orientación de la cámara, no su posición.
+pub fn draw_starfield(renderer: &mut Renderer, camera: &Camera, catalog: &StarCatalog, constellations: bool) {
+    let projector = SkyProjector::new(renderer, camera);
+
+    if constellations {
+        for &(a, b) in &catalog.lines {
+            let ends = projector.project(catalog.stars[a].direction).zip(projector.project(catalog.stars[b].direction));
+            // Un extremo casi al costado de la cámara se proyecta lejísimos: no vale la pena recorrerlo
+            if let Some((p0, p1)) = ends.filter(|(p0, p1)| projector.near_screen(*p0) && projector.near_screen(*p1)) {
+                renderer.draw_line(
+                    (p0.0 as i32, p0.1 as i32),
+                    (p1.0 as i32, p1.1 as i32),
+                    CONSTELLATION_COLOR,
+                );
+            }
+        }
+    }
+
+    for star in &catalog.stars {
+        // Cada 5 magnitudes el flujo baja 100 veces; con la raíz el ojo ve mejor las débiles
+        let brightness = 10f32.powf(-0.2 * (star.magnitude - REFERENCE_MAGNITUDE));
+        if brightness < MIN_BRIGHTNESS {
+            continue;
+        }
+        if let Some(center) = projector.project(star.direction) {
+            let radius = BASE_RADIUS + RADIUS_GAIN * brightness.sqrt();
+            draw_sprite(renderer, center, radius, star.color * brightness.min(1.2));
+        }
+    }
+}
+
+/// Mancha gaussiana centrada en `center` (coordenadas de pantalla con decimales).
+fn draw_sprite(renderer: &mut Renderer, center: (f32, f32), radius: f32, rgb: Vec3) {
+    let sigma2 = 2.0 * (radius * 0.5) * (radius * 0.5);
+    let extent = (radius * 1.5).ceil() as i32;
+    let (cx, cy) = (center.0.floor() as i32, center.1.floor() as i32);
+
+    for y in cy - extent..=cy + extent {
+        for x in cx - extent..=cx + extent {
+            let dx = x as f32 + 0.5 - center.0;
+            let dy = y as f32 + 0.5 - center.1;
+            let falloff = (-(dx * dx + dy * dy) / sigma2).exp();
+            if falloff > 0.01 {
+                renderer.add_pixel(x, y, rgb * falloff);
+            }
+        }
+    }
+}
+
+/// Proyección de direcciones (puntos en el infinito) a la pantalla.
+struct SkyProjector {
+    right: Vec3,
+    up: Vec3,
+    forward: Vec3,
+    scale_x: f32,
+    scale_y: f32,
+    width: f32,
+    height: f32,
+}
+
+impl SkyProjector {
+    fn new(renderer: &Renderer, camera: &Camera) -> Self {
+        let (right, up, forward) = camera.basis();
+        let tan_half_fov = (camera.fov_y * 0.5).tan();
+        Self {
+            right,
+            up,
+            forward,
+            scale_x: 1.0 / (tan_half_fov * renderer.aspect()),
+            scale_y: 1.0 / tan_half_fov,
+            width: renderer.width as f32,
+            height: renderer.height as f32,
+        }
+    }
+
+    fn near_screen(&self, (x, y): (f32, f32)) -> bool {
+        x.abs() < self.width * 4.0 && y.abs() < self.height * 4.0
+    }
+
+    /// Posición en pantalla, o `None` si la dirección queda detrás de la cámara.
+    fn project(&self, direction: Vec3) -> Option<(f32, f32)> {
+        let z = direction.dot(self.forward);
+        if z <= 1e-3 {
+            return None;
+        }
+        let ndc_x = direction.dot(self.right) / z * self.scale_x;
+        let ndc_y = direction.dot(self.up) / z * self.scale_y;
+        Some(((ndc_x + 1.0) * 0.5 * self.width, (1.0 - ndc_y) * 0.5 * self.height))
+    }
+}
@@ -0,0 +1,132 @@
+pub mod procgen;
+
+use std::fmt;
+
+pub use procgen::TerrainParams;
+
+pub struct Texture {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<u32>,
+}
+
+/// Error al cargar una textura desde disco.
+#[derive(Debug)]
+pub enum TextureError {
+    /// El archivo no existe, no se pudo leer o no se pudo decodificar.
+    Image(image::ImageError),
+    /// La imagen se decodificó pero no tiene píxeles.
+    Empty,
+    /// Ninguna raíz de búsqueda ni los assets embebidos tienen esa clave.
+    NotFound(String),
+}
+
+impl fmt::Display for TextureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TextureError::Image(e) => write!(f, "{}", e),
+            TextureError::Empty => write!(f, "la imagen no tiene píxeles"),
+            TextureError::NotFound(key) => write!(f, "no se encontró el asset {}", key),
+        }
+    }
+}
+
+impl std::error::Error for TextureError {}
+
+impl From<image::ImageError> for TextureError {
+    fn from(e: image::ImageError) -> Self {
+        TextureError::Image(e)
+    }
+}
+
+impl Texture {
+    pub fn from_file(path: &str) -> Result<Self, TextureError> {
+        let img = image::open(path)?;
+        Self::from_image(img)
+    }
+
+    /// Decodifica una imagen que ya está en memoria (p. ej. un asset embebido).
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, TextureError> {
+        let img = image::load_from_memory(bytes)?;
+        Self::from_image(img)
+    }
+
+    fn from_image(img: image::DynamicImage) -> Result<Self, TextureError> {
+        let rgba = img.to_rgba8();
+        let (w, h) = rgba.dimensions();
+
+        if w == 0 || h == 0 {
+            return Err(TextureError::Empty);
+        }
+
+        let mut pixels = Vec::with_capacity((w * h) as usize);
+
+        for p in rgba.pixels() {
+            let [r, g, b, a] = p.0;
+            let argb = ((a as u32) << 24)
+                | ((r as u32) << 16)
+                | ((g as u32) << 8)
+                | (b as u32);
+            pixels.push(argb);
+        }
+
+        Ok(Self {
+            width: w as usize,
+            height: h as usize,
+            pixels,
+        })
+    }
+
+    /// Muestreo por vecino más cercano: `u` se repite (longitud) y `v` se satura.
+    pub fn sample(&self, u: f32, v: f32) -> u32 {
+        let u = u - u.floor();
+        let v = v.clamp(0.0, 1.0);
+
+        let x = ((u * self.width as f32) as usize).min(self.width - 1);
+        let y = ((v * self.height as f32) as usize).min(self.height - 1);
+
+        self.pixels[y * self.width + x]
+    }
+
+    /// Textura de 1x1 de un solo color.
+    pub fn solid(color: u32) -> Self {
+        Self {
+            width: 1,
+            height: 1,
+            pixels: vec![color | 0xFF00_0000],
+        }
+    }
+
+    /// Tablero de ajedrez `cells` x `cells` alternando `a` y `b`.
+    pub fn checkerboard(size: usize, cells: usize, a: u32, b: u32) -> Self {
+        let size = size.max(1);
+        let cell = (size / cells.max(1)).max(1);
+        let a = a | 0xFF00_0000;
+        let b = b | 0xFF00_0000;
+
+        let mut pixels = Vec::with_capacity(size * size);
+        for y in 0..size {
+            for x in 0..size {
+                let even = ((x / cell) + (y / cell)).is_multiple_of(2);
+                pixels.push(if even { a } else { b });
+            }
+        }
+
+        Self {
+            width: size,
+            height: size,
+            pixels,
+        }
+    }
+
+    /// Textura de reemplazo para un cuerpo: tablero con su color y una versión más oscura,
+    /// para que un asset roto se note a simple vista.
+    pub fn fallback_for(color: u32) -> Self {
+        let r = ((color >> 16) & 0xFF) / 2;
+        let g = ((color >> 8) & 0xFF) / 2;
+        let b = (color & 0xFF) / 2;
+        let dark = (r << 16) | (g << 8) | b;
+
+        Self::checkerboard(64, 8, color, dark)
+    }
+}
@@ -0,0 +1,230 @@
+//! Texturas procedurales de planetas: relieve con ruido fractal sobre la
+//! esfera, océanos, biomas según temperatura y humedad, y casquetes polares.
+//! La misma semilla da siempre la misma textura.
+
+use std::f32::consts::PI;
+
+use super::Texture;
+use crate::math::{smoothstep, Noise3, Vec3};
+use crate::renderer::color;
+use crate::renderer::sphere::equirect_direction;
+
+/// Parámetros del generador. Todos los valores "de clima" están en [0, 1]
+/// salvo que se diga otra cosa.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TerrainParams {
+    pub seed: u64,
+    /// Tamaño de la textura; el alto es la mitad (equirectangular).
+    pub width: usize,
+    /// Fracción de la superficie bajo el agua (0 = sin océanos, 1 = todo océano).
+    pub sea_level: f32,
+    /// Latitud (0 = ecuador, 1 = polo) donde empiezan los casquetes; 1 = sin casquetes.
+    pub polar_caps: f32,
+    /// Desplaza el clima: negativo = planeta helado, positivo = desértico.
+    pub temperature: f32,
+    /// Desplaza la humedad: negativo = seco, positivo = selvático.
+    pub humidity: f32,
+    /// Tamaño de los continentes: más alto = continentes más chicos y numerosos.
+    pub continent_scale: f32,
+}
+
+impl TerrainParams {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            width: 512,
+            sea_level: 0.55,
+            polar_caps: 0.8,
+            temperature: 0.0,
+            humidity: 0.0,
+            continent_scale: 1.6,
+        }
+    }
+}
+
+/// Colores de los biomas: filas de frío a calor, columnas de seco a húmedo.
+/// Se interpola entre celdas, así los biomas se funden en vez de cortar.
+const BIOMES: [[u32; 3]; 4] = [
+    // Frío: tundra seca, tundra, taiga
+    [0xFF9A9484, 0xFF8A8F78, 0xFF4F6B4A],
+    // Templado: estepa, pradera, bosque
+    [0xFFB3A77A, 0xFF7FA157, 0xFF3F7A3A],
+    // Cálido: matorral, sabana, bosque húmedo
+    [0xFFC9A96B, 0xFF9DAA55, 0xFF2F6E2C],
+    // Tórrido: desierto, sabana seca, selva
+    [0xFFE0C68E, 0xFFB8A460, 0xFF1F5A22],
+];
+const DEEP_SEA: u32 = 0xFF0B2250;
+const SHALLOW_SEA: u32 = 0xFF2A6FA0;
+const BEACH: u32 = 0xFFD8CC9A;
+const ROCK: u32 = 0xFF7A716A;
+const ICE: u32 = 0xFFF2F6FA;
+
+/// Genera una textura equirectangular de `width` x `width / 2`. El ruido se
+/// evalúa sobre la esfera (no sobre el rectángulo), así los bordes izquierdo y
+/// derecho empalman sin costura y los polos no se estiran.
+pub fn planet(params: &TerrainParams) -> Texture {
+    let width = params.width.max(8);
+    let height = width / 2;
+    let relief = Noise3::new(params.seed);
+    let climate = Noise3::new(params.seed ^ 0xC11A_7E00);
+
+    let mut pixels = Vec::with_capacity(width * height);
+    for y in 0..height {
+        let v = (y as f32 + 0.5) / height as f32;
+        for x in 0..width {
+            let p = equirect_direction((x as f32 + 0.5) / width as f32, v);
+            pixels.push(surface_color(params, &relief, &climate, p));
+        }
+    }
+
+    Texture { width, height, pixels }
+}
+
+/// Altura en [0, 1] del punto `p` de la esfera: continentes grandes más
+/// detalle de montañas.
+fn elevation(params: &TerrainParams, relief: &Noise3, p: Vec3) -> f32 {
+    let continents = relief.fbm(p * params.continent_scale, 6);
+    let ridges = 1.0 - relief.sample(p * (params.continent_scale * 5.0) + Vec3::new(7.3, 1.9, 4.4)).abs();
+    (0.5 + 0.9 * continents + 0.08 * (ridges - 0.5)).clamp(0.0, 1.0)
+}
+
+/// Altura del nivel del mar. Las alturas se reparten más o menos como una
+/// logística centrada un poco arriba de 0.5 (el fbm se junta cerca del medio);
+/// invirtiéndola, `sea_level` da aproximadamente la fracción de océano, con
+/// 0 sin mar y 1 todo mar.
+fn sea_height(params: &TerrainParams) -> f32 {
+    const MEDIAN: f32 = 0.52;
+    const SPREAD: f32 = 0.085;
+    let fraction = params.sea_level.clamp(0.0, 1.0);
+    (MEDIAN + SPREAD * (fraction / (1.0 - fraction)).ln()).clamp(0.0, 1.01)
+}
+
+/// Latitud angular normalizada: 0 en el ecuador, 1 en el polo.
+fn latitude(p: Vec3) -> f32 {
+    p.y.abs().min(1.0).asin() / (PI / 2.0)
+}
+
+fn surface_color(params: &TerrainParams, relief: &Noise3, climate: &Noise3, p: Vec3) -> u32 {
+    let elevation = elevation(params, relief, p);
+    let sea = sea_height(params);
+    let latitude = latitude(p);
+    let wobble = climate.fbm(p * 4.0, 3);
+
+    // Casquetes: cubren tierra y mar, con borde irregular
+    let cap = smoothstep(params.polar_caps - 0.03, params.polar_caps + 0.03, latitude + wobble * 0.08);
+
+    let rgb = if elevation < sea {
+        // Océano: más oscuro cuanto más hondo
+        let depth = ((sea - elevation) / sea.max(1e-3)).min(1.0);
+        color::to_rgb(SHALLOW_SEA).lerp(color::to_rgb(DEEP_SEA), smoothstep(0.0, 0.8, depth))
+    } else {
+        let height = (elevation - sea) / (1.0 - sea).max(1e-3);
+        // Más frío hacia los polos y en altura
+        let temperature = (1.0 - latitude * 1.2 - height * 0.5 + wobble * 0.15 + params.temperature).clamp(0.0, 1.0);
+        let moisture = (0.5 + 0.9 * climate.fbm(p * 2.5 + Vec3::new(3.1, 8.2, 5.7), 4) + params.humidity).clamp(0.0, 1.0);
+
+        let mut land = biome(temperature, moisture);
+        land = color::to_rgb(BEACH).lerp(land, smoothstep(0.0, 0.03, height));
+        land = land.lerp(color::to_rgb(ROCK), smoothstep(0.45, 0.7, height));
+        // Nieve en las cumbres y en las tierras muy frías
+        let snow = smoothstep(0.75, 0.9, height).max(smoothstep(0.12, 0.04, temperature));
+        land.lerp(color::to_rgb(ICE), snow)
+    };
+
+    color::from_rgb(rgb.lerp(color::to_rgb(ICE), cap))
+}
+
+/// Color del bioma interpolando en la tabla `BIOMES`.
+fn biome(temperature: f32, moisture: f32) -> Vec3 {
+    let row = temperature * (BIOMES.len() - 1) as f32;
+    let col = moisture * (BIOMES[0].len() - 1) as f32;
+    let (r0, c0) = (row.floor() as usize, col.floor() as usize);
+    let (r1, c1) = ((r0 + 1).min(BIOMES.len() - 1), (c0 + 1).min(BIOMES[0].len() - 1));
+    let (tr, tc) = (row - r0 as f32, col - c0 as f32);
+
+    let at = |r: usize, c: usize| color::to_rgb(BIOMES[r][c]);
+    let top = at(r0, c0).lerp(at(r0, c1), tc);
+    let bottom = at(r1, c0).lerp(at(r1, c1), tc);
+    top.lerp(bottom, tr)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn small(seed: u64) -> TerrainParams {
+        TerrainParams {
+            width: 64,
+            ..TerrainParams::new(seed)
+        }
+    }
+
+    #[test]
+    fn same_seed_gives_same_planet() {
+        let a = planet(&small(42));
+        let b = planet(&small(42));
+        assert_eq!((a.width, a.height), (64, 32));
+        assert_eq!(a.pixels, b.pixels);
+    }
+
+    /// Fracción de la superficie (pesada por área) bajo el nivel del mar.
+    fn ocean_fraction(params: &TerrainParams) -> f32 {
+        let relief = Noise3::new(params.seed);
+        let sea = sea_height(params);
+        let (mut ocean, mut total) = (0.0, 0.0);
+        for y in 0..64 {
+            let v = (y as f32 + 0.5) / 64.0;
+            let area = (PI * v).sin();
+            for x in 0..128 {
+                let p = equirect_direction((x as f32 + 0.5) / 128.0, v);
+                if elevation(params, &relief, p) < sea {
+                    ocean += area;
+                }
+                total += area;
+            }
+        }
+        ocean / total
+    }
+
+    #[test]
+    fn ocean_fraction_follows_sea_level() {
+        let mut previous = 0.0;
+        for sea_level in [0.2, 0.5, 0.8] {
+            // Promedio de varias semillas: un planeta solo puede salir con un continente de más
+            let fraction = (1..=4)
+                .map(|seed| ocean_fraction(&TerrainParams { sea_level, ..TerrainParams::new(seed) }))
+                .sum::<f32>()
+                / 4.0;
+            assert!((fraction - sea_level).abs() < 0.1, "sea_level {}: {} de océano", sea_level, fraction);
+            assert!(fraction > previous);
+            previous = fraction;
+        }
+        // Los extremos son exactos: sin mar y todo mar
+        assert_eq!(ocean_fraction(&TerrainParams { sea_level: 0.0, ..TerrainParams::new(1) }), 0.0);
+        assert_eq!(ocean_fraction(&TerrainParams { sea_level: 1.0, ..TerrainParams::new(1) }), 1.0);
+    }
+
+    #[test]
+    fn polar_caps_cover_the_poles_above_their_latitude() {
+        // El borde del casquete se corre hasta 0.08 con el ruido y se funde en ±0.03
+        let edge = 0.08 + 0.03;
+        let ice = |params: &TerrainParams, band: &dyn Fn(f32) -> bool| {
+            let texture = planet(params);
+            let pixels: Vec<u32> = (0..texture.height)
+                .filter(|&y| band(latitude(equirect_direction(0.0, (y as f32 + 0.5) / texture.height as f32))))
+                .flat_map(|y| texture.pixels[y * texture.width..(y + 1) * texture.width].iter().copied())
+                .collect();
+            pixels.iter().filter(|&&c| c == ICE).count() as f32 / pixels.len() as f32
+        };
+
+        for polar_caps in [0.6, 0.8] {
+            let params = TerrainParams { polar_caps, ..small(3) };
+            assert_eq!(ice(&params, &|lat| lat > polar_caps + edge), 1.0, "casquete {}", polar_caps);
+            assert!(ice(&params, &|lat| lat < polar_caps - edge) < 0.1, "casquete {}", polar_caps);
+        }
+        // Sin casquetes (1) los polos no quedan tapados
+        let params = TerrainParams { polar_caps: 1.0, ..small(3) };
+        assert!(ice(&params, &|lat| lat > 0.8 + edge) < 1.0);
+    }
+}
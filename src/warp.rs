@@ -1,4 +1,3 @@
-use crate::camera::Camera;
 use crate::math::Vec3;
 
 pub struct WarpState {
@@ -28,14 +27,15 @@ impl WarpState {
         self.duration = duration;
     }
 
-    pub fn update(&mut self, dt: f32, camera: &mut Camera) {
+    /// Avanza la animación moviendo `position` (la cámara o la nave que se esté controlando).
+    pub fn update(&mut self, dt: f32, position: &mut Vec3) {
         if !self.active {
             return;
         }
 
         self.t += dt;
         let alpha = (self.t / self.duration).min(1.0);
-        camera.position = self.start.lerp(self.target, alpha);
+        *position = self.start.lerp(self.target, alpha);
 
         if alpha >= 1.0 {
             self.active = false;
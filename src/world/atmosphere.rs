@@ -0,0 +1,21 @@
+/// Capa de gas alrededor de un cuerpo: se ve como un halo en el borde y tiñe
+/// la zona del terminador.
+#[derive(Clone, Debug)]
+pub struct Atmosphere {
+    /// Color de la luz dispersada (azul en un planeta tipo Tierra).
+    pub color: u32,
+    /// Altura de la capa sobre la superficie, en unidades de mundo.
+    pub thickness: f32,
+    /// Cuánto opaca: 0 = invisible, 1 = tipo Tierra, más = muy densa.
+    pub density: f32,
+}
+
+impl Atmosphere {
+    pub fn new(color: u32) -> Self {
+        Self {
+            color,
+            thickness: 0.8,
+            density: 1.0,
+        }
+    }
+}
@@ -0,0 +1,128 @@
+use std::f32::consts::PI;
+
+use crate::camera::Camera;
+use crate::math::{Rng, Vec3};
+use crate::renderer::{color, Renderer};
+
+use super::orbit::OrbitalElements;
+
+/// Parámetros para generar un cinturón. Ángulos en radianes.
+#[derive(Clone, Debug)]
+pub struct BeltParams {
+    pub seed: u64,
+    pub count: usize,
+    pub inner_radius: f32,
+    pub outer_radius: f32,
+    /// Desvío de la inclinación de las órbitas respecto de la eclíptica.
+    pub inclination: f32,
+    /// Excentricidad máxima.
+    pub eccentricity: f32,
+    /// Velocidad angular en el borde interno; hacia afuera baja como a^-1.5 (3ª ley de Kepler).
+    pub orbit_speed: f32,
+    /// Radio de cada partícula (para dibujar y para las colisiones).
+    pub particle_size: f32,
+}
+
+/// Partícula liviana: solo sus elementos orbitales y lo necesario para dibujarla.
+#[derive(Clone, Copy, Debug)]
+pub struct BeltParticle {
+    pub elements: OrbitalElements,
+    /// Movimiento medio (rad/s).
+    pub mean_motion: f32,
+    pub size: f32,
+    /// Brillo relativo, para que no todas se vean iguales.
+    pub shade: f32,
+}
+
+/// Cinturón (o campo de anillos) con miles de partículas en órbitas keplerianas
+/// alrededor de un cuerpo. Las posiciones se recalculan una vez por frame y se
+/// comparten entre el dibujo y las colisiones.
+pub struct AsteroidBelt {
+    pub name: String,
+    pub parent: Option<usize>,
+    pub color: u32,
+    pub particles: Vec<BeltParticle>,
+    /// Posición de cada partícula relativa al padre, al tiempo del último `update`.
+    pub positions: Vec<Vec3>,
+    /// Distancia máxima al padre y altura máxima sobre la eclíptica (para descartar rápido).
+    max_radius: f32,
+    max_height: f32,
+}
+
+impl AsteroidBelt {
+    pub fn generate(name: String, parent: Option<usize>, color: u32, params: &BeltParams) -> Self {
+        let mut rng = Rng::new(params.seed);
+        let inner2 = params.inner_radius * params.inner_radius;
+        let outer2 = params.outer_radius * params.outer_radius;
+
+        let particles: Vec<BeltParticle> = (0..params.count)
+            .map(|_| {
+                // Densidad uniforme por área del anillo
+                let semi_major = (inner2 + (outer2 - inner2) * rng.next_f32()).sqrt();
+                let elements = OrbitalElements {
+                    semi_major,
+                    eccentricity: params.eccentricity * rng.next_f32(),
+                    inclination: (rng.gaussian() * params.inclination).abs(),
+                    ascending_node: rng.range(0.0, 2.0 * PI),
+                    arg_periapsis: rng.range(0.0, 2.0 * PI),
+                    mean_anomaly: rng.range(0.0, 2.0 * PI),
+                };
+                BeltParticle {
+                    elements,
+                    mean_motion: params.orbit_speed * (params.inner_radius / semi_major).powf(1.5),
+                    size: params.particle_size * rng.range(0.4, 1.0),
+                    shade: rng.range(0.55, 1.0),
+                }
+            })
+            .collect();
+
+        let max_radius = particles.iter().map(|p| p.elements.apoapsis()).fold(0.0, f32::max);
+        let max_height = particles
+            .iter()
+            .map(|p| p.elements.apoapsis() * p.elements.inclination.sin())
+            .fold(0.0, f32::max);
+
+        let mut belt = AsteroidBelt {
+            name,
+            parent,
+            color,
+            positions: vec![Vec3::zero(); particles.len()],
+            particles,
+            max_radius,
+            max_height,
+        };
+        belt.update(0.0);
+        belt
+    }
+
+    /// Ubica cada partícula en el instante `time` (absoluto, no incremental).
+    pub fn update(&mut self, time: f32) {
+        for (p, pos) in self.particles.iter().zip(&mut self.positions) {
+            *pos = p.elements.position_at(p.elements.mean_anomaly + p.mean_motion * time);
+        }
+    }
+
+    /// `true` si `local` (relativo al padre) puede estar a menos de `margin` de alguna partícula.
+    pub fn may_contain(&self, local: Vec3, margin: f32) -> bool {
+        let planar = (local.x * local.x + local.z * local.z).sqrt();
+        planar <= self.max_radius + margin && local.y.abs() <= self.max_height + margin
+    }
+
+    /// Dibuja las partículas como puntos con z-buffer; las cercanas se ven más grandes.
+    pub fn render(&self, renderer: &mut Renderer, camera: &Camera, center: Vec3) {
+        let view_proj = renderer.view_projection(camera);
+        let focal = renderer.focal_length(camera);
+        let base = color::to_rgb(self.color);
+
+        for (p, local) in self.particles.iter().zip(&self.positions) {
+            let Some((x, y, w)) = renderer.project_with(&view_proj, center + *local) else {
+                continue;
+            };
+            let size_px = (p.size * focal / w).clamp(1.0, 6.0) as i32;
+            // Las lejanas se apagan un poco para no saturar el fondo
+            let fade = (size_px as f32 / 2.0).clamp(0.5, 1.0);
+            let rgb = base * (p.shade * fade);
+            renderer.draw_point((x, y), w, size_px, color::from_rgb(rgb));
+        }
+    }
+}
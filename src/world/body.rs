@@ -1,10 +1,63 @@
-use crate::math::Vec3;
+use super::orbit::OrbitalElements;
+use super::{Atmosphere, CloudLayer, CometTail, Ring, SurfaceMaps};
+use crate::math::{Quat, Vec3};
+use crate::mesh::AsteroidParams;
+use crate::texture::TerrainParams;
 
 #[derive(Clone, Copy)]
 pub enum BodyKind {
     Star,
     Planet,
     Moon,
+    /// Cuerpo chico e irregular; se dibuja con una malla procedural.
+    Asteroid,
+    /// Núcleo chico en órbita muy excéntrica, con coma y colas cerca del perihelio.
+    Comet,
+}
+
+/// Los shaders procedurales de superficie, para elegirlos por nombre en la
+/// escena. El renderer arma cada uno con `renderer::shader::build`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ShaderKind {
+    Granulation,
+    GasGiant,
+    Rock,
+    Lava,
+    Ice,
+}
+
+impl ShaderKind {
+    pub const NAMES: [&'static str; 5] = ["granulation", "gas_giant", "rock", "lava", "ice"];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "granulation" => Some(Self::Granulation),
+            "gas_giant" => Some(Self::GasGiant),
+            "rock" => Some(Self::Rock),
+            "lava" => Some(Self::Lava),
+            "ice" => Some(Self::Ice),
+            _ => None,
+        }
+    }
+}
+
+/// Giro propio independiente de la órbita (períodos de rotación reales).
+#[derive(Clone, Copy, Debug)]
+pub struct Spin {
+    /// Velocidad angular en rad/s; negativa para rotación retrógrada.
+    pub speed: f32,
+    pub angle: f32,
+    /// Inclinación del eje respecto de +Y (radianes, alrededor de X), como en `Ring`.
+    pub tilt: f32,
+    /// Giro (radianes, alrededor de +Y) del eje de la inclinación; 0 = el eje X.
+    pub node: f32,
+}
+
+impl Spin {
+    /// Lleva +Y al eje de rotación.
+    pub fn pole(&self) -> Quat {
+        Quat::from_axis_angle(Vec3::up(), self.node) * Quat::from_axis_angle(Vec3::new(1.0, 0.0, 0.0), self.tilt)
+    }
 }
 
 pub struct Body {
@@ -15,16 +68,78 @@ pub struct Body {
     pub orbit_radius: f32,
     pub orbit_speed: f32,
     pub angle: f32,
-    pub parent: Option<usize>, 
+    pub parent: Option<usize>,
+    /// Órbita kepleriana (elíptica e inclinada). Si está, `angle` es la anomalía
+    /// media actual y `orbit_speed` el movimiento medio; si no, la órbita es un
+    /// círculo de `orbit_radius` sobre la eclíptica.
+    pub orbit: Option<OrbitalElements>,
+    /// Clave de la textura en el `AssetManager` (p. ej. `textures/earth.jpg`).
+    pub texture: Option<String>,
+    /// Shader procedural que reemplaza a la textura (si hay los dos, gana el shader).
+    pub shader: Option<ShaderKind>,
+    /// Textura generada con ruido en vez de leída de un archivo (gana sobre `texture`).
+    pub terrain: Option<TerrainParams>,
+    /// Forma irregular generada con semilla; `None` = esfera perfecta.
+    pub shape: Option<AsteroidParams>,
+    pub ring: Option<Ring>,
+    pub atmosphere: Option<Atmosphere>,
+    pub surface: Option<SurfaceMaps>,
+    pub clouds: Option<CloudLayer>,
+    pub tail: Option<CometTail>,
+    /// Sin giro propio, la textura gira con el ángulo de la órbita.
+    pub spin: Option<Spin>,
 }
 
 impl Body {
+    /// Cuerpo quieto en el origen, sin órbita ni extras; se completa campo por campo.
+    pub fn new(name: impl Into<String>, kind: BodyKind, radius: f32, color: u32) -> Self {
+        Self {
+            name: name.into(),
+            kind,
+            radius,
+            color,
+            orbit_radius: 0.0,
+            orbit_speed: 0.0,
+            angle: 0.0,
+            parent: None,
+            orbit: None,
+            texture: None,
+            shader: None,
+            terrain: None,
+            shape: None,
+            ring: None,
+            atmosphere: None,
+            surface: None,
+            clouds: None,
+            tail: None,
+            spin: None,
+        }
+    }
+
     pub fn update(&mut self, dt: f32) {
         match self.kind {
             BodyKind::Star => {}
-            BodyKind::Planet | BodyKind::Moon => {
+            BodyKind::Planet | BodyKind::Moon | BodyKind::Asteroid | BodyKind::Comet => {
                 self.angle += self.orbit_speed * dt;
             }
         }
+        if let Some(spin) = &mut self.spin {
+            spin.angle += spin.speed * dt;
+        }
+        if let Some(clouds) = &mut self.clouds {
+            clouds.update(dt);
+        }
+    }
+
+    /// Orientación del eje propio; sin `spin` el eje es +Y.
+    pub fn pole(&self) -> Quat {
+        self.spin.map_or(Quat::identity(), |spin| spin.pole())
+    }
+
+    /// Ejes del cuerpo en el mundo: el giro alrededor del eje propio y el eje
+    /// inclinado hacia su polo.
+    pub fn orientation(&self) -> Quat {
+        let angle = self.spin.map_or(self.angle, |spin| spin.angle);
+        self.pole() * Quat::from_axis_angle(Vec3::up(), angle)
     }
 }
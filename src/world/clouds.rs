@@ -0,0 +1,62 @@
+use crate::math::{Noise3, Vec3};
+use crate::renderer::sphere::equirect_direction;
+use crate::texture::Texture;
+
+/// Capa de nubes: una cáscara apenas más grande que el cuerpo que gira por su
+/// cuenta y le hace sombra a la superficie.
+#[derive(Clone, Debug)]
+pub struct CloudLayer {
+    /// Clave de la textura equirectangular (gris o alfa = cuánta nube hay).
+    pub texture: Option<String>,
+    /// Altura de la cáscara sobre la superficie, en unidades de mundo.
+    pub altitude: f32,
+    /// Velocidad de giro propia (rad/s), independiente de la del cuerpo.
+    pub rotation_speed: f32,
+    /// Ángulo de giro actual (radianes).
+    pub angle: f32,
+    /// Opacidad máxima de las nubes en [0, 1].
+    pub opacity: f32,
+}
+
+impl CloudLayer {
+    pub fn new() -> Self {
+        Self {
+            texture: None,
+            altitude: 0.15,
+            rotation_speed: 0.05,
+            angle: 0.0,
+            opacity: 0.85,
+        }
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        self.angle += self.rotation_speed * dt;
+    }
+
+    /// Nubes procedurales: ruido fractal sobre la esfera, así la textura no
+    /// tiene costura en la longitud 0. Es blanca y el alfa marca la nube.
+    pub fn default_texture(seed: u64) -> Texture {
+        const WIDTH: usize = 512;
+        const HEIGHT: usize = 256;
+        let noise = Noise3::new(seed);
+
+        let mut pixels = vec![0; WIDTH * HEIGHT];
+        for y in 0..HEIGHT {
+            let v = (y as f32 + 0.5) / HEIGHT as f32;
+            for x in 0..WIDTH {
+                let p = equirect_direction((x as f32 + 0.5) / WIDTH as f32, v);
+
+                // Bandas estiradas en longitud, como las que arma el viento
+                let stretched = Vec3::new(p.x * 2.0, p.y * 5.0, p.z * 2.0);
+                let density = (noise.fbm(stretched, 5) * 2.2 + 0.2).clamp(0.0, 1.0);
+                pixels[y * WIDTH + x] = (((density * 255.0) as u32) << 24) | 0x00FF_FFFF;
+            }
+        }
+
+        Texture {
+            width: WIDTH,
+            height: HEIGHT,
+            pixels,
+        }
+    }
+}
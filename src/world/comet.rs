@@ -0,0 +1,93 @@
+use crate::camera::Camera;
+use crate::math::{Rng, Vec3};
+use crate::renderer::{color, Renderer};
+
+use super::orbit::OrbitalElements;
+
+/// Puntos de brillo que forman cada cola.
+const ION_PUFFS: usize = 70;
+const DUST_PUFFS: usize = 90;
+
+/// Colas y coma de un cometa.
+#[derive(Clone, Debug)]
+pub struct CometTail {
+    /// Largo máximo de las colas (en el perihelio).
+    pub length: f32,
+    /// Radio máximo de la coma.
+    pub coma_radius: f32,
+    pub ion_color: u32,
+    pub dust_color: u32,
+}
+
+/// Dónde está el cometa en este frame y cuán activo está.
+#[derive(Clone, Copy, Debug)]
+pub struct CometState {
+    pub nucleus: Vec3,
+    /// Posición de la estrella más cercana.
+    pub star: Vec3,
+    pub velocity: Vec3,
+    /// 0 en el afelio, 1 en el perihelio (ver `activity`).
+    pub activity: f32,
+}
+
+impl CometTail {
+    pub fn new() -> Self {
+        Self {
+            length: 40.0,
+            coma_radius: 4.0,
+            ion_color: 0xFF7FB8FF,
+            dust_color: 0xFFFFE9C4,
+        }
+    }
+
+    /// Dibuja la coma y las dos colas con brillo aditivo. La cola de iones es
+    /// recta y apunta en contra de la estrella; la de polvo se curva hacia atrás
+    /// de la órbita. Ambas crecen con la actividad. `seed` fija la dispersión
+    /// de los puntos para que no titilen entre frames.
+    pub fn render(&self, renderer: &mut Renderer, camera: &Camera, state: &CometState, seed: u64) {
+        let CometState { nucleus, star, velocity, activity } = *state;
+        if activity <= 0.01 {
+            return;
+        }
+
+        let anti_sun = (nucleus - star).normalized();
+        let trailing = -velocity.normalized();
+        let length = self.length * activity;
+        let mut rng = Rng::new(seed);
+
+        // Coma
+        let coma = color::to_rgb(self.dust_color);
+        renderer.draw_glow(camera, nucleus, self.coma_radius * (0.4 + 0.6 * activity), coma * (0.9 * activity));
+
+        // Cola de iones: recta, angosta y azulada
+        let ion = color::to_rgb(self.ion_color);
+        for i in 0..ION_PUFFS {
+            let s = (i as f32 + rng.next_f32()) / ION_PUFFS as f32;
+            let jitter = rng.unit_vector() * (0.6 * s * self.coma_radius);
+            let position = nucleus + anti_sun * (s * length) + jitter;
+            let radius = self.coma_radius * (0.35 + 0.5 * s);
+            renderer.draw_glow(camera, position, radius, ion * (0.35 * activity * (1.0 - s)));
+        }
+
+        // Cola de polvo: se abre y se curva hacia atrás de la órbita
+        let dust = color::to_rgb(self.dust_color);
+        for i in 0..DUST_PUFFS {
+            let s = (i as f32 + rng.next_f32()) / DUST_PUFFS as f32;
+            let bend = trailing * (s * s * length * 0.6);
+            let jitter = rng.unit_vector() * (s * self.coma_radius * 1.5);
+            let position = nucleus + anti_sun * (s * length * 0.8) + bend + jitter;
+            let radius = self.coma_radius * (0.5 + 1.2 * s);
+            renderer.draw_glow(camera, position, radius, dust * (0.25 * activity * (1.0 - s)));
+        }
+    }
+}
+
+/// Actividad del cometa en [0, 1]: 0 en el afelio y 1 en el perihelio.
+pub fn activity(orbit: &OrbitalElements, distance: f32) -> f32 {
+    let (q, big_q) = (orbit.periapsis(), orbit.apoapsis());
+    if big_q <= q {
+        return 1.0;
+    }
+    let t = ((big_q - distance) / (big_q - q)).clamp(0.0, 1.0);
+    t * t
+}
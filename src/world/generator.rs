@@ -0,0 +1,419 @@
+//! Generador de sistemas con semilla: una estrella, planetas en órbitas
+//! espaciadas en escala logarítmica, lunas dentro de la esfera de Hill de su
+//! planeta, cinturones en los huecos grandes y anillos en los gigantes.
+//! La misma semilla da siempre el mismo sistema.
+//!
+//! Las escalas son las de la demo (planetas enormes al lado de la estrella),
+//! así que las masas son "de juguete": crecen con el radio al cubo y una
+//! densidad por tipo, elegidas para que las lunas tengan lugar donde orbitar.
+
+use std::f32::consts::PI;
+
+use crate::math::Rng;
+use crate::texture::TerrainParams;
+
+use super::orbit::OrbitalElements;
+use super::{Atmosphere, AsteroidBelt, BeltParams, Body, BodyKind, CloudLayer, Ring, ShaderKind, SolarSystem};
+
+/// GM de una estrella tipo Sol: una órbita de radio 70 tarda lo mismo que la
+/// Tierra de la demo (0.54 rad/s).
+const GM_SUN: f32 = 0.54 * 0.54 * 70.0 * 70.0 * 70.0;
+/// Masa (en soles) de un cuerpo rocoso de radio 8, el de la estrella de referencia.
+const ROCK_MASS: f32 = 0.5;
+/// Los gigantes son mucho menos densos que los rocosos.
+const GIANT_DENSITY: f32 = 0.25;
+/// Radio de la luna más lejana, como fracción de la esfera de Hill (más allá
+/// las órbitas progradas dejan de ser estables).
+const HILL_STABLE: f32 = 0.5;
+/// Distancia de la zona habitable para una estrella de luminosidad 1.
+const HABITABLE_DISTANCE: f32 = 70.0;
+/// La línea de hielo, en múltiplos de la zona habitable.
+const FROST_LINE: f32 = 2.5;
+
+/// Tipo espectral de la estrella.
+struct StarType {
+    color: u32,
+    radius: f32,
+    /// En masas solares.
+    mass: f32,
+    /// En luminosidades solares.
+    luminosity: f32,
+    /// Peso relativo al sortear (las enanas rojas son las más comunes).
+    weight: f32,
+}
+
+const STAR_TYPES: [StarType; 6] = [
+    StarType { color: 0xFFFF8F5A, radius: 5.0, mass: 0.4, luminosity: 0.1, weight: 30.0 }, // M
+    StarType { color: 0xFFFFB870, radius: 6.5, mass: 0.75, luminosity: 0.4, weight: 25.0 }, // K
+    StarType { color: 0xFFFFD27F, radius: 8.0, mass: 1.0, luminosity: 1.0, weight: 20.0 }, // G
+    StarType { color: 0xFFFFF1D6, radius: 9.5, mass: 1.3, luminosity: 2.5, weight: 13.0 }, // F
+    StarType { color: 0xFFDCE6FF, radius: 11.0, mass: 2.0, luminosity: 10.0, weight: 8.0 }, // A
+    StarType { color: 0xFFA8C0FF, radius: 13.0, mass: 4.0, luminosity: 40.0, weight: 4.0 }, // B
+];
+
+const SYLLABLES: [&str; 16] = [
+    "ka", "lo", "ri", "te", "va", "nu", "si", "mar", "dra", "ex", "zel", "or", "phi", "tan", "qu", "bel",
+];
+const ROMAN: [&str; 6] = ["I", "II", "III", "IV", "V", "VI"];
+
+const ROCKY_COLORS: [u32; 5] = [0xFF9A8F80, 0xFFB08A6A, 0xFF8C8C8C, 0xFFA67C5B, 0xFF7D8A96];
+const GAS_COLORS: [u32; 4] = [0xFFD9A46A, 0xFFC9B38A, 0xFFE0C9A0, 0xFFB88A5A];
+const ICE_GIANT_COLORS: [u32; 3] = [0xFF8FC8E0, 0xFF6FA0E0, 0xFF9FD8D0];
+
+impl SolarSystem {
+    /// Sistema nuevo a partir de una semilla.
+    pub fn generate(seed: u64) -> Self {
+        let mut rng = Rng::new(seed);
+        let star_type = pick_star(&mut rng);
+        let star_name = random_name(&mut rng);
+
+        let mut star = Body::new(star_name.clone(), BodyKind::Star, star_type.radius * rng.range(0.9, 1.1), star_type.color);
+        star.shader = Some(ShaderKind::Granulation);
+        let mut bodies = vec![star];
+        let mut belts = Vec::new();
+
+        let habitable = HABITABLE_DISTANCE * star_type.luminosity.sqrt();
+        let frost = habitable * FROST_LINE;
+        let gm = GM_SUN * star_type.mass;
+
+        // Órbitas en progresión geométrica con algo de ruido (tipo Titius-Bode)
+        let count = 3 + (rng.next_u32() % 6) as usize;
+        let mut orbits = Vec::with_capacity(count);
+        let mut a = (bodies[0].radius * 3.5).max(habitable * 0.35) * rng.range(1.0, 1.3);
+        for _ in 0..count {
+            orbits.push(a);
+            a *= rng.range(1.45, 1.9);
+        }
+
+        for (i, &a) in orbits.iter().enumerate() {
+            let name = format!("{} {}", star_name, (b'b' + i as u8) as char);
+            let mut planet = make_planet(&mut rng, name, a, habitable, frost);
+
+            let elements = OrbitalElements {
+                semi_major: a,
+                eccentricity: rng.range(0.0, 0.06),
+                inclination: rng.range(0.0, 2.5).to_radians(),
+                ascending_node: rng.range(0.0, 2.0 * PI),
+                arg_periapsis: rng.range(0.0, 2.0 * PI),
+                mean_anomaly: rng.range(0.0, 2.0 * PI),
+            };
+            planet.parent = Some(0);
+            planet.orbit_radius = a;
+            planet.orbit_speed = (gm / (a * a * a)).sqrt();
+            planet.angle = elements.mean_anomaly;
+            planet.orbit = Some(elements);
+
+            let index = bodies.len();
+            let moons = make_moons(&mut rng, &planet, index, star_type.mass, frost);
+            bodies.push(planet);
+            bodies.extend(moons);
+        }
+
+        // Cinturón en el hueco más grande entre dos planetas vecinos
+        let widest = orbits
+            .windows(2)
+            .map(|w| (w[0], w[1]))
+            .max_by(|x, y| (x.1 / x.0).partial_cmp(&(y.1 / y.0)).unwrap());
+        if let Some((inner, outer)) = widest.filter(|(i, o)| o / i > 1.7) {
+            if rng.next_f32() < 0.75 {
+                let (from, to) = (inner * 1.2, outer / 1.2);
+                belts.push(make_belt(&mut rng, format!("Cinturón de {}", star_name), from, to, gm, 0xFFA89C8A));
+            }
+        }
+        // Cinturón helado más allá del último planeta
+        if let Some(&last) = orbits.last() {
+            if rng.next_f32() < 0.4 {
+                let from = last * 1.3;
+                belts.push(make_belt(&mut rng, format!("Cinturón exterior de {}", star_name), from, from * 1.3, gm, 0xFF9FB0C0));
+            }
+        }
+
+        let mut system = SolarSystem::from_bodies(bodies);
+        system.belts = belts;
+        system
+    }
+}
+
+fn pick_star(rng: &mut Rng) -> &'static StarType {
+    let total: f32 = STAR_TYPES.iter().map(|s| s.weight).sum();
+    let mut roll = rng.next_f32() * total;
+    for star in &STAR_TYPES {
+        if roll < star.weight {
+            return star;
+        }
+        roll -= star.weight;
+    }
+    &STAR_TYPES[STAR_TYPES.len() - 1]
+}
+
+fn random_name(rng: &mut Rng) -> String {
+    let syllables = 2 + (rng.next_u32() % 2) as usize;
+    let mut name: String = (0..syllables)
+        .map(|_| SYLLABLES[rng.next_u32() as usize % SYLLABLES.len()])
+        .collect();
+    if let Some(first) = name.get(0..1) {
+        name = first.to_uppercase() + &name[1..];
+    }
+    name
+}
+
+fn pick<T: Copy>(rng: &mut Rng, options: &[T]) -> T {
+    options[rng.next_u32() as usize % options.len()]
+}
+
+/// Masa de juguete (en soles) según radio y tipo.
+fn mass_of(body: &Body) -> f32 {
+    let density = if body.shader == Some(ShaderKind::GasGiant) { GIANT_DENSITY } else { 1.0 };
+    ROCK_MASS * density * (body.radius / 8.0).powi(3)
+}
+
+/// Hasta dónde puede llegar una luna (órbita más radio) sin que la estrella se
+/// la lleve: una fracción de la esfera de Hill en el periastro del planeta.
+fn stable_limit(orbit: &OrbitalElements, mass: f32, star_mass: f32) -> f32 {
+    orbit.periapsis() * (mass / (3.0 * star_mass)).cbrt() * HILL_STABLE
+}
+
+/// Planeta sin órbita todavía: el tipo depende de la distancia a la estrella.
+/// Adentro de la línea de hielo son rocosos (de lava, templados o fríos);
+/// afuera, gigantes gaseosos o de hielo.
+fn make_planet(rng: &mut Rng, name: String, a: f32, habitable: f32, frost: f32) -> Body {
+    let warmth = a / habitable;
+
+    if a > frost && rng.next_f32() < 0.75 {
+        let ice_giant = rng.next_f32() < 0.35;
+        let (radius, color) = if ice_giant {
+            (rng.range(6.0, 9.0), pick(rng, &ICE_GIANT_COLORS))
+        } else {
+            (rng.range(8.0, 14.0), pick(rng, &GAS_COLORS))
+        };
+        let mut planet = Body::new(name, BodyKind::Planet, radius, color);
+        planet.shader = Some(ShaderKind::GasGiant);
+        if rng.next_f32() < if ice_giant { 0.25 } else { 0.45 } {
+            planet.ring = Some(Ring {
+                inner_radius: radius * rng.range(1.25, 1.45),
+                outer_radius: radius * rng.range(1.9, 2.4),
+                tilt: rng.range(0.0, 30.0).to_radians(),
+                node: rng.range(0.0, 2.0 * PI),
+                color: pick(rng, &[0xFFD9C7A3, 0xFFC8C0B0, 0xFFB0A89A]),
+                texture: None,
+            });
+        }
+        return planet;
+    }
+
+    let radius = rng.range(2.2, 6.0);
+    let mut planet = Body::new(name, BodyKind::Planet, radius, pick(rng, &ROCKY_COLORS));
+    if warmth < 0.55 {
+        // Demasiado cerca: roca desnuda o lava
+        planet.shader = Some(if rng.next_f32() < 0.5 { ShaderKind::Lava } else { ShaderKind::Rock });
+    } else if a > frost {
+        planet.shader = Some(ShaderKind::Ice);
+        planet.color = 0xFFCFE4F2;
+    } else {
+        // Mundos con clima: textura generada según qué tan lejos de la zona habitable están
+        let mut terrain = TerrainParams::new(rng.next_u64());
+        terrain.temperature = ((1.0 - warmth) * 0.6).clamp(-0.5, 0.45);
+        terrain.sea_level = if warmth < 0.8 { rng.range(0.1, 0.4) } else { rng.range(0.35, 0.75) };
+        terrain.humidity = rng.range(-0.25, 0.25);
+        terrain.polar_caps = (0.85 - (warmth - 1.0).max(0.0) * 0.25).clamp(0.35, 1.0);
+        planet.terrain = Some(terrain);
+
+        if rng.next_f32() < 0.8 {
+            planet.atmosphere = Some(Atmosphere {
+                thickness: radius * 0.14,
+                density: rng.range(0.6, 1.8),
+                ..Atmosphere::new(pick(rng, &[0xFF6FA8FF, 0xFF8FB8FF, 0xFFF2D29A]))
+            });
+            if rng.next_f32() < 0.6 {
+                planet.clouds = Some(CloudLayer {
+                    altitude: radius * 0.03,
+                    rotation_speed: rng.range(0.02, 0.1),
+                    opacity: rng.range(0.5, 0.9),
+                    ..CloudLayer::new()
+                });
+            }
+        }
+    }
+    planet
+}
+
+/// Lunas en órbitas circulares entre el borde del planeta (o su anillo) y la
+/// parte estable de su esfera de Hill: r_H = a·(1 − e)·∛(m / 3M).
+fn make_moons(rng: &mut Rng, planet: &Body, index: usize, star_mass: f32, frost: f32) -> Vec<Body> {
+    let Some(orbit) = planet.orbit else {
+        return Vec::new();
+    };
+    let mass = mass_of(planet);
+    let farthest = stable_limit(&orbit, mass, star_mass);
+    let closest = planet.ring.as_ref().map_or(planet.radius * 2.0, |r| r.outer_radius * 1.2);
+
+    let giant = planet.shader == Some(ShaderKind::GasGiant);
+    let wanted = if giant { 1 + rng.next_u32() % 5 } else { rng.next_u32() % 3 } as usize;
+    let gm = GM_SUN * mass;
+
+    let mut moons = Vec::new();
+    let mut distance = closest * rng.range(1.0, 1.3);
+    while moons.len() < wanted.min(ROMAN.len()) {
+        let radius = rng.range(0.35, (planet.radius * 0.3).clamp(0.4, 2.6));
+        if distance + radius > farthest {
+            break;
+        }
+
+        let name = format!("{} {}", planet.name, ROMAN[moons.len()]);
+        let shader = if orbit.semi_major > frost {
+            pick(rng, &[ShaderKind::Ice, ShaderKind::Ice, ShaderKind::Rock, ShaderKind::Lava])
+        } else {
+            pick(rng, &[ShaderKind::Rock, ShaderKind::Rock, ShaderKind::Lava])
+        };
+        let color = match shader {
+            ShaderKind::Ice => 0xFFCFE4F2,
+            ShaderKind::Lava => 0xFF6B4A3A,
+            _ => pick(rng, &ROCKY_COLORS),
+        };
+
+        let mut moon = Body::new(name, BodyKind::Moon, radius, color);
+        moon.shader = Some(shader);
+        moon.parent = Some(index);
+        moon.orbit_radius = distance;
+        moon.orbit_speed = (gm / (distance * distance * distance)).sqrt();
+        moon.angle = rng.range(0.0, 2.0 * PI);
+        moons.push(moon);
+
+        distance = (distance + radius) * rng.range(1.35, 1.7);
+    }
+    moons
+}
+
+fn make_belt(rng: &mut Rng, name: String, inner: f32, outer: f32, gm: f32, color: u32) -> AsteroidBelt {
+    let params = BeltParams {
+        seed: rng.next_u64(),
+        count: 2500,
+        inner_radius: inner,
+        outer_radius: outer,
+        inclination: 3.0f32.to_radians(),
+        eccentricity: 0.08,
+        orbit_speed: (gm / (inner * inner * inner)).sqrt(),
+        particle_size: 0.35,
+    };
+    AsteroidBelt::generate(name, Some(0), color, &params)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Lo que define a cada cuerpo generado. `{:?}` de un f32 alcanza para
+    /// recuperar el valor exacto, así que comparar textos es comparar bits.
+    fn fingerprint(system: &SolarSystem) -> Vec<String> {
+        system
+            .bodies
+            .iter()
+            .map(|b| {
+                let numbers = [b.radius, b.orbit_radius, b.orbit_speed, b.angle];
+                format!("{} {:08X} {:?} {:?} {:?} {:?}", b.name, b.color, b.parent, numbers, b.orbit, b.shader)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn same_seed_gives_same_system() {
+        let a = SolarSystem::generate(2024);
+        let b = SolarSystem::generate(2024);
+        assert!(a.bodies.len() > 1);
+        assert_eq!(fingerprint(&a), fingerprint(&b));
+        assert_eq!(a.belts.len(), b.belts.len());
+    }
+
+    /// Zona habitable y línea de hielo del sistema, según el tipo de su estrella.
+    fn climate_lines(system: &SolarSystem) -> (f32, f32) {
+        let star = STAR_TYPES.iter().find(|s| s.color == system.bodies[0].color).unwrap();
+        let habitable = HABITABLE_DISTANCE * star.luminosity.sqrt();
+        (habitable, habitable * FROST_LINE)
+    }
+
+    #[test]
+    fn planet_types_follow_the_distance_to_the_star() {
+        let (mut giants, mut temperate) = (0, 0);
+        for seed in 0..200 {
+            let system = SolarSystem::generate(seed);
+            let (habitable, frost) = climate_lines(&system);
+            for planet in system.bodies.iter().filter(|b| matches!(b.kind, BodyKind::Planet)) {
+                let a = planet.orbit_radius;
+                if planet.shader == Some(ShaderKind::GasGiant) {
+                    assert!(a > frost, "semilla {}: {} es gigante adentro de la línea de hielo", seed, planet.name);
+                    giants += 1;
+                }
+                if planet.terrain.is_some() {
+                    assert!(a >= habitable * 0.55 && a <= frost, "semilla {}: {} tiene clima a {}", seed, planet.name, a);
+                    temperate += 1;
+                }
+                if a < habitable * 0.55 {
+                    assert!(matches!(planet.shader, Some(ShaderKind::Lava | ShaderKind::Rock)));
+                }
+            }
+        }
+        assert!(giants > 0 && temperate > 0);
+    }
+
+    #[test]
+    fn orbits_are_spaced_and_belts_fill_empty_gaps() {
+        let mut belts = 0;
+        for seed in 0..200 {
+            let system = SolarSystem::generate(seed);
+            let orbits: Vec<f32> = system
+                .bodies
+                .iter()
+                .filter(|b| matches!(b.kind, BodyKind::Planet))
+                .map(|b| b.orbit_radius)
+                .collect();
+            assert!((3..=8).contains(&orbits.len()));
+            for pair in orbits.windows(2) {
+                assert!(pair[1] / pair[0] >= 1.45 * (1.0 - 1e-5), "semilla {}: órbitas {:?}", seed, pair);
+            }
+
+            // Ningún planeta orbita dentro de un cinturón
+            for belt in &system.belts {
+                let semi_majors = belt.particles.iter().map(|p| p.elements.semi_major);
+                let inner = semi_majors.clone().fold(f32::MAX, f32::min);
+                let outer = semi_majors.fold(0.0, f32::max);
+                assert!(
+                    orbits.iter().all(|&a| a < inner || a > outer),
+                    "semilla {}: {} entre {} y {} con planetas en {:?}",
+                    seed,
+                    belt.name,
+                    inner,
+                    outer,
+                    orbits
+                );
+                belts += 1;
+            }
+        }
+        assert!(belts > 0);
+    }
+
+    #[test]
+    fn moons_stay_inside_the_stable_part_of_the_hill_sphere() {
+        let mut checked = 0;
+        for seed in 0..200 {
+            let system = SolarSystem::generate(seed);
+            for moon in system.bodies.iter().filter(|b| matches!(b.kind, BodyKind::Moon)) {
+                let planet = &system.bodies[moon.parent.unwrap()];
+                let orbit = planet.orbit.unwrap();
+                // La masa de la estrella sale de la velocidad del planeta: ω² a³ = GM
+                let a = planet.orbit_radius;
+                let star_mass = planet.orbit_speed * planet.orbit_speed * a * a * a / GM_SUN;
+                let limit = stable_limit(&orbit, mass_of(planet), star_mass);
+                assert!(
+                    moon.orbit_radius + moon.radius <= limit * (1.0 + 1e-4),
+                    "semilla {}: {} llega a {} y el límite es {}",
+                    seed,
+                    moon.name,
+                    moon.orbit_radius + moon.radius,
+                    limit
+                );
+                checked += 1;
+            }
+        }
+        assert!(checked > 0);
+    }
+}
@@ -0,0 +1,37 @@
+use crate::math::{Mat4, Quat, Vec3};
+
+/// Malla (OBJ) ubicada en la escena: estaciones, naves, asteroides irregulares.
+/// Orbita a su cuerpo padre igual que un `Body` y además gira sobre su eje Y.
+pub struct MeshInstance {
+    pub name: String,
+    /// Clave del OBJ en el `AssetManager` (p. ej. `models/station.obj`).
+    pub model: String,
+    pub parent: Option<usize>,
+    pub orbit_radius: f32,
+    pub orbit_speed: f32,
+    pub angle: f32,
+    pub scale: f32,
+    /// Inclinación del eje de giro (radianes, alrededor de X).
+    pub tilt: f32,
+    pub spin_speed: f32,
+    pub spin: f32,
+}
+
+impl MeshInstance {
+    pub fn update(&mut self, dt: f32) {
+        self.angle += self.orbit_speed * dt;
+        self.spin += self.spin_speed * dt;
+    }
+
+    pub fn orientation(&self) -> Quat {
+        Quat::from_axis_angle(Vec3::new(1.0, 0.0, 0.0), self.tilt)
+            * Quat::from_axis_angle(Vec3::up(), self.spin)
+    }
+
+    /// Matriz modelo -> mundo para una instancia ubicada en `position`.
+    pub fn model_matrix(&self, position: Vec3) -> Mat4 {
+        Mat4::translation(position)
+            * self.orientation().to_mat4()
+            * Mat4::scale(Vec3::new(self.scale, self.scale, self.scale))
+    }
+}
@@ -1,5 +1,23 @@
+pub mod atmosphere;
+pub mod belt;
 pub mod body;
+pub mod clouds;
+pub mod comet;
+pub mod generator;
+pub mod instance;
+pub mod orbit;
+pub mod ring;
+pub mod scene;
+pub mod solar;
+pub mod surface;
 pub mod system;
 
-pub use body::{Body, BodyKind};
+pub use atmosphere::Atmosphere;
+pub use belt::{AsteroidBelt, BeltParams};
+pub use body::{Body, BodyKind, ShaderKind, Spin};
+pub use clouds::CloudLayer;
+pub use comet::CometTail;
+pub use instance::MeshInstance;
+pub use ring::Ring;
+pub use surface::SurfaceMaps;
 pub use system::SolarSystem;
@@ -0,0 +1,162 @@
+//! Órbitas keplerianas. Los elementos se dan respecto de la eclíptica, que en
+//! el mundo es el plano XZ con +Y hacia el "norte" (igual que las órbitas
+//! circulares de `Body`: ángulo 0 en +X y avanzando hacia +Z).
+
+use std::f32::consts::PI;
+
+use crate::math::Vec3;
+
+/// Elementos orbitales clásicos. Ángulos en radianes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OrbitalElements {
+    pub semi_major: f32,
+    pub eccentricity: f32,
+    pub inclination: f32,
+    /// Longitud del nodo ascendente (Ω).
+    pub ascending_node: f32,
+    /// Argumento del periastro (ω).
+    pub arg_periapsis: f32,
+    /// Anomalía media en t = 0.
+    pub mean_anomaly: f32,
+}
+
+impl OrbitalElements {
+    /// Pasa elementos reales (eclíptica J2000 vista desde el norte, con las
+    /// órbitas directas en sentido antihorario) a los del mundo. Los ejes del
+    /// mundo son un espejo de la eclíptica (las órbitas avanzan de +X hacia +Z),
+    /// así que para que el cielo no quede invertido se usa la órbita reflejada:
+    /// mismos a, e, ω y anomalía, con Ω → −Ω e i → π − i.
+    pub fn from_ecliptic(ecliptic: Self) -> Self {
+        Self {
+            inclination: PI - ecliptic.inclination,
+            ascending_node: (-ecliptic.ascending_node).rem_euclid(2.0 * PI),
+            ..ecliptic
+        }
+    }
+
+    /// Distancia más cercana al cuerpo central.
+    pub fn periapsis(&self) -> f32 {
+        self.semi_major * (1.0 - self.eccentricity)
+    }
+
+    pub fn apoapsis(&self) -> f32 {
+        self.semi_major * (1.0 + self.eccentricity)
+    }
+
+    /// Posición relativa al cuerpo central para una anomalía media dada.
+    pub fn position_at(&self, mean_anomaly: f32) -> Vec3 {
+        let e = self.eccentricity;
+        let big_e = solve_kepler(mean_anomaly, e);
+
+        // Posición en el plano de la órbita (periastro sobre +x)
+        let px = self.semi_major * (big_e.cos() - e);
+        let py = self.semi_major * (1.0 - e * e).sqrt() * big_e.sin();
+
+        self.orbit_to_world(px, py)
+    }
+
+    /// Rota un punto del plano orbital (ω, i, Ω) y lo pasa a ejes del mundo.
+    fn orbit_to_world(&self, px: f32, py: f32) -> Vec3 {
+        let (sin_w, cos_w) = self.arg_periapsis.sin_cos();
+        let (sin_i, cos_i) = self.inclination.sin_cos();
+        let (sin_n, cos_n) = self.ascending_node.sin_cos();
+
+        let x1 = px * cos_w - py * sin_w;
+        let y1 = px * sin_w + py * cos_w;
+
+        // Coordenadas eclípticas (X, Y en el plano, Z al norte)
+        let x = x1 * cos_n - y1 * cos_i * sin_n;
+        let y = x1 * sin_n + y1 * cos_i * cos_n;
+        let z = y1 * sin_i;
+
+        Vec3::new(x, z, y)
+    }
+}
+
+/// Newton se corta cuando el paso es menor que esto (radianes, cerca del
+/// redondeo de un f32 en [−π, π]).
+const KEPLER_TOLERANCE: f32 = 1e-6;
+/// Tope de iteraciones. Arrancando desde M (o desde π si la órbita es muy
+/// excéntrica) Newton converge en pocas; el tope solo evita un ciclo infinito
+/// si el redondeo hace oscilar el último bit.
+const KEPLER_MAX_ITERATIONS: usize = 50;
+
+/// Resuelve la ecuación de Kepler `M = E - e·sin(E)` por Newton-Raphson y
+/// devuelve la anomalía excéntrica `E`, en [−π, π].
+pub fn solve_kepler(mean_anomaly: f32, eccentricity: f32) -> f32 {
+    let m = (mean_anomaly + PI).rem_euclid(2.0 * PI) - PI;
+    // Para órbitas muy excéntricas conviene arrancar desde π
+    let mut e_anom = if eccentricity > 0.8 { PI.copysign(m) } else { m };
+
+    for _ in 0..KEPLER_MAX_ITERATIONS {
+        let f = e_anom - eccentricity * e_anom.sin() - m;
+        let df = 1.0 - eccentricity * e_anom.cos();
+        let step = f / df;
+        e_anom -= step;
+        if step.abs() < KEPLER_TOLERANCE {
+            break;
+        }
+    }
+    e_anom
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn orbit(eccentricity: f32) -> OrbitalElements {
+        OrbitalElements {
+            semi_major: 10.0,
+            eccentricity,
+            inclination: 0.4,
+            ascending_node: 1.1,
+            arg_periapsis: 2.3,
+            mean_anomaly: 0.0,
+        }
+    }
+
+    /// Anomalías medias en toda la vuelta, incluidas las cercanas a 0 y a ±π.
+    fn anomalies() -> impl Iterator<Item = f32> {
+        (-200..=200).map(|i| i as f32 / 200.0 * PI).chain([1e-4, -1e-4, PI - 1e-4, 7.0, -20.0])
+    }
+
+    #[test]
+    fn circular_orbit_keeps_its_radius_and_the_mean_anomaly() {
+        let circle = orbit(0.0);
+        for m in anomalies() {
+            let wrapped = (m + PI).rem_euclid(2.0 * PI) - PI;
+            assert!((solve_kepler(m, 0.0) - wrapped).abs() < 1e-6, "M = {}", m);
+            assert!((circle.position_at(m).length() - 10.0).abs() < 1e-4, "M = {}", m);
+        }
+    }
+
+    #[test]
+    fn kepler_converges_for_very_eccentric_orbits() {
+        for e in [0.5, 0.9, 0.97, 0.99] {
+            for m in anomalies() {
+                let big_e = solve_kepler(m, e);
+                let wrapped = (m + PI).rem_euclid(2.0 * PI) - PI;
+                let residual = big_e - e * big_e.sin() - wrapped;
+                assert!(residual.abs() < 2e-6, "e = {}, M = {}: residuo {}", e, m, residual);
+            }
+        }
+    }
+
+    #[test]
+    fn distances_span_periapsis_to_apoapsis() {
+        for e in [0.0, 0.3, 0.97] {
+            let elements = orbit(e);
+            let (near, far) = (elements.periapsis(), elements.apoapsis());
+            assert!((near - 10.0 * (1.0 - e)).abs() < 1e-5);
+            assert!((far - 10.0 * (1.0 + e)).abs() < 1e-5);
+
+            // El periastro es M = 0 y el apoastro M = π
+            assert!((elements.position_at(0.0).length() - near).abs() < 1e-4, "e = {}", e);
+            assert!((elements.position_at(PI).length() - far).abs() < 1e-4, "e = {}", e);
+            for m in anomalies() {
+                let r = elements.position_at(m).length();
+                assert!(r >= near - 1e-4 && r <= far + 1e-4, "e = {}, M = {}: r = {}", e, m, r);
+            }
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use crate::math::{Noise3, Quat, Vec3};
+use crate::renderer::color;
+use crate::texture::Texture;
+
+/// Anillo plano alrededor de un cuerpo (estilo Saturno).
+#[derive(Clone, Debug)]
+pub struct Ring {
+    pub inner_radius: f32,
+    pub outer_radius: f32,
+    /// Inclinación del plano del anillo respecto del plano XZ (radianes, alrededor de X).
+    pub tilt: f32,
+    /// Giro (radianes, alrededor de +Y) del eje de la inclinación; 0 = el eje X.
+    pub node: f32,
+    /// Color base para la franja procedural cuando no hay textura.
+    pub color: u32,
+    /// Clave de la franja radial (color + alfa): x = 0 en el borde interno, x = 1 en el externo.
+    pub texture: Option<String>,
+}
+
+impl Ring {
+    pub fn orientation(&self) -> Quat {
+        Quat::from_axis_angle(Vec3::up(), self.node) * Quat::from_axis_angle(Vec3::new(1.0, 0.0, 0.0), self.tilt)
+    }
+
+    pub fn normal(&self) -> Vec3 {
+        self.orientation().rotate(Vec3::up())
+    }
+
+    /// Posición radial normalizada (0 = borde interno, 1 = externo) de un punto
+    /// a `distance` del centro, o `None` si cae fuera del anillo.
+    pub fn radial(&self, distance: f32) -> Option<f32> {
+        if distance < self.inner_radius || distance > self.outer_radius {
+            return None;
+        }
+        Some((distance - self.inner_radius) / (self.outer_radius - self.inner_radius))
+    }
+
+    /// Donde el rayo `origin + dir·t` (t > 0) cruza el plano del anillo centrado
+    /// en `center`, devuelve la posición radial normalizada si cae sobre el anillo.
+    pub fn intersect(&self, center: Vec3, origin: Vec3, dir: Vec3) -> Option<f32> {
+        let normal = self.normal();
+        let denom = dir.dot(normal);
+        if denom.abs() < 1e-6 {
+            return None;
+        }
+        let t = (center - origin).dot(normal) / denom;
+        if t <= 0.0 {
+            return None;
+        }
+        self.radial((origin + dir * t - center).length())
+    }
+
+    /// Franja procedural: bandas de ruido con una división oscura (tipo Cassini)
+    /// y bordes que se desvanecen.
+    pub fn default_strip(color: u32) -> Texture {
+        const WIDTH: usize = 256;
+        let noise = Noise3::new(color as u64);
+        let base = color::to_rgb(color);
+
+        let pixels = (0..WIDTH)
+            .map(|i| {
+                let x = i as f32 / (WIDTH - 1) as f32;
+                let bands = 0.5 + 0.5 * noise.fbm(Vec3::new(x * 12.0, 0.0, 0.0), 4);
+                let gap = if (0.62..0.68).contains(&x) { 0.1 } else { 1.0 };
+                let edges = (x / 0.08).min((1.0 - x) / 0.05).min(1.0);
+
+                let alpha = (0.25 + 0.7 * bands) * gap * edges;
+                let rgb = base * (0.7 + 0.45 * bands);
+                (color::from_rgb(rgb) & 0x00FF_FFFF) | (((alpha.clamp(0.0, 1.0) * 255.0) as u32) << 24)
+            })
+            .collect();
+
+        Texture {
+            width: WIDTH,
+            height: 1,
+            pixels,
+        }
+    }
+}
@@ -0,0 +1,503 @@
+use std::f32::consts::PI;
+use std::fmt;
+
+use super::orbit::OrbitalElements;
+use super::{
+    Atmosphere, AsteroidBelt, BeltParams, Body, BodyKind, CloudLayer, CometTail, MeshInstance, Ring, ShaderKind, SolarSystem, Spin,
+    SurfaceMaps,
+};
+use crate::math::rng::seed_from_str;
+use crate::mesh::AsteroidParams;
+use crate::texture::TerrainParams;
+
+/// Error de sintaxis o de contenido en un archivo de escena.
+#[derive(Debug)]
+pub struct SceneError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl SceneError {
+    fn new(line: usize, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for SceneError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "línea {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for SceneError {}
+
+/// Bloque `[nombre]` con sus pares `clave = valor`.
+pub struct Section {
+    pub name: String,
+    pub line: usize,
+    entries: Vec<(String, String, usize)>,
+}
+
+impl Section {
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|(k, _, _)| k == key)
+            .map(|(_, v, _)| v.as_str())
+    }
+
+    fn line_of(&self, key: &str) -> usize {
+        self.entries
+            .iter()
+            .find(|(k, _, _)| k == key)
+            .map(|(_, _, l)| *l)
+            .unwrap_or(self.line)
+    }
+
+    pub fn require(&self, key: &str) -> Result<&str, SceneError> {
+        self.get(key).ok_or_else(|| {
+            SceneError::new(self.line, format!("a [{}] le falta `{}`", self.name, key))
+        })
+    }
+
+    pub fn f32_or(&self, key: &str, default: f32) -> Result<f32, SceneError> {
+        match self.get(key) {
+            None => Ok(default),
+            Some(v) => v.parse().map_err(|_| {
+                SceneError::new(self.line_of(key), format!("`{}` no es un número: {}", key, v))
+            }),
+        }
+    }
+
+    pub fn u64_or(&self, key: &str, default: u64) -> Result<u64, SceneError> {
+        match self.get(key) {
+            None => Ok(default),
+            Some(v) => v.parse().map_err(|_| {
+                SceneError::new(self.line_of(key), format!("`{}` no es un entero: {}", key, v))
+            }),
+        }
+    }
+
+    /// Color ARGB en hex (`FF8DFF8D` o `0x8DFF8D`); sin alfa se asume opaco.
+    pub fn color_or(&self, key: &str, default: u32) -> Result<u32, SceneError> {
+        match self.get(key) {
+            None => Ok(default),
+            Some(v) => {
+                let hex = v.trim_start_matches("0x");
+                let value = u32::from_str_radix(hex, 16).map_err(|_| {
+                    SceneError::new(self.line_of(key), format!("`{}` no es un color hex: {}", key, v))
+                })?;
+                Ok(if hex.len() <= 6 { value | 0xFF00_0000 } else { value })
+            }
+        }
+    }
+
+    pub fn error(&self, key: &str, message: impl Into<String>) -> SceneError {
+        SceneError::new(self.line_of(key), message)
+    }
+}
+
+/// Archivo de escena ya separado en secciones, en el orden en que aparecen.
+pub struct Scene {
+    pub sections: Vec<Section>,
+}
+
+impl Scene {
+    /// Formato tipo INI: `[seccion]`, `clave = valor`, comentarios con `#`.
+    pub fn parse(src: &str) -> Result<Self, SceneError> {
+        let mut sections: Vec<Section> = Vec::new();
+
+        for (i, raw) in src.lines().enumerate() {
+            let line_no = i + 1;
+            let line = raw.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                sections.push(Section {
+                    name: name.trim().to_string(),
+                    line: line_no,
+                    entries: Vec::new(),
+                });
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| SceneError::new(line_no, format!("se esperaba `clave = valor`: {}", line)))?;
+
+            let section = sections
+                .last_mut()
+                .ok_or_else(|| SceneError::new(line_no, "valor fuera de una [sección]"))?;
+
+            section
+                .entries
+                .push((key.trim().to_string(), value.trim().to_string(), line_no));
+        }
+
+        Ok(Self { sections })
+    }
+}
+
+impl SolarSystem {
+    /// Construye un sistema a partir del texto de un archivo `.scene`.
+    pub fn from_scene(src: &str) -> Result<Self, SceneError> {
+        let scene = Scene::parse(src)?;
+        let mut bodies: Vec<Body> = Vec::new();
+        let mut meshes: Vec<MeshInstance> = Vec::new();
+        let mut belts: Vec<AsteroidBelt> = Vec::new();
+
+        for section in &scene.sections {
+            match section.name.as_str() {
+                "body" => {
+                    let body = parse_body(section, &bodies)?;
+                    bodies.push(body);
+                }
+                "mesh" => {
+                    let mesh = parse_mesh(section, &bodies)?;
+                    meshes.push(mesh);
+                }
+                "belt" => {
+                    let belt = parse_belt(section, &bodies)?;
+                    belts.push(belt);
+                }
+                other => {
+                    return Err(SceneError::new(section.line, format!("sección desconocida [{}]", other)));
+                }
+            }
+        }
+
+        if bodies.is_empty() {
+            return Err(SceneError::new(1, "la escena no define ningún [body]"));
+        }
+
+        let mut system = SolarSystem::from_bodies(bodies);
+        system.meshes = meshes;
+        system.belts = belts;
+        Ok(system)
+    }
+}
+
+fn parse_body(section: &Section, previous: &[Body]) -> Result<Body, SceneError> {
+    let name = section.require("name")?.to_string();
+
+    let kind = match section.get("kind").unwrap_or("planet") {
+        "star" => BodyKind::Star,
+        "planet" => BodyKind::Planet,
+        "moon" => BodyKind::Moon,
+        "asteroid" => BodyKind::Asteroid,
+        "comet" => BodyKind::Comet,
+        other => return Err(section.error("kind", format!("tipo de cuerpo desconocido: {}", other))),
+    };
+
+    let parent = parse_parent(section, previous, &name)?;
+    let shape = parse_shape(section, kind, &name)?;
+    let terrain = parse_terrain(section, &name)?;
+    let ring = parse_ring(section)?;
+    let atmosphere = parse_atmosphere(section)?;
+    let surface = parse_surface(section)?;
+    let clouds = parse_clouds(section)?;
+    let orbit = parse_orbit(section, kind)?;
+    let tail = parse_tail(section, kind)?;
+    let spin = parse_spin(section)?;
+
+    Ok(Body {
+        name,
+        kind,
+        radius: section.f32_or("radius", 1.0)?,
+        color: section.color_or("color", 0xFFFFFFFF)?,
+        orbit_radius: section.f32_or("orbit_radius", 0.0)?,
+        orbit_speed: section.f32_or("orbit_speed", 0.0)?,
+        angle: section.f32_or("angle", 0.0)?.to_radians(),
+        parent,
+        orbit,
+        texture: section.get("texture").map(str::to_string),
+        shader: parse_shader(section)?,
+        terrain,
+        shape,
+        ring,
+        atmosphere,
+        surface,
+        clouds,
+        tail,
+        spin,
+    })
+}
+
+/// Giro propio opcional: `rotation_period` en segundos (negativo = retrógrado).
+fn parse_spin(section: &Section) -> Result<Option<Spin>, SceneError> {
+    if section.get("rotation_period").is_none() {
+        return Ok(None);
+    }
+    let period = section.f32_or("rotation_period", 0.0)?;
+    if period == 0.0 {
+        return Err(section.error("rotation_period", "el período de rotación no puede ser 0"));
+    }
+    Ok(Some(Spin {
+        speed: 2.0 * PI / period,
+        angle: 0.0,
+        tilt: 0.0,
+        node: 0.0,
+    }))
+}
+
+/// Shader procedural opcional (`shader = gas_giant`, etc.).
+fn parse_shader(section: &Section) -> Result<Option<ShaderKind>, SceneError> {
+    let Some(name) = section.get("shader") else {
+        return Ok(None);
+    };
+    ShaderKind::from_name(name).map(Some).ok_or_else(|| {
+        section.error(
+            "shader",
+            format!("shader desconocido: {} (opciones: {})", name, ShaderKind::NAMES.join(", ")),
+        )
+    })
+}
+
+/// Mapas de relieve y brillo: `normal_map`, `height_map`, `specular_map`,
+/// `emissive_map`, `bump_strength` y `shininess`. Solo si hay al menos un mapa.
+fn parse_surface(section: &Section) -> Result<Option<SurfaceMaps>, SceneError> {
+    let map = |key: &str| section.get(key).map(str::to_string);
+    let (normal_map, height_map, specular_map) = (map("normal_map"), map("height_map"), map("specular_map"));
+    let emissive_map = map("emissive_map");
+    if normal_map.is_none() && height_map.is_none() && specular_map.is_none() && emissive_map.is_none() {
+        return Ok(None);
+    }
+
+    let defaults = SurfaceMaps::new();
+    let shininess = section.f32_or("shininess", defaults.shininess)?;
+    if shininess < 1.0 {
+        return Err(section.error("shininess", "el exponente especular tiene que ser al menos 1"));
+    }
+
+    Ok(Some(SurfaceMaps {
+        normal_map,
+        height_map,
+        specular_map,
+        emissive_map,
+        bump_strength: section.f32_or("bump_strength", defaults.bump_strength)?.max(0.0),
+        shininess,
+    }))
+}
+
+/// Capa de nubes: `cloud_texture`, `cloud_altitude`, `cloud_speed` (rad/s)
+/// y `cloud_opacity`. Sin textura se generan nubes procedurales.
+fn parse_clouds(section: &Section) -> Result<Option<CloudLayer>, SceneError> {
+    let keys = ["cloud_texture", "cloud_altitude", "cloud_speed", "cloud_opacity"];
+    if keys.iter().all(|k| section.get(k).is_none()) {
+        return Ok(None);
+    }
+
+    let defaults = CloudLayer::new();
+    let altitude = section.f32_or("cloud_altitude", defaults.altitude)?;
+    if altitude <= 0.0 {
+        return Err(section.error("cloud_altitude", "la altura de las nubes tiene que ser positiva"));
+    }
+
+    Ok(Some(CloudLayer {
+        texture: section.get("cloud_texture").map(str::to_string),
+        altitude,
+        rotation_speed: section.f32_or("cloud_speed", defaults.rotation_speed)?,
+        opacity: section.f32_or("cloud_opacity", defaults.opacity)?.clamp(0.0, 1.0),
+        ..defaults
+    }))
+}
+
+/// Atmósfera opcional: `atmosphere_color`, `atmosphere_thickness` y `atmosphere_density`.
+fn parse_atmosphere(section: &Section) -> Result<Option<Atmosphere>, SceneError> {
+    if section.get("atmosphere_color").is_none() {
+        return Ok(None);
+    }
+
+    let defaults = Atmosphere::new(section.color_or("atmosphere_color", 0xFF6FA8FF)?);
+    let thickness = section.f32_or("atmosphere_thickness", defaults.thickness)?;
+    if thickness <= 0.0 {
+        return Err(section.error("atmosphere_thickness", "el espesor tiene que ser positivo"));
+    }
+
+    Ok(Some(Atmosphere {
+        thickness,
+        density: section.f32_or("atmosphere_density", defaults.density)?.max(0.0),
+        ..defaults
+    }))
+}
+
+/// Órbita kepleriana: para los cometas, o si se da alguno de sus elementos
+/// (ángulos en grados). `orbit_radius` es el semieje mayor, `angle` la anomalía
+/// media inicial y `orbit_speed` el movimiento medio.
+fn parse_orbit(section: &Section, kind: BodyKind) -> Result<Option<OrbitalElements>, SceneError> {
+    const KEYS: [&str; 4] = ["eccentricity", "inclination", "ascending_node", "arg_periapsis"];
+    let keplerian = matches!(kind, BodyKind::Comet) || KEYS.iter().any(|k| section.get(k).is_some());
+    if !keplerian {
+        return Ok(None);
+    }
+
+    let eccentricity = section.f32_or("eccentricity", 0.0)?;
+    if !(0.0..1.0).contains(&eccentricity) {
+        return Err(section.error("eccentricity", "la excentricidad tiene que estar en [0, 1)"));
+    }
+
+    Ok(Some(OrbitalElements {
+        semi_major: section.f32_or("orbit_radius", 0.0)?,
+        eccentricity,
+        inclination: section.f32_or("inclination", 0.0)?.to_radians(),
+        ascending_node: section.f32_or("ascending_node", 0.0)?.to_radians(),
+        arg_periapsis: section.f32_or("arg_periapsis", 0.0)?.to_radians(),
+        mean_anomaly: 0.0,
+    }))
+}
+
+/// Colas de los cometas: `tail_length`, `coma_radius`, `ion_color` y `dust_color`.
+fn parse_tail(section: &Section, kind: BodyKind) -> Result<Option<CometTail>, SceneError> {
+    if !matches!(kind, BodyKind::Comet) {
+        return Ok(None);
+    }
+
+    let defaults = CometTail::new();
+    Ok(Some(CometTail {
+        length: section.f32_or("tail_length", defaults.length)?,
+        coma_radius: section.f32_or("coma_radius", defaults.coma_radius)?,
+        ion_color: section.color_or("ion_color", defaults.ion_color)?,
+        dust_color: section.color_or("dust_color", defaults.dust_color)?,
+    }))
+}
+
+/// Anillo opcional: `ring_inner`, `ring_outer`, `ring_tilt` y `ring_node` (grados), `ring_color`
+/// y `ring_texture` (franja radial con alfa).
+fn parse_ring(section: &Section) -> Result<Option<Ring>, SceneError> {
+    if section.get("ring_outer").is_none() {
+        return Ok(None);
+    }
+
+    let inner_radius = section.f32_or("ring_inner", 0.0)?;
+    let outer_radius = section.f32_or("ring_outer", 0.0)?;
+    if inner_radius <= 0.0 || outer_radius <= inner_radius {
+        return Err(section.error("ring_outer", "se necesita 0 < ring_inner < ring_outer"));
+    }
+
+    Ok(Some(Ring {
+        inner_radius,
+        outer_radius,
+        tilt: section.f32_or("ring_tilt", 0.0)?.to_radians(),
+        node: section.f32_or("ring_node", 0.0)?.to_radians(),
+        color: section.color_or("ring_color", 0xFFD8C8A8)?,
+        texture: section.get("ring_texture").map(str::to_string),
+    }))
+}
+
+/// Forma irregular: siempre para asteroides y cometas, y para cualquier otro cuerpo
+/// que defina alguna de sus claves (p. ej. una luna chica con `lumpiness`).
+/// Sin `seed` se usa una derivada del nombre, así la escena se reproduce igual.
+fn parse_shape(section: &Section, kind: BodyKind, name: &str) -> Result<Option<AsteroidParams>, SceneError> {
+    const KEYS: [&str; 5] = ["seed", "subdivisions", "lumpiness", "craters", "crater_size"];
+    let irregular = matches!(kind, BodyKind::Asteroid | BodyKind::Comet) || KEYS.iter().any(|k| section.get(k).is_some());
+    if !irregular {
+        return Ok(None);
+    }
+
+    let defaults = AsteroidParams::new(section.u64_or("seed", seed_from_str(name))?);
+    Ok(Some(AsteroidParams {
+        subdivisions: section.u64_or("subdivisions", defaults.subdivisions as u64)?.min(6) as u32,
+        lumpiness: section.f32_or("lumpiness", defaults.lumpiness)?,
+        craters: section.u64_or("craters", defaults.craters as u64)? as u32,
+        crater_size: section.f32_or("crater_size", defaults.crater_size.to_degrees())?.to_radians(),
+        ..defaults
+    }))
+}
+
+/// Textura procedural: `terrain_seed`, `sea_level`, `polar_caps`, `temperature`,
+/// `humidity`, `continent_scale` y `texture_size`. Con cualquiera de esas claves
+/// el cuerpo usa una textura generada; la semilla por defecto sale del nombre.
+fn parse_terrain(section: &Section, name: &str) -> Result<Option<TerrainParams>, SceneError> {
+    const KEYS: [&str; 7] = [
+        "terrain_seed",
+        "sea_level",
+        "polar_caps",
+        "temperature",
+        "humidity",
+        "continent_scale",
+        "texture_size",
+    ];
+    if KEYS.iter().all(|k| section.get(k).is_none()) {
+        return Ok(None);
+    }
+
+    let defaults = TerrainParams::new(section.u64_or("terrain_seed", seed_from_str(name))?);
+    let sea_level = section.f32_or("sea_level", defaults.sea_level)?;
+    if !(0.0..=1.0).contains(&sea_level) {
+        return Err(section.error("sea_level", "el nivel del mar tiene que estar entre 0 y 1"));
+    }
+    let width = section.u64_or("texture_size", defaults.width as u64)?;
+    if !(16..=4096).contains(&width) {
+        return Err(section.error("texture_size", "el ancho de la textura tiene que estar entre 16 y 4096"));
+    }
+
+    Ok(Some(TerrainParams {
+        width: width as usize,
+        sea_level,
+        polar_caps: section.f32_or("polar_caps", defaults.polar_caps)?,
+        temperature: section.f32_or("temperature", defaults.temperature)?,
+        humidity: section.f32_or("humidity", defaults.humidity)?,
+        continent_scale: section.f32_or("continent_scale", defaults.continent_scale)?.max(0.1),
+        ..defaults
+    }))
+}
+
+fn parse_mesh(section: &Section, bodies: &[Body]) -> Result<MeshInstance, SceneError> {
+    let name = section.require("name")?.to_string();
+    let parent = parse_parent(section, bodies, &name)?;
+
+    Ok(MeshInstance {
+        model: section.require("model")?.to_string(),
+        parent,
+        orbit_radius: section.f32_or("orbit_radius", 0.0)?,
+        orbit_speed: section.f32_or("orbit_speed", 0.0)?,
+        angle: section.f32_or("angle", 0.0)?.to_radians(),
+        scale: section.f32_or("scale", 1.0)?,
+        tilt: section.f32_or("tilt", 0.0)?.to_radians(),
+        spin_speed: section.f32_or("spin_speed", 0.0)?,
+        spin: 0.0,
+        name,
+    })
+}
+
+fn parse_belt(section: &Section, bodies: &[Body]) -> Result<AsteroidBelt, SceneError> {
+    let name = section.require("name")?.to_string();
+    let parent = parse_parent(section, bodies, &name)?;
+
+    let inner_radius = section.f32_or("inner_radius", 0.0)?;
+    let outer_radius = section.f32_or("outer_radius", 0.0)?;
+    if inner_radius <= 0.0 || outer_radius < inner_radius {
+        return Err(section.error("outer_radius", "se necesita 0 < inner_radius <= outer_radius"));
+    }
+
+    let params = BeltParams {
+        seed: section.u64_or("seed", seed_from_str(&name))?,
+        count: section.u64_or("count", 1000)? as usize,
+        inner_radius,
+        outer_radius,
+        inclination: section.f32_or("inclination", 2.0)?.to_radians(),
+        eccentricity: section.f32_or("eccentricity", 0.05)?.clamp(0.0, 0.95),
+        orbit_speed: section.f32_or("orbit_speed", 0.1)?,
+        particle_size: section.f32_or("particle_size", 0.3)?,
+    };
+    let color = section.color_or("color", 0xFF9A8F80)?;
+
+    Ok(AsteroidBelt::generate(name, parent, color, &params))
+}
+
+/// `parent = <nombre>` se refiere a un cuerpo definido más arriba en el archivo.
+fn parse_parent(section: &Section, bodies: &[Body], name: &str) -> Result<Option<usize>, SceneError> {
+    match section.get("parent") {
+        None => Ok(None),
+        Some(parent_name) => bodies
+            .iter()
+            .position(|b| b.name == parent_name)
+            .map(Some)
+            .ok_or_else(|| section.error("parent", format!("`{}` no está definido antes de `{}`", parent_name, name))),
+    }
+}
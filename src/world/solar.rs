@@ -0,0 +1,604 @@
+//! El Sistema Solar real: el Sol, los ocho planetas y sus lunas principales.
+//!
+//! Los planetas usan los elementos keplerianos de J2000 con sus tasas seculares
+//! (tabla de JPL "Approximate Positions of the Planets", válida 1800-2050), así
+//! que las posiciones son las de la fecha pedida. La Luna usa sus elementos
+//! medios, con la precesión del nodo y del perigeo. El resto de las lunas giran
+//! en el plano ecuatorial de su planeta, con la fase y el periastro de J2000
+//! (series de Meeus para las galileanas, elementos medios de JPL para el resto)
+//! y sin precesión. Cada cuerpo rota alrededor de su polo.
+//!
+//! Radios, distancias y períodos son reales; la escala del mundo es `AU` unidades
+//! por unidad astronómica y un segundo de simulación equivale a `DAYS_PER_SECOND`
+//! días. Con `Scale::Exaggerated` los radios (y las órbitas de las lunas, para
+//! que no queden adentro de su planeta) se agrandan para que se vean.
+
+use std::f64::consts::PI;
+
+use crate::math::rng::seed_from_str;
+use crate::math::Vec3;
+
+use super::orbit::OrbitalElements;
+use super::{Atmosphere, AsteroidBelt, BeltParams, Body, BodyKind, CloudLayer, Ring, ShaderKind, SolarSystem, Spin, SurfaceMaps};
+
+/// Unidades del mundo por unidad astronómica (Neptuno queda antes del plano lejano).
+const AU: f64 = 150.0;
+const KM_PER_AU: f64 = 149_597_870.7;
+/// Días que pasan por cada segundo de simulación.
+pub const DAYS_PER_SECOND: f64 = 1.0;
+/// Día juliano de la época J2000 (2000-01-01 12:00 TT).
+pub const J2000: f64 = 2_451_545.0;
+/// Oblicuidad de la eclíptica en J2000, para pasar polos ecuatoriales a eclípticos.
+const OBLIQUITY: f64 = 23.439_291;
+/// Radio del Sol con los radios exagerados (a escala real no entraría Mercurio).
+const EXAGGERATED_SUN: f64 = 12.0;
+
+/// Cómo se escalan los tamaños de los cuerpos.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Scale {
+    /// Todo en la misma escala: los planetas son puntos casi invisibles.
+    True,
+    /// Radios comprimidos (∝ r^0.6) y lunas alejadas en proporción.
+    Exaggerated,
+}
+
+impl Scale {
+    /// Radio en unidades del mundo para un cuerpo de `km` kilómetros.
+    fn radius(self, km: f64) -> f64 {
+        match self {
+            Scale::True => km / KM_PER_AU * AU,
+            Scale::Exaggerated => (km / 1000.0).powf(0.6).max(0.25),
+        }
+    }
+
+    /// Distancia de una luna a su planeta, que mide `parent_km` de radio.
+    fn moon_distance(self, km: f64, parent_km: f64) -> f64 {
+        match self {
+            Scale::True => km / KM_PER_AU * AU,
+            // En radios del planeta, con la raíz para que las lejanas no se escapen
+            Scale::Exaggerated => self.radius(parent_km) * (km / parent_km).sqrt(),
+        }
+    }
+
+    /// Largo de un radio del planeta medido en kilómetros, en unidades del mundo
+    /// (para anillos, que se tienen que agrandar junto con el planeta).
+    fn around(self, km: f64, parent_km: f64) -> f64 {
+        self.radius(parent_km) * km / parent_km
+    }
+}
+
+/// Elementos de JPL: a (UA), e, I, L, ϖ, Ω (grados), cada uno con su tasa por siglo.
+struct PlanetData {
+    name: &'static str,
+    radius_km: f64,
+    /// Período de rotación sideral en horas (negativo = retrógrado).
+    rotation_hours: f64,
+    color: u32,
+    elements: [f64; 6],
+    rates: [f64; 6],
+    /// Polo norte (ascensión recta, declinación) en grados, para el plano de las lunas.
+    pole: (f64, f64),
+}
+
+const PLANETS: [PlanetData; 8] = [
+    PlanetData {
+        name: "Mercurio",
+        radius_km: 2439.7,
+        rotation_hours: 1407.6,
+        color: 0xFF9A948C,
+        elements: [0.387_099_27, 0.205_635_93, 7.004_979_02, 252.250_323_5, 77.457_796_28, 48.330_765_93],
+        rates: [0.000_000_37, 0.000_019_06, -0.005_947_49, 149_472.674_111_75, 0.160_476_89, -0.125_340_81],
+        pole: (281.01, 61.41),
+    },
+    PlanetData {
+        name: "Venus",
+        radius_km: 6051.8,
+        rotation_hours: -5832.5,
+        color: 0xFFE8CF9A,
+        elements: [0.723_335_66, 0.006_776_72, 3.394_676_05, 181.979_099_5, 131.602_467_18, 76.679_842_55],
+        rates: [0.000_003_9, -0.000_041_07, -0.000_788_9, 58_517.815_387_29, 0.002_683_29, -0.277_694_18],
+        pole: (272.76, 67.16),
+    },
+    PlanetData {
+        // En realidad el baricentro Tierra-Luna (4700 km de diferencia)
+        name: "Tierra",
+        radius_km: 6371.0,
+        rotation_hours: 23.934_5,
+        color: 0xFF6FA8FF,
+        elements: [1.000_002_61, 0.016_711_23, -0.000_015_31, 100.464_571_66, 102.937_681_93, 0.0],
+        rates: [0.000_005_62, -0.000_043_92, -0.012_946_68, 35_999.372_449_81, 0.323_273_64, 0.0],
+        pole: (0.0, 90.0),
+    },
+    PlanetData {
+        name: "Marte",
+        radius_km: 3389.5,
+        rotation_hours: 24.622_9,
+        color: 0xFFC1663F,
+        elements: [1.523_710_34, 0.093_394_1, 1.849_691_42, -4.553_432_05, -23.943_629_59, 49.559_538_91],
+        rates: [0.000_018_47, 0.000_078_82, -0.008_131_31, 19_140.302_684_99, 0.444_410_88, -0.292_573_43],
+        pole: (317.681, 52.887),
+    },
+    PlanetData {
+        name: "Júpiter",
+        radius_km: 69_911.0,
+        rotation_hours: 9.925,
+        color: 0xFFD8B48A,
+        elements: [5.202_887, 0.048_386_24, 1.304_396_95, 34.396_440_51, 14.728_479_83, 100.473_909_09],
+        rates: [-0.000_116_07, -0.000_132_53, -0.001_837_14, 3_034.746_127_75, 0.212_526_68, 0.204_691_06],
+        pole: (268.057, 64.495),
+    },
+    PlanetData {
+        name: "Saturno",
+        radius_km: 58_232.0,
+        rotation_hours: 10.656,
+        color: 0xFFE3CF9A,
+        elements: [9.536_675_94, 0.053_861_79, 2.485_991_87, 49.954_244_23, 92.598_878_31, 113.662_424_48],
+        rates: [-0.001_250_6, -0.000_509_91, 0.001_936_09, 1_222.493_622_01, -0.418_972_16, -0.288_677_94],
+        pole: (40.589, 83.537),
+    },
+    PlanetData {
+        name: "Urano",
+        radius_km: 25_362.0,
+        rotation_hours: -17.24,
+        color: 0xFFAFDCE6,
+        elements: [19.189_164_64, 0.047_257_44, 0.772_637_83, 313.238_104_51, 170.954_276_3, 74.016_925_03],
+        rates: [-0.001_961_76, -0.000_043_97, -0.002_429_39, 428.482_027_85, 0.408_052_81, 0.042_405_89],
+        pole: (257.311, -15.175),
+    },
+    PlanetData {
+        name: "Neptuno",
+        radius_km: 24_622.0,
+        rotation_hours: 16.11,
+        color: 0xFF5B7FD8,
+        elements: [30.069_922_76, 0.008_590_48, 1.770_043_47, -55.120_029_69, 44.964_762_27, 131.784_225_74],
+        rates: [0.000_262_91, 0.000_051_05, 0.000_353_72, 218.459_453_25, -0.322_414_64, -0.005_086_64],
+        pole: (299.36, 43.46),
+    },
+];
+
+/// Luna en órbita casi circular sobre el ecuador de su planeta (rotación sincrónica).
+/// Las longitudes son de J2000 y en grados; como en `PLANETS`, se cuentan por la
+/// eclíptica hasta el nodo del ecuador del planeta y de ahí por la órbita.
+struct MoonData {
+    name: &'static str,
+    /// Índice en `PLANETS`.
+    planet: usize,
+    radius_km: f64,
+    distance_km: f64,
+    period_days: f64,
+    eccentricity: f64,
+    /// Longitud del periastro (ϖ = Ω + ω).
+    periapsis_longitude: f64,
+    /// Longitud media (L = ϖ + M).
+    mean_longitude: f64,
+    /// Gira al revés que su planeta (Tritón).
+    retrograde: bool,
+    shader: ShaderKind,
+    color: u32,
+}
+
+const MOONS: [MoonData; 12] = [
+    MoonData { name: "Fobos", planet: 3, radius_km: 11.1, distance_km: 9_376.0, period_days: 0.318_91, eccentricity: 0.0151, periapsis_longitude: 357.227, mean_longitude: 89.701, retrograde: false, shader: ShaderKind::Rock, color: 0xFF7A6E64 },
+    MoonData { name: "Deimos", planet: 3, radius_km: 6.2, distance_km: 23_463.0, period_days: 1.262_44, eccentricity: 0.0003, periapsis_longitude: 312.145, mean_longitude: 248.375, retrograde: false, shader: ShaderKind::Rock, color: 0xFF8A7E70 },
+    MoonData { name: "Ío", planet: 4, radius_km: 1821.6, distance_km: 421_700.0, period_days: 1.769_138, eccentricity: 0.0041, periapsis_longitude: 36.05, mean_longitude: 17.46, retrograde: false, shader: ShaderKind::Lava, color: 0xFFE8D36A },
+    MoonData { name: "Europa", planet: 4, radius_km: 1560.8, distance_km: 671_034.0, period_days: 3.551_181, eccentricity: 0.009, periapsis_longitude: 198.706, mean_longitude: 212.067, retrograde: false, shader: ShaderKind::Ice, color: 0xFFE6DCC8 },
+    MoonData { name: "Ganímedes", planet: 4, radius_km: 2634.1, distance_km: 1_070_412.0, period_days: 7.154_553, eccentricity: 0.0013, periapsis_longitude: 249.084, mean_longitude: 219.371, retrograde: false, shader: ShaderKind::Rock, color: 0xFF9C9080 },
+    MoonData { name: "Calisto", planet: 4, radius_km: 2410.3, distance_km: 1_882_709.0, period_days: 16.689_018, eccentricity: 0.0074, periapsis_longitude: 351.009, mean_longitude: 78.462, retrograde: false, shader: ShaderKind::Rock, color: 0xFF6E6458 },
+    MoonData { name: "Titán", planet: 5, radius_km: 2574.7, distance_km: 1_221_870.0, period_days: 15.945, eccentricity: 0.0288, periapsis_longitude: 338.154, mean_longitude: 141.464, retrograde: false, shader: ShaderKind::Rock, color: 0xFFD9A050 },
+    MoonData { name: "Rea", planet: 5, radius_km: 763.8, distance_km: 527_108.0, period_days: 4.518_212, eccentricity: 0.0013, periapsis_longitude: 2.223, mean_longitude: 182.004, retrograde: false, shader: ShaderKind::Ice, color: 0xFFD8D4CC },
+    MoonData { name: "Jápeto", planet: 5, radius_km: 734.5, distance_km: 3_560_820.0, period_days: 79.3215, eccentricity: 0.0286, periapsis_longitude: 122.273, mean_longitude: 324.062, retrograde: false, shader: ShaderKind::Rock, color: 0xFFA09488 },
+    MoonData { name: "Titania", planet: 6, radius_km: 788.4, distance_km: 435_910.0, period_days: 8.705_872, eccentricity: 0.0011, periapsis_longitude: 16.876, mean_longitude: 41.49, retrograde: false, shader: ShaderKind::Ice, color: 0xFFB8B0A8 },
+    MoonData { name: "Oberón", planet: 6, radius_km: 761.4, distance_km: 583_520.0, period_days: 13.463_239, eccentricity: 0.0014, periapsis_longitude: 16.876, mean_longitude: 299.964, retrograde: false, shader: ShaderKind::Rock, color: 0xFFA89C94 },
+    MoonData { name: "Tritón", planet: 7, radius_km: 1353.4, distance_km: 354_759.0, period_days: 5.876_854, eccentricity: 0.0, periapsis_longitude: 322.292, mean_longitude: 314.549, retrograde: true, shader: ShaderKind::Ice, color: 0xFFE0D0D0 },
+];
+
+const MOON_RADIUS_KM: f64 = 1737.4;
+const MOON_DISTANCE_KM: f64 = 384_400.0;
+
+/// Polos de rotación (ascensión recta y declinación en grados) del Sol y la Luna.
+const SUN_POLE: (f64, f64) = (286.13, 63.87);
+const MOON_POLE: (f64, f64) = (269.995, 66.539);
+
+impl SolarSystem {
+    /// El Sistema Solar en el día juliano `date` (ver `julian_day`).
+    pub fn real(date: f64, scale: Scale) -> Self {
+        let days = date - J2000;
+        let centuries = days / 36_525.0;
+
+        let sun_km = 695_700.0;
+        let sun_radius = match scale {
+            Scale::True => scale.radius(sun_km),
+            Scale::Exaggerated => EXAGGERATED_SUN,
+        };
+        let mut sun = Body::new("Sol", BodyKind::Star, sun_radius as f32, 0xFFFFD27F);
+        sun.texture = Some("textures/sun.jpg".into());
+        sun.shader = Some(ShaderKind::Granulation);
+        sun.spin = Some(spin(days, 609.12, SUN_POLE));
+        let mut bodies = vec![sun];
+
+        for (p, data) in PLANETS.iter().enumerate() {
+            let index = bodies.len();
+            bodies.push(planet(data, centuries, days, scale));
+
+            if p == 2 {
+                bodies.push(luna(index, days, scale));
+            }
+            for moon in MOONS.iter().filter(|m| m.planet == p) {
+                bodies.push(moon_body(moon, index, days, scale));
+            }
+        }
+
+        // Cinturón principal, entre Marte y Júpiter
+        let inner = 2.2;
+        let mut belt = AsteroidBelt::generate(
+            "Cinturón de asteroides".into(),
+            Some(0),
+            0xFFA89C8A,
+            &BeltParams {
+                seed: seed_from_str("Cinturón de asteroides"),
+                count: 3000,
+                inner_radius: (inner * AU) as f32,
+                outer_radius: (3.2 * AU) as f32,
+                inclination: 10.0f32.to_radians(),
+                eccentricity: 0.15,
+                orbit_speed: (2.0 * PI / (365.256 * inner * inner.sqrt()) * DAYS_PER_SECOND) as f32,
+                particle_size: if scale == Scale::True { 0.05 } else { 0.35 },
+            },
+        );
+
+        for particle in &mut belt.particles {
+            // Generadas con los ejes del mundo: se reflejan para que giren como los planetas
+            particle.elements = OrbitalElements::from_ecliptic(particle.elements);
+        }
+        belt.update(0.0);
+
+        let mut system = SolarSystem::from_bodies(bodies);
+        system.belts = vec![belt];
+        system
+    }
+}
+
+/// Giro propio con el período sideral en horas, en la fase que toca a `days` de J2000,
+/// alrededor del polo norte `pole` (ascensión recta y declinación en grados). Un
+/// período positivo es el sentido directo visto desde ese polo.
+fn spin(days: f64, hours: f64, pole: (f64, f64)) -> Spin {
+    let speed = 2.0 * PI * 24.0 / hours;
+    let (tilt, node) = axis_tilt(pole);
+    Spin {
+        speed: (speed * DAYS_PER_SECOND) as f32,
+        angle: (speed * days).rem_euclid(2.0 * PI) as f32,
+        tilt,
+        node,
+    }
+}
+
+/// Elementos a `centuries` de J2000, con la anomalía media en esa fecha.
+fn planet(data: &PlanetData, centuries: f64, days: f64, scale: Scale) -> Body {
+    let at = |i: usize| data.elements[i] + data.rates[i] * centuries;
+    let (a, e, incl, l, peri, node) = (at(0), at(1), at(2), at(3), at(4), at(5));
+    let mean_anomaly = (l - peri).to_radians().rem_euclid(2.0 * PI);
+    // Movimiento medio en rad/día (sin la precesión del perihelio)
+    let mean_motion = (data.rates[3] - data.rates[4]).to_radians() / 36_525.0;
+
+    let orbit = OrbitalElements::from_ecliptic(OrbitalElements {
+        semi_major: (a * AU) as f32,
+        eccentricity: e as f32,
+        inclination: incl.to_radians() as f32,
+        ascending_node: node.to_radians() as f32,
+        arg_periapsis: (peri - node).to_radians() as f32,
+        mean_anomaly: mean_anomaly as f32,
+    });
+
+    let radius = scale.radius(data.radius_km) as f32;
+    let mut body = Body::new(data.name, BodyKind::Planet, radius, data.color);
+    body.parent = Some(0);
+    body.orbit_radius = orbit.semi_major;
+    body.orbit_speed = (mean_motion * DAYS_PER_SECOND) as f32;
+    body.angle = orbit.mean_anomaly;
+    body.orbit = Some(orbit);
+    body.spin = Some(spin(days, data.rotation_hours, data.pole));
+
+    match data.name {
+        "Mercurio" => body.texture = Some("textures/mercury.jpg".into()),
+        "Venus" => {
+            body.texture = Some("textures/venus.jpg".into());
+            body.atmosphere = Some(Atmosphere { thickness: radius * 0.2, density: 2.5, ..Atmosphere::new(0xFFF2D29A) });
+        }
+        "Tierra" => {
+            body.texture = Some("textures/earth.jpg".into());
+            body.surface = Some(SurfaceMaps {
+                height_map: Some("textures/earth_height.png".into()),
+                specular_map: Some("textures/earth_specular.png".into()),
+                emissive_map: Some("textures/earth_lights.png".into()),
+                bump_strength: 0.4,
+                shininess: 60.0,
+                ..SurfaceMaps::new()
+            });
+            body.atmosphere = Some(Atmosphere { thickness: radius * 0.15, ..Atmosphere::new(0xFF6FA8FF) });
+            // Las nubes acompañan la rotación, un poco más rápido que el suelo
+            let spin_speed = body.spin.map_or(0.0, |s| s.speed);
+            body.clouds = Some(CloudLayer { altitude: radius * 0.03, rotation_speed: spin_speed * 1.03, ..CloudLayer::new() });
+        }
+        "Marte" => {
+            body.texture = Some("textures/mars.jpg".into());
+            body.surface = Some(SurfaceMaps {
+                height_map: Some("textures/mars_height.png".into()),
+                bump_strength: 0.8,
+                ..SurfaceMaps::new()
+            });
+        }
+        "Saturno" => {
+            body.shader = Some(ShaderKind::GasGiant);
+            let (tilt, node) = axis_tilt(data.pole);
+            body.ring = Some(Ring {
+                inner_radius: scale.around(74_500.0, data.radius_km) as f32,
+                outer_radius: scale.around(136_780.0, data.radius_km) as f32,
+                tilt,
+                node,
+                color: 0xFFD9C7A3,
+                texture: None,
+            });
+        }
+        "Urano" | "Neptuno" => {
+            body.shader = Some(ShaderKind::GasGiant);
+            body.atmosphere = Some(Atmosphere { thickness: radius * 0.05, density: 0.6, ..Atmosphere::new(data.color) });
+        }
+        _ => body.shader = Some(ShaderKind::GasGiant),
+    }
+    body
+}
+
+/// La Luna con sus elementos medios en la fecha (el nodo retrocede en 18.6 años
+/// y el perigeo avanza en 8.85).
+fn luna(earth: usize, days: f64, scale: Scale) -> Body {
+    let l = 218.316_447_7 + 13.176_396_48 * days;
+    let node = 125.044_52 - 0.052_953_8 * days;
+    let peri = 83.353_246_5 + 0.111_404_080_3 * days;
+    let mean_motion = (13.176_396_48 - 0.111_404_080_3f64).to_radians();
+
+    let orbit = OrbitalElements::from_ecliptic(OrbitalElements {
+        semi_major: scale.moon_distance(MOON_DISTANCE_KM, PLANETS[2].radius_km) as f32,
+        eccentricity: 0.0549,
+        inclination: 5.145f32.to_radians(),
+        ascending_node: node.to_radians().rem_euclid(2.0 * PI) as f32,
+        arg_periapsis: (peri - node).to_radians().rem_euclid(2.0 * PI) as f32,
+        mean_anomaly: (l - peri).to_radians().rem_euclid(2.0 * PI) as f32,
+    });
+
+    let mut moon = Body::new("Luna", BodyKind::Moon, scale.radius(MOON_RADIUS_KM) as f32, 0xFFCFCFCF);
+    moon.texture = Some("textures/moon.jpg".into());
+    moon.parent = Some(earth);
+    moon.orbit_radius = orbit.semi_major;
+    moon.orbit_speed = (mean_motion * DAYS_PER_SECOND) as f32;
+    moon.angle = orbit.mean_anomaly;
+    moon.orbit = Some(orbit);
+    moon.spin = Some(spin(days, 27.321_661 * 24.0, MOON_POLE));
+    moon
+}
+
+fn moon_body(data: &MoonData, parent: usize, days: f64, scale: Scale) -> Body {
+    let planet = &PLANETS[data.planet];
+    let (mut inclination, mut node) = equator_plane(planet.pole);
+    let mut pole = planet.pole;
+    if data.retrograde {
+        // El mismo plano recorrido al revés; gira alrededor del polo opuesto
+        inclination = PI - inclination;
+        node += PI;
+        pole = (pole.0 + 180.0, -pole.1);
+    }
+    let mean_motion = 2.0 * PI / data.period_days;
+    let (l, peri) = (data.mean_longitude.to_radians(), data.periapsis_longitude.to_radians());
+
+    let orbit = OrbitalElements::from_ecliptic(OrbitalElements {
+        semi_major: scale.moon_distance(data.distance_km, planet.radius_km) as f32,
+        eccentricity: data.eccentricity as f32,
+        inclination: inclination as f32,
+        ascending_node: node.rem_euclid(2.0 * PI) as f32,
+        arg_periapsis: (peri - node).rem_euclid(2.0 * PI) as f32,
+        mean_anomaly: (l - peri + mean_motion * days).rem_euclid(2.0 * PI) as f32,
+    });
+
+    let radius = scale.radius(data.radius_km) as f32;
+    let mut moon = Body::new(data.name, BodyKind::Moon, radius, data.color);
+    moon.shader = Some(data.shader);
+    moon.parent = Some(parent);
+    moon.orbit_radius = orbit.semi_major;
+    moon.orbit_speed = (mean_motion * DAYS_PER_SECOND) as f32;
+    moon.angle = orbit.mean_anomaly;
+    moon.orbit = Some(orbit);
+    moon.spin = Some(spin(days, data.period_days * 24.0, pole));
+    if data.name == "Titán" {
+        moon.atmosphere = Some(Atmosphere { thickness: radius * 0.3, density: 3.0, ..Atmosphere::new(0xFFD9A050) });
+    }
+    moon
+}
+
+/// Dirección eclíptica (x al equinoccio, z al norte) de unas coordenadas
+/// ecuatoriales en grados; sirve para polos de planetas y para estrellas.
+fn equatorial_to_ecliptic((ra, dec): (f64, f64)) -> (f64, f64, f64) {
+    let (ra, dec, eps) = (ra.to_radians(), dec.to_radians(), OBLIQUITY.to_radians());
+    let (x, y, z) = (dec.cos() * ra.cos(), dec.cos() * ra.sin(), dec.sin());
+    (x, y * eps.cos() + z * eps.sin(), -y * eps.sin() + z * eps.cos())
+}
+
+/// Dirección en el mundo de un punto del cielo (ascensión recta y declinación en
+/// grados). La eclíptica es el plano XZ, el polo norte eclíptico +Y y el equinoccio
+/// +X; el eje y de la eclíptica va a −Z, igual que en `OrbitalElements::from_ecliptic`.
+pub fn equatorial_direction(ra: f64, dec: f64) -> Vec3 {
+    let (x, y, z) = equatorial_to_ecliptic((ra, dec));
+    Vec3::new(x as f32, z as f32, -y as f32)
+}
+
+/// Inclinación y nodo ascendente (radianes) del plano ecuatorial respecto de la eclíptica.
+fn equator_plane(pole: (f64, f64)) -> (f64, f64) {
+    let (x, y, z) = equatorial_to_ecliptic(pole);
+    (z.clamp(-1.0, 1.0).acos(), x.atan2(-y))
+}
+
+/// `tilt` y `node` de un `Ring` o un `Spin` sobre el ecuador: gira +Y alrededor
+/// de X y después de +Y hasta la dirección del polo en el mundo.
+fn axis_tilt(pole: (f64, f64)) -> (f32, f32) {
+    let (ra, dec) = pole;
+    let normal = equatorial_direction(ra, dec);
+    (normal.y.clamp(-1.0, 1.0).acos(), normal.x.atan2(normal.z))
+}
+
+/// Día juliano de una fecha del calendario gregoriano (a las 0 h de tiempo universal).
+pub fn julian_day(year: i32, month: u32, day: u32) -> f64 {
+    let (mut y, mut m) = (year as f64, month as f64);
+    if month <= 2 {
+        y -= 1.0;
+        m += 12.0;
+    }
+    let a = (y / 100.0).floor();
+    let b = 2.0 - a + (a / 4.0).floor();
+    (365.25 * (y + 4716.0)).floor() + (30.6001 * (m + 1.0)).floor() + day as f64 + b - 1524.5
+}
+
+/// Lee una fecha `AAAA-MM-DD`.
+pub fn parse_date(text: &str) -> Option<f64> {
+    let mut parts = text.trim().splitn(3, '-');
+    let year = parts.next()?.parse().ok()?;
+    let month = parts.next()?.parse().ok()?;
+    let day = parts.next()?.parse().ok()?;
+    if !(1..=12).contains(&month) || !(1..=days_in_month(year, month)).contains(&day) {
+        return None;
+    }
+    Some(julian_day(year, month, day))
+}
+
+/// Días del mes en el calendario gregoriano.
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Día juliano de hoy, según el reloj del sistema.
+pub fn today() -> f64 {
+    let seconds = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0.0, |d| d.as_secs_f64());
+    2_440_587.5 + seconds / 86_400.0
+}
+
+/// Fecha del calendario (año, mes, día) de un día juliano.
+pub fn calendar_date(jd: f64) -> (i32, u32, u32) {
+    let z = (jd + 0.5).floor();
+    let alpha = ((z - 1_867_216.25) / 36_524.25).floor();
+    let a = z + 1.0 + alpha - (alpha / 4.0).floor();
+    let b = a + 1524.0;
+    let c = ((b - 122.1) / 365.25).floor();
+    let d = (365.25 * c).floor();
+    let e = ((b - d) / 30.6001).floor();
+
+    let day = (b - d - (30.6001 * e).floor()) as u32;
+    let month = if e < 14.0 { e - 1.0 } else { e - 13.0 } as u32;
+    let year = if month > 2 { c - 4716.0 } else { c - 4715.0 } as i32;
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn body<'a>(system: &'a SolarSystem, name: &str) -> &'a Body {
+        system.bodies.iter().find(|b| b.name == name).unwrap()
+    }
+
+    fn axis(body: &Body) -> Vec3 {
+        body.pole().rotate(Vec3::up())
+    }
+
+    /// Longitud eclíptica heliocéntrica (grados, J2000) y distancia al Sol (UA).
+    fn heliocentric(system: &SolarSystem, name: &str) -> (f64, f64) {
+        let index = system.bodies.iter().position(|b| b.name == name).unwrap();
+        let p = system.body_position(index);
+        // Inversa de `equatorial_direction`: el eje y de la eclíptica es −Z
+        let longitude = (-p.z as f64).atan2(p.x as f64).to_degrees().rem_euclid(360.0);
+        (longitude, p.length() as f64 / AU)
+    }
+
+    fn assert_angle(a: f64, b: f64, eps: f64) {
+        let diff = (a - b + 180.0).rem_euclid(360.0) - 180.0;
+        assert!(diff.abs() < eps, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn earth_matches_the_ephemeris() {
+        // 2000-01-01 0 h TU: el Sol aparente en 279.87°, a 0.98331 UA (dos días
+        // antes del perihelio)
+        let system = SolarSystem::real(julian_day(2000, 1, 1), Scale::True);
+        let (longitude, distance) = heliocentric(&system, "Tierra");
+        assert_angle(longitude, 99.87, 0.05);
+        assert!((distance - 0.98331).abs() < 5e-4, "{}", distance);
+
+        // Equinoccio de marzo de 2010 (20 de marzo, 17:32 TU): longitud 180° del
+        // equinoccio de la fecha, 0.14° menos en J2000 por la precesión
+        let system = SolarSystem::real(julian_day(2010, 3, 20) + 17.53 / 24.0, Scale::True);
+        let (longitude, distance) = heliocentric(&system, "Tierra");
+        assert_angle(longitude, 179.86, 0.05);
+        assert!((distance - 0.99595).abs() < 5e-4, "{}", distance);
+    }
+
+    #[test]
+    fn jupiter_matches_its_2010_opposition() {
+        // Oposición del 21 de septiembre de 2010 (11 h TU): Júpiter y la Tierra en
+        // la misma longitud heliocéntrica, a 3.95 UA entre sí
+        let system = SolarSystem::real(julian_day(2010, 9, 21) + 11.0 / 24.0, Scale::True);
+        let (earth, earth_distance) = heliocentric(&system, "Tierra");
+        let (jupiter, distance) = heliocentric(&system, "Júpiter");
+        assert_angle(jupiter, earth, 0.2);
+        assert!((distance - (3.954 + earth_distance)).abs() < 0.01, "{}", distance);
+    }
+
+    #[test]
+    fn parse_date_rejects_days_past_the_end_of_the_month() {
+        assert_eq!(parse_date("2000-01-01"), Some(julian_day(2000, 1, 1)));
+        assert!(parse_date("2023-02-31").is_none());
+        assert!(parse_date("2023-04-31").is_none());
+        assert!(parse_date("2023-02-29").is_none());
+        assert!(parse_date("2024-02-29").is_some());
+        assert!(parse_date("2000-02-29").is_some());
+        assert!(parse_date("1900-02-29").is_none());
+        assert!(parse_date("2023-12-31").is_some());
+        assert!(parse_date("2023-13-01").is_none());
+        assert!(parse_date("2023-06-00").is_none());
+    }
+
+    #[test]
+    fn saturn_spins_in_its_ring_plane() {
+        let system = SolarSystem::real(J2000, Scale::Exaggerated);
+        let saturn = body(&system, "Saturno");
+        let normal = saturn.ring.as_ref().unwrap().normal();
+        assert!(axis(saturn).dot(normal) > 0.9999);
+        // Unos 27° respecto de su órbita, que está casi en la eclíptica
+        let tilt = axis(saturn).y.acos().to_degrees();
+        assert!((tilt - 27.0).abs() < 2.0, "{}", tilt);
+    }
+
+    #[test]
+    fn uranus_lies_on_its_side() {
+        let system = SolarSystem::real(J2000, Scale::Exaggerated);
+        // El polo norte de la IAU queda apenas al norte de la eclíptica y rota al
+        // revés a su alrededor: la oblicuidad de 98° vista desde el otro polo
+        let uranus = body(&system, "Urano");
+        let tilt = axis(uranus).y.acos().to_degrees();
+        assert!((tilt - 82.0).abs() < 2.0, "{}", tilt);
+        assert!(uranus.spin.unwrap().speed < 0.0);
+    }
+
+    #[test]
+    fn moons_orbit_in_their_planet_equator() {
+        let system = SolarSystem::real(J2000, Scale::Exaggerated);
+        for moon in system.bodies.iter().filter(|b| matches!(b.kind, BodyKind::Moon) && b.name != "Luna") {
+            let planet = &system.bodies[moon.parent.unwrap()];
+            let offset = system.body_position(system.bodies.iter().position(|b| b.name == moon.name).unwrap())
+                - system.body_position(moon.parent.unwrap());
+            assert!(offset.normalized().dot(axis(planet)).abs() < 0.05, "{}", moon.name);
+        }
+    }
+}
@@ -0,0 +1,30 @@
+/// Mapas que agregan detalle a la superficie de un cuerpo, encima de su
+/// textura de color. Todos son equirectangulares, igual que la textura.
+#[derive(Clone, Debug)]
+pub struct SurfaceMaps {
+    /// Mapa de normales en espacio tangente (RGB: este, norte, afuera).
+    pub normal_map: Option<String>,
+    /// Mapa de alturas en gris (blanco = alto); se usa si no hay mapa de normales.
+    pub height_map: Option<String>,
+    /// Máscara especular en gris (blanco = brilla, por ejemplo los océanos).
+    pub specular_map: Option<String>,
+    /// Luces del lado nocturno (ciudades); solo se ven donde no llega el sol.
+    pub emissive_map: Option<String>,
+    /// Cuánto inclinan el relieve los mapas (0 = liso).
+    pub bump_strength: f32,
+    /// Exponente de Blinn-Phong: más alto = reflejo más chico y nítido.
+    pub shininess: f32,
+}
+
+impl SurfaceMaps {
+    pub fn new() -> Self {
+        Self {
+            normal_map: None,
+            height_map: None,
+            specular_map: None,
+            emissive_map: None,
+            bump_strength: 1.0,
+            shininess: 40.0,
+        }
+    }
+}
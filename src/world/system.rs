@@ -2,15 +2,22 @@ use std::f32::consts::PI;
 
 use crate::camera::Camera;
 use crate::math::Vec3;
-use crate::renderer::Renderer;
+use crate::renderer::{Occluder, Renderer};
 
-use super::{Body, BodyKind};
+use super::{AsteroidBelt, Body, BodyKind, MeshInstance};
 
 pub struct SolarSystem {
     pub bodies: Vec<Body>,
+    /// Mallas ubicadas en la escena (estaciones, naves, asteroides).
+    pub meshes: Vec<MeshInstance>,
+    /// Cinturones de asteroides (miles de partículas livianas cada uno).
+    pub belts: Vec<AsteroidBelt>,
+    /// Tiempo de simulación acumulado (segundos).
+    pub time: f32,
 }
 
 impl SolarSystem {
+    #[allow(clippy::vec_init_then_push)]
     pub fn new_demo() -> Self {
         let mut bodies = Vec::new();
 
@@ -24,6 +31,17 @@ impl SolarSystem {
             orbit_speed: 0.0,
             angle: 0.0,
             parent: None,
+            orbit: None,
+            texture: Some("textures/sun.jpg".into()),
+            shape: None,
+            shader: None,
+            terrain: None,
+            ring: None,
+            atmosphere: None,
+            surface: None,
+            clouds: None,
+            tail: None,
+            spin: None,
         });
 
         // Planeta 1 (1)
@@ -36,6 +54,17 @@ impl SolarSystem {
             orbit_speed: 0.12,
             angle: 0.0,
             parent: Some(0),
+            orbit: None,
+            texture: Some("textures/mercury.jpg".into()),
+            shape: None,
+            shader: None,
+            terrain: None,
+            ring: None,
+            atmosphere: None,
+            surface: None,
+            clouds: None,
+            tail: None,
+            spin: None,
         });
 
         // Planeta 2 (2)
@@ -48,6 +77,17 @@ impl SolarSystem {
             orbit_speed: 0.32,
             angle: PI / 3.0,
             parent: Some(0),
+            orbit: None,
+            texture: Some("textures/venus.jpg".into()),
+            shape: None,
+            shader: None,
+            terrain: None,
+            ring: None,
+            atmosphere: None,
+            surface: None,
+            clouds: None,
+            tail: None,
+            spin: None,
         });
 
         // Planeta 3 (3)
@@ -60,6 +100,17 @@ impl SolarSystem {
             orbit_speed: 0.54,
             angle: PI / 2.0,
             parent: Some(0),
+            orbit: None,
+            texture: Some("textures/earth.jpg".into()),
+            shape: None,
+            shader: None,
+            terrain: None,
+            ring: None,
+            atmosphere: None,
+            surface: None,
+            clouds: None,
+            tail: None,
+            spin: None,
         });
 
         // Luna de Verdania (4)
@@ -72,6 +123,17 @@ impl SolarSystem {
             orbit_speed: 2.0,
             angle: PI / 4.0,
             parent: Some(3),
+            orbit: None,
+            texture: Some("textures/moon.jpg".into()),
+            shape: None,
+            shader: None,
+            terrain: None,
+            ring: None,
+            atmosphere: None,
+            surface: None,
+            clouds: None,
+            tail: None,
+            spin: None,
         });
 
         bodies.push(Body {
@@ -83,26 +145,69 @@ impl SolarSystem {
             orbit_speed: 1.0,
             angle: PI / 4.0,
             parent: Some(0),
+            orbit: None,
+            texture: Some("textures/mars.jpg".into()),
+            shape: None,
+            shader: None,
+            terrain: None,
+            ring: None,
+            atmosphere: None,
+            surface: None,
+            clouds: None,
+            tail: None,
+            spin: None,
         });
 
+        SolarSystem::from_bodies(bodies)
+    }
 
-        SolarSystem { bodies }
+    pub fn from_bodies(bodies: Vec<Body>) -> Self {
+        SolarSystem {
+            bodies,
+            meshes: Vec::new(),
+            belts: Vec::new(),
+            time: 0.0,
+        }
     }
 
     pub fn update(&mut self, dt: f32) {
+        self.time += dt;
         for b in &mut self.bodies {
             b.update(dt);
         }
+        for m in &mut self.meshes {
+            m.update(dt);
+        }
+        for belt in &mut self.belts {
+            belt.update(self.time);
+        }
+    }
+
+    /// Avanza la simulación hasta `time`; sirve para que un sistema recién
+    /// recargado quede en el mismo instante que el anterior.
+    pub fn advance_to(&mut self, time: f32) {
+        let dt = time - self.time;
+        if dt > 0.0 {
+            self.update(dt);
+        }
     }
 
     /// Posición global del cuerpo i
     pub fn body_position(&self, index: usize) -> Vec3 {
         let b = &self.bodies[index];
 
+        if let Some(orbit) = &b.orbit {
+            let center = match b.parent {
+                None => Vec3::zero(),
+                Some(parent_idx) => self.body_position(parent_idx),
+            };
+            return center + orbit.position_at(b.angle);
+        }
+
         match b.parent {
             None => match b.kind {
                 BodyKind::Star => Vec3::zero(),
-                BodyKind::Planet | BodyKind::Moon => {
+                BodyKind::Planet | BodyKind::Moon | BodyKind::Asteroid | BodyKind::Comet => {
                     let x = b.orbit_radius * b.angle.cos();
                     let z = b.orbit_radius * b.angle.sin();
                     Vec3::new(x, 0.0, z)
@@ -121,30 +226,83 @@ impl SolarSystem {
         }
     }
 
-    /// Posición en pantalla + radio del cuerpo `index`, para dibujar la esfera texturizada
-    pub fn project_body(
-        &self,
-        index: usize,
-        renderer: &Renderer,
-        camera: &Camera,
-    ) -> Option<((i32, i32), i32)> {
+    /// Posición global de la malla i
+    pub fn mesh_position(&self, index: usize) -> Vec3 {
+        let m = &self.meshes[index];
+        let center = match m.parent {
+            None => Vec3::zero(),
+            Some(parent_idx) => self.body_position(parent_idx),
+        };
+        center + Vec3::new(m.orbit_radius * m.angle.cos(), 0.0, m.orbit_radius * m.angle.sin())
+    }
+
+    /// Velocidad orbital del cuerpo i respecto de su padre.
+    pub fn body_velocity(&self, index: usize) -> Vec3 {
+        const DT: f32 = 0.01;
         let b = &self.bodies[index];
-        let center_world = self.body_position(index);
-
-        if let Some((sx, sy)) = renderer.project_point(center_world, camera) {
-            let sample_world = center_world + Vec3::new(b.radius, 0.0, 0.0);
-            let radius_px = if let Some((sx2, sy2)) = renderer.project_point(sample_world, camera) {
-                let dx = (sx2 - sx) as f32;
-                let dy = (sy2 - sy) as f32;
-                let r = (dx * dx + dy * dy).sqrt() as i32;
-                if r < 2 { 2 } else { r }
-            } else {
-                4
-            };
+        match &b.orbit {
+            Some(orbit) => (orbit.position_at(b.angle + b.orbit_speed * DT) - orbit.position_at(b.angle)) / DT,
+            None => Vec3::new(-b.angle.sin(), 0.0, b.angle.cos()) * (b.orbit_radius * b.orbit_speed),
+        }
+    }
 
-            Some(((sx, sy), radius_px))
-        } else {
-            None
+    /// Posición de la estrella más cercana a `point`.
+    pub fn nearest_star(&self, point: Vec3) -> Option<Vec3> {
+        (0..self.bodies.len())
+            .filter(|&i| matches!(self.bodies[i].kind, BodyKind::Star))
+            .map(|i| self.body_position(i))
+            .min_by(|a, b| (*a - point).length().partial_cmp(&(*b - point).length()).unwrap())
+    }
+
+    /// Centro del cinturón i (la posición de su cuerpo padre).
+    pub fn belt_center(&self, index: usize) -> Vec3 {
+        match self.belts[index].parent {
+            None => Vec3::zero(),
+            Some(parent_idx) => self.body_position(parent_idx),
+        }
+    }
+
+    /// Cinturón cuya región contiene `point`, si hay alguno.
+    pub fn belt_at(&self, point: Vec3) -> Option<&AsteroidBelt> {
+        (0..self.belts.len())
+            .find(|&i| self.belts[i].may_contain(point - self.belt_center(i), 0.0))
+            .map(|i| &self.belts[i])
+    }
+
+    /// Posición de la primera estrella (la fuente de luz de la escena).
+    pub fn light_position(&self) -> Vec3 {
+        self.bodies
+            .iter()
+            .position(|b| matches!(b.kind, BodyKind::Star))
+            .map(|i| self.body_position(i))
+            .unwrap_or_else(Vec3::zero)
+    }
+
+    /// Radio de la primera estrella (para la penumbra de los eclipses).
+    pub fn light_radius(&self) -> f32 {
+        self.bodies
+            .iter()
+            .find(|b| matches!(b.kind, BodyKind::Star))
+            .map_or(0.0, |b| b.radius)
+    }
+
+    /// Cuerpos que pueden tapar la luz de la estrella (todos menos las estrellas).
+    pub fn occluders(&self) -> Vec<Occluder> {
+        self.bodies
+            .iter()
+            .enumerate()
+            .filter(|(_, b)| !matches!(b.kind, BodyKind::Star))
+            .map(|(i, b)| Occluder {
+                center: self.body_position(i),
+                radius: b.radius,
+            })
+            .collect()
+    }
+
+    /// Dibuja las partículas de los cinturones con z-buffer (después de los cuerpos).
+    pub fn render_belts(&self, renderer: &mut Renderer, camera: &Camera) {
+        for (i, belt) in self.belts.iter().enumerate() {
+            belt.render(renderer, camera, self.belt_center(i));
         }
     }
 
@@ -155,7 +313,7 @@ impl SolarSystem {
 
         for b in &self.bodies {
             match b.kind {
-                BodyKind::Planet | BodyKind::Moon => {
+                BodyKind::Planet | BodyKind::Moon | BodyKind::Asteroid | BodyKind::Comet => {
                     if b.orbit_radius <= 0.0 {
                         continue;
                     }
@@ -165,20 +323,26 @@ impl SolarSystem {
                         Some(parent_idx) => self.body_position(parent_idx),
                     };
 
-                    let segments = 64;
+                    let segments = if b.orbit.is_some() { 160 } else { 64 };
                     let mut prev: Option<(i32, i32)> = None;
 
                     for s in 0..=segments {
                         let t = s as f32 / segments as f32 * 2.0 * PI;
-                        let x = center_world.x + b.orbit_radius * t.cos();
-                        let z = center_world.z + b.orbit_radius * t.sin();
-                        let world = Vec3::new(x, center_world.y, z);
+                        let world = match &b.orbit {
+                            // Se recorre en anomalía excéntrica para que el perihelio no quede con pocos puntos
+                            Some(orbit) => center_world + orbit.position_at(t - orbit.eccentricity * t.sin()),
+                            None => {
+                                let x = center_world.x + b.orbit_radius * t.cos();
+                                let z = center_world.z + b.orbit_radius * t.sin();
+                                Vec3::new(x, center_world.y, z)
+                            }
+                        };
 
                         if let Some(screen) = renderer.project_point(world, camera) {
                             if let Some(prev_pt) = prev {
                                 let col = match b.kind {
                                     BodyKind::Planet => orbit_color_planet,
-                                    BodyKind::Moon => orbit_color_moon,
+                                    BodyKind::Moon | BodyKind::Asteroid | BodyKind::Comet => orbit_color_moon,
                                     _ => orbit_color_planet,
                                 };
                                 renderer.draw_line(prev_pt, screen, col);